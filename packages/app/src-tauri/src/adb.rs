@@ -1,7 +1,13 @@
+use crate::cdp::CdpTarget;
+use futures_util::future::join_all;
 use serde::{Deserialize, Serialize};
 use specta::Type;
-use tauri::{AppHandle, Runtime};
-use tauri_plugin_shell::{process::Output, ShellExt};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_shell::process::{Command, CommandChild, CommandEvent, Output};
+use tauri_plugin_shell::ShellExt;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -10,16 +16,185 @@ pub enum AdbError {
     ExecutionFailed(String),
     #[error("ADB command failed with output: {0}")]
     CommandFailed(String),
+    #[error("Device is offline")]
+    DeviceOffline,
+    #[error("No devices/emulators found")]
+    NoDevices,
+    #[error(
+        "Device unauthorized. Accept the \"Allow USB debugging?\" RSA key prompt on the device"
+    )]
+    DeviceUnauthorized,
+    #[error("No permission to access the device; check udev rules or reconnect it")]
+    DeviceNoPermissions,
+    #[error("Device not found: {0}")]
+    DeviceNotFound(String),
+    #[error("More than one device/emulator connected. Select a device first")]
+    MultipleDevices,
+    #[error("Cannot bind listener: {0}")]
+    CannotBindListener(String),
+    #[error("No device ID specified. Select a device first")]
+    MissingDeviceId,
+    #[error("No debuggable WebViews found; the app must call WebView.setWebContentsDebuggingEnabled(true)")]
+    NoDebuggableWebViews,
+    #[error("Process {0} not found (the app may have been killed)")]
+    ProcessNotFound(u32),
+    #[error("Wireless connection failed: {0}")]
+    WirelessConnectFailed(String),
+    #[error(
+        "Pairing rejected: {0}. Check the pairing code and port shown under Wireless debugging"
+    )]
+    PairingRejected(String),
+}
+
+impl AdbError {
+    /// Classify ADB error output into a specific error kind.
+    /// Matches known substrings anywhere in the (possibly multi-line) output,
+    /// falling back to `CommandFailed` with the raw text.
+    pub fn from_output_text(text: &str) -> Self {
+        let lower = text.to_lowercase();
+
+        if lower.contains("device offline") {
+            AdbError::DeviceOffline
+        } else if lower.contains("no devices/emulators found") {
+            AdbError::NoDevices
+        } else if lower.contains("device unauthorized") {
+            AdbError::DeviceUnauthorized
+        } else if lower.contains("more than one device") {
+            AdbError::MultipleDevices
+        } else if lower.contains("insufficient permissions for device")
+            || lower.contains("no permissions")
+        {
+            AdbError::DeviceNoPermissions
+        } else if lower.contains("cannot bind listener") {
+            AdbError::CannotBindListener(text.trim().to_string())
+        } else if let Some(serial) = missing_device_serial(text) {
+            AdbError::DeviceNotFound(serial)
+        } else {
+            AdbError::CommandFailed(text.to_string())
+        }
+    }
+}
+
+/// Serial from adb's "device '<serial>' not found"
+fn missing_device_serial(text: &str) -> Option<String> {
+    let start = text.find("device '")? + "device '".len();
+    let rest = &text[start..];
+    let end = rest.find("' not found")?;
+    Some(rest[..end].to_string())
+}
+
+/// Build a classified error from a failed command's stderr
+fn command_error(output: &Output) -> AdbError {
+    AdbError::from_output_text(&String::from_utf8_lossy(&output.stderr))
+}
+
+/// Reject empty device IDs, which would make ADB target "the only device"
+/// and fail confusingly when several devices are attached.
+fn require_device_id(device_id: &str) -> Result<(), AdbError> {
+    if device_id.trim().is_empty() {
+        return Err(AdbError::MissingDeviceId);
+    }
+    Ok(())
+}
+
+/// Port the adb server listens on unless told otherwise
+pub const DEFAULT_ADB_SERVER_PORT: u16 = 5037;
+
+/// An adb server on another machine (`adb -H <host> -P <port>`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct AdbServer {
+    pub host: String,
+    pub port: u16,
+}
+
+/// Which adb binary to run and which server it talks to; registered as its
+/// own Tauri managed state
+#[derive(Debug, Default)]
+pub struct AdbConfig {
+    /// Custom adb executable; None prefers system adb, then the sidecar
+    path: RwLock<Option<String>>,
+    /// Remote adb server; None uses the local one
+    server: RwLock<Option<AdbServer>>,
+}
+
+impl AdbConfig {
+    pub fn new(path: Option<String>, server: Option<AdbServer>) -> Self {
+        Self {
+            path: RwLock::new(path),
+            server: RwLock::new(server),
+        }
+    }
+
+    pub fn path(&self) -> Option<String> {
+        self.path.read().unwrap().clone()
+    }
+
+    pub fn set_path(&self, path: Option<String>) {
+        *self.path.write().unwrap() = path;
+    }
+
+    pub fn server(&self) -> Option<AdbServer> {
+        self.server.read().unwrap().clone()
+    }
+
+    pub fn set_server(&self, server: Option<AdbServer>) {
+        *self.server.write().unwrap() = server;
+    }
+}
+
+/// Command for the user-configured adb binary, if one is set
+fn configured_adb<R: Runtime>(app: &AppHandle<R>) -> Option<Command> {
+    let path = app.try_state::<AdbConfig>()?.path()?;
+    Some(app.shell().command(path))
+}
+
+fn configured_server<R: Runtime>(app: &AppHandle<R>) -> Option<AdbServer> {
+    app.try_state::<AdbConfig>()?.server()
+}
+
+/// `args` prefixed with `-H <host> -P <port>` when a remote server is set
+fn with_server_args<R: Runtime>(app: &AppHandle<R>, args: &[&str]) -> Vec<String> {
+    let mut full = Vec::with_capacity(args.len() + 4);
+    if let Some(server) = configured_server(app) {
+        full.extend([
+            "-H".to_string(),
+            server.host,
+            "-P".to_string(),
+            server.port.to_string(),
+        ]);
+    }
+    full.extend(args.iter().map(|arg| arg.to_string()));
+    full
+}
+
+/// Host on which `adb forward` listens: ports are bound by the adb server,
+/// so with a remote server the CDP endpoint lives on that machine. The
+/// server must have been started with `adb -a` for forwards to be
+/// reachable from here.
+pub fn forward_host<R: Runtime>(app: &AppHandle<R>) -> String {
+    configured_server(app)
+        .map(|server| server.host)
+        .unwrap_or_else(|| "localhost".to_string())
 }
 
 /// Execute an ADB command, preferring system ADB over bundled sidecar.
-/// Tries system ADB first to reuse existing ADB server, falls back to bundled sidecar.
+/// A configured custom adb is used exclusively. Otherwise tries system ADB
+/// first to reuse existing ADB server, falls back to bundled sidecar.
 async fn run_adb_command<R: Runtime>(
     app: &AppHandle<R>,
     args: &[&str],
 ) -> Result<Output, AdbError> {
+    let args = with_server_args(app, args);
+    if let Some(command) = configured_adb(app) {
+        return command
+            .args(args)
+            .output()
+            .await
+            .map_err(|e| AdbError::ExecutionFailed(e.to_string()));
+    }
+
     // Try system ADB first (reuses existing ADB server if running)
-    if let Ok(output) = app.shell().command("system-adb").args(args).output().await {
+    if let Ok(output) = app.shell().command("system-adb").args(&args).output().await {
         return Ok(output);
     }
 
@@ -33,17 +208,67 @@ async fn run_adb_command<R: Runtime>(
         .map_err(|e| AdbError::ExecutionFailed(e.to_string()))
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+/// Connection state reported by `adb devices`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceStatus {
+    /// Connected and authorized; the only state ADB commands work in
+    Device,
+    /// Waiting for the user to accept the RSA key prompt
+    Unauthorized,
+    Offline,
+    /// The host user can't open the USB device (udev rules on Linux)
+    NoPermissions,
+    Recovery,
+    /// Any other state (bootloader, sideload, authorizing, ...)
+    Unknown,
+}
+
+impl DeviceStatus {
+    /// Parse the state column of `adb devices`; "no permissions" spans
+    /// two words, so `next` is the word after `state`
+    pub fn from_adb(state: &str, next: Option<&str>) -> Self {
+        match (state, next) {
+            ("device", _) => DeviceStatus::Device,
+            ("unauthorized", _) => DeviceStatus::Unauthorized,
+            ("offline", _) => DeviceStatus::Offline,
+            ("no", Some(next)) if next.starts_with("permissions") => DeviceStatus::NoPermissions,
+            ("recovery", _) => DeviceStatus::Recovery,
+            _ => DeviceStatus::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
 pub struct Device {
     pub id: String,
+    /// AVD name for emulators, otherwise the `model:` field
     pub name: String,
-    pub status: String,
+    pub status: DeviceStatus,
+    pub is_emulator: bool,
+}
+
+/// What is serving a devtools socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WebViewKind {
+    /// An app's WebView (`webview_devtools_remote_<pid>`)
+    WebView,
+    /// Chrome's main browser socket (`chrome_devtools_remote`) or another
+    /// Chromium browser's `<name>_devtools_remote`
+    Chrome,
+    /// A second Chrome process with a pid-suffixed socket
+    /// (`chrome_devtools_remote_<pid>`), such as one hosting Custom Tabs
+    CustomTabs,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct WebView {
     pub socket_name: String,
-    pub pid: u32,
+    pub kind: WebViewKind,
+    /// None when the owning process couldn't be found (browser sockets
+    /// carry no pid)
+    pub pid: Option<u32>,
     pub package_name: Option<String>,
 }
 
@@ -51,12 +276,25 @@ pub async fn list_devices<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<Device>,
     let output = run_adb_command(app, &["devices", "-l"]).await?;
 
     if !output.status.success() {
-        return Err(AdbError::CommandFailed(
-            String::from_utf8_lossy(&output.stderr).to_string(),
-        ));
+        return Err(command_error(&output));
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut devices = parse_device_list(&String::from_utf8_lossy(&output.stdout));
+
+    // Emulators all report the same generic model; the AVD name tells them apart
+    for device in &mut devices {
+        if device.is_emulator && device.status == DeviceStatus::Device {
+            if let Some(avd_name) = emulator_avd_name(app, &device.id).await {
+                device.name = avd_name;
+            }
+        }
+    }
+
+    Ok(devices)
+}
+
+/// Parse `adb devices -l` output
+fn parse_device_list(stdout: &str) -> Vec<Device> {
     let mut devices = Vec::new();
 
     for line in stdout.lines().skip(1) {
@@ -68,7 +306,7 @@ pub async fn list_devices<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<Device>,
         let parts: Vec<&str> = line.split_whitespace().collect();
         if parts.len() >= 2 {
             let id = parts[0].to_string();
-            let status = parts[1].to_string();
+            let status = DeviceStatus::from_adb(parts[1], parts.get(2).copied());
 
             // Extract device name from "model:" field if present
             let name = parts
@@ -77,80 +315,254 @@ pub async fn list_devices<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<Device>,
                 .map(|p| p.trim_start_matches("model:").to_string())
                 .unwrap_or_else(|| id.clone());
 
-            devices.push(Device { id, name, status });
+            let is_emulator = id.starts_with("emulator-");
+
+            devices.push(Device {
+                id,
+                name,
+                status,
+                is_emulator,
+            });
         }
     }
 
-    Ok(devices)
+    devices
+}
+
+/// AVD name of a running emulator, e.g. "Pixel 7 API 34" for `Pixel_7_API_34`
+async fn emulator_avd_name<R: Runtime>(app: &AppHandle<R>, device_id: &str) -> Option<String> {
+    let output = run_adb_command(app, &["-s", device_id, "emu", "avd", "name"])
+        .await
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    // The console answers with the name followed by an "OK" line
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && *line != "OK")
+        .map(|name| name.replace('_', " "))
+}
+
+/// Poll interval used when `adb track-devices` isn't available
+const DEVICE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Kills the wrapped child process when dropped, e.g. when the watcher
+/// task is aborted
+struct KillOnDrop(Option<CommandChild>);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        if let Some(child) = self.0.take() {
+            let _ = child.kill();
+        }
+    }
+}
+
+/// Watch for devices connecting, disconnecting or changing status and call
+/// `on_change` with the full device list each time it changes (and once at
+/// start). Uses `adb track-devices` as the change signal, falling back to
+/// polling `adb devices` if it can't be spawned or exits. Runs until the
+/// task is aborted.
+pub async fn watch_devices<R: Runtime, F>(app: AppHandle<R>, mut on_change: F)
+where
+    F: FnMut(Vec<Device>) + Send,
+{
+    let mut last: Option<Vec<Device>> = None;
+    let mut refresh = |devices: Result<Vec<Device>, AdbError>| {
+        if let Ok(devices) = devices {
+            if last.as_ref() != Some(&devices) {
+                last = Some(devices.clone());
+                on_change(devices);
+            }
+        }
+    };
+
+    let track_args = with_server_args(&app, &["track-devices"]);
+    let tracker = match configured_adb(&app) {
+        Some(command) => command.args(&track_args).spawn().map_err(|e| e.to_string()),
+        None => app
+            .shell()
+            .command("system-adb")
+            .args(&track_args)
+            .spawn()
+            .or_else(|_| {
+                app.shell()
+                    .sidecar("adb")
+                    .map_err(|e| e.to_string())?
+                    .args(&track_args)
+                    .spawn()
+                    .map_err(|e| e.to_string())
+            }),
+    };
+
+    match tracker {
+        Ok((mut events, child)) => {
+            let _child = KillOnDrop(Some(child));
+            // Each message is a length-prefixed device list; re-run
+            // `devices -l` on every one to get model names as well
+            while let Some(event) = events.recv().await {
+                match event {
+                    CommandEvent::Stdout(_) => refresh(list_devices(&app).await),
+                    CommandEvent::Terminated(_) => break,
+                    _ => {}
+                }
+            }
+            tracing::warn!("adb track-devices exited; polling for device changes");
+        }
+        Err(e) => tracing::warn!("adb track-devices unavailable ({}); polling instead", e),
+    }
+
+    loop {
+        refresh(list_devices(&app).await);
+        tokio::time::sleep(DEVICE_POLL_INTERVAL).await;
+    }
+}
+
+/// Get the sole connected device, for single-device setups that don't
+/// need an explicit selection.
+pub async fn get_default_device<R: Runtime>(app: &AppHandle<R>) -> Result<Device, AdbError> {
+    let mut devices: Vec<Device> = list_devices(app)
+        .await?
+        .into_iter()
+        .filter(|d| d.status == DeviceStatus::Device)
+        .collect();
+
+    match devices.len() {
+        0 => Err(AdbError::NoDevices),
+        1 => Ok(devices.remove(0)),
+        _ => Err(AdbError::MultipleDevices),
+    }
 }
 
 pub async fn list_webviews<R: Runtime>(
     app: &AppHandle<R>,
     device_id: &str,
 ) -> Result<Vec<WebView>, AdbError> {
+    require_device_id(device_id)?;
+
     let output = run_adb_command(app, &["-s", device_id, "shell", "cat", "/proc/net/unix"]).await?;
 
     if !output.status.success() {
-        return Err(AdbError::CommandFailed(
-            String::from_utf8_lossy(&output.stderr).to_string(),
-        ));
+        return Err(command_error(&output));
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let mut webviews = Vec::new();
-    let mut seen_pids = std::collections::HashSet::new();
+    let mut seen_sockets = std::collections::HashSet::new();
 
     for line in stdout.lines() {
-        if let Some(socket_name) = line.split_whitespace().last() {
-            let socket_name = socket_name.trim_start_matches('@');
-
-            // Handle WebView sockets: webview_devtools_remote_<pid>
-            if socket_name.starts_with("webview_devtools_remote_") {
-                if let Some(pid_str) = socket_name.strip_prefix("webview_devtools_remote_") {
-                    if let Ok(pid) = pid_str.parse::<u32>() {
-                        if seen_pids.insert(pid) {
-                            webviews.push(WebView {
-                                socket_name: socket_name.to_string(),
-                                pid,
-                                package_name: None,
-                            });
-                        }
-                    }
-                }
-            }
-            // Handle Chrome socket: chrome_devtools_remote (no PID suffix)
-            else if socket_name == "chrome_devtools_remote" {
-                // Chrome uses a fixed socket name, use 0 as placeholder PID
-                // We'll get the actual PID from cmdline later
-                if !seen_pids.contains(&0) {
-                    seen_pids.insert(0);
-                    webviews.push(WebView {
-                        socket_name: socket_name.to_string(),
-                        pid: 0, // Will be resolved later
-                        package_name: Some("com.android.chrome".to_string()),
-                    });
-                }
-            }
+        let Some(socket_name) = line.split_whitespace().last() else {
+            continue;
+        };
+        let socket_name = socket_name.trim_start_matches('@');
+        let Some((kind, pid)) = classify_socket(socket_name) else {
+            continue;
+        };
+        // A listening socket can appear once per open connection
+        if !seen_sockets.insert(socket_name.to_string()) {
+            continue;
         }
+
+        // Chrome's own socket names no process; assume stable Chrome
+        let package_name = (socket_name == CHROME_SOCKET).then(|| CHROME_PACKAGE.to_string());
+        webviews.push(WebView {
+            socket_name: socket_name.to_string(),
+            kind,
+            pid,
+            package_name,
+        });
     }
 
-    // Try to get package names for each PID
-    for webview in &mut webviews {
-        // For Chrome (pid=0), try to get actual PID
-        if webview.pid == 0 && webview.package_name == Some("com.android.chrome".to_string()) {
-            if let Ok(pid) = get_pid_for_package(app, device_id, "com.android.chrome").await {
-                webview.pid = pid;
+    // ADB can't query the debugging flag directly; no devtools sockets means
+    // no running app has enabled WebView debugging
+    if webviews.is_empty() {
+        return Err(AdbError::NoDebuggableWebViews);
+    }
+
+    // Resolve package names (or Chrome's PID) concurrently; each lookup is a
+    // separate adb shell round trip
+    join_all(webviews.iter_mut().map(|webview| async move {
+        match (webview.pid, &webview.package_name) {
+            (Some(pid), None) => {
+                if let Ok(pkg) = get_package_name(app, device_id, pid).await {
+                    webview.package_name = Some(pkg);
+                }
             }
-        } else if webview.package_name.is_none() {
-            if let Ok(pkg) = get_package_name(app, device_id, webview.pid).await {
-                webview.package_name = Some(pkg);
+            (None, Some(package)) => {
+                webview.pid = get_pid_for_package(app, device_id, package).await.ok();
             }
+            _ => {}
         }
-    }
+    }))
+    .await;
 
     Ok(webviews)
 }
 
+/// Socket Chrome's browser process listens on
+const CHROME_SOCKET: &str = "chrome_devtools_remote";
+
+const CHROME_PACKAGE: &str = "com.android.chrome";
+
+/// Kind and (when encoded) PID of a devtools socket, or None for sockets
+/// that aren't devtools endpoints
+fn classify_socket(socket_name: &str) -> Option<(WebViewKind, Option<u32>)> {
+    if let Some(pid) = socket_pid(socket_name) {
+        return Some((WebViewKind::WebView, Some(pid)));
+    }
+    if socket_name.ends_with("_devtools_remote") && !socket_name.starts_with("webview_") {
+        return Some((WebViewKind::Chrome, None));
+    }
+    socket_name
+        .strip_prefix(CHROME_SOCKET)
+        .and_then(|rest| rest.strip_prefix('_'))
+        .and_then(|pid| pid.parse().ok())
+        .map(|pid| (WebViewKind::CustomTabs, Some(pid)))
+}
+
+/// PID encoded in a `webview_devtools_remote_<pid>` socket name
+pub fn socket_pid(socket_name: &str) -> Option<u32> {
+    socket_name
+        .strip_prefix("webview_devtools_remote_")
+        .and_then(|pid| pid.parse().ok())
+}
+
+/// A CDP target annotated with the WebView process serving it
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct EnrichedTarget {
+    pub target: CdpTarget,
+    pub socket_name: String,
+    pub pid: Option<u32>,
+    pub package_name: Option<String>,
+}
+
+/// Attach process info to the targets listed by the port forwarded to
+/// `socket_name`. The socket is matched against `webviews` by name, then by
+/// the PID in its name; targets keep `None` fields if neither matches.
+pub fn match_webview_to_target(
+    webviews: &[WebView],
+    socket_name: &str,
+    targets: Vec<CdpTarget>,
+) -> Vec<EnrichedTarget> {
+    let socket_name = socket_name.trim_start_matches('@');
+    let pid = classify_socket(socket_name).and_then(|(_, pid)| pid);
+    let webview = webviews
+        .iter()
+        .find(|w| w.socket_name == socket_name)
+        .or_else(|| pid.and_then(|pid| webviews.iter().find(|w| w.pid == Some(pid))));
+
+    targets
+        .into_iter()
+        .map(|target| EnrichedTarget {
+            target,
+            socket_name: socket_name.to_string(),
+            pid: webview.and_then(|w| w.pid).or(pid),
+            package_name: webview.and_then(|w| w.package_name.clone()),
+        })
+        .collect()
+}
+
 async fn get_package_name<R: Runtime>(
     app: &AppHandle<R>,
     device_id: &str,
@@ -171,7 +583,7 @@ async fn get_package_name<R: Runtime>(
     Err(AdbError::CommandFailed("Could not get package name".into()))
 }
 
-async fn get_pid_for_package<R: Runtime>(
+pub async fn get_pid_for_package<R: Runtime>(
     app: &AppHandle<R>,
     device_id: &str,
     package: &str,
@@ -197,31 +609,113 @@ pub async fn forward_port<R: Runtime>(
     local_port: u16,
     socket_name: &str,
 ) -> Result<(), AdbError> {
+    require_device_id(device_id)?;
+
+    // A missing, offline or unauthorized device is told apart by the
+    // forward's own stderr, without listing devices first
     let tcp_arg = format!("tcp:{}", local_port);
     let socket_arg = format!("localabstract:{}", socket_name);
     let output = run_adb_command(app, &["-s", device_id, "forward", &tcp_arg, &socket_arg]).await?;
 
     if !output.status.success() {
-        return Err(AdbError::CommandFailed(
-            String::from_utf8_lossy(&output.stderr).to_string(),
-        ));
+        return Err(command_error(&output));
     }
 
     Ok(())
 }
 
+/// How many free ports to try when another process grabs the probed one
+/// before adb binds it
+const AUTO_FORWARD_ATTEMPTS: u32 = 3;
+
+/// Forward to a free local port chosen by the OS and return that port.
+/// The port is probed by binding 127.0.0.1:0 and released right before
+/// adb binds it, so a clash is still possible and retried.
+pub async fn forward_port_auto<R: Runtime>(
+    app: &AppHandle<R>,
+    device_id: &str,
+    socket_name: &str,
+) -> Result<u16, AdbError> {
+    let mut last_error = None;
+    for _ in 0..AUTO_FORWARD_ATTEMPTS {
+        let local_port = free_local_port()?;
+        match forward_port(app, device_id, local_port, socket_name).await {
+            Ok(()) => return Ok(local_port),
+            Err(e @ AdbError::CannotBindListener(_)) => last_error = Some(e),
+            Err(e) => return Err(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| AdbError::CannotBindListener("no free port".into())))
+}
+
+fn free_local_port() -> Result<u16, AdbError> {
+    std::net::TcpListener::bind(("127.0.0.1", 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|e| AdbError::ExecutionFailed(format!("Failed to find a free port: {}", e)))
+}
+
+/// One line of `adb forward --list`
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PortForward {
+    pub device_id: String,
+    /// Local side, e.g. `tcp:9222`
+    pub local: String,
+    /// Device side, e.g. `localabstract:webview_devtools_remote_1234`
+    pub remote: String,
+    /// Port from a `tcp:` local side
+    pub local_port: Option<u16>,
+    /// Socket from a `localabstract:` remote side
+    pub socket_name: Option<String>,
+}
+
+/// List active forwards, optionally only those of one device
+pub async fn list_forwards<R: Runtime>(
+    app: &AppHandle<R>,
+    device_id: Option<&str>,
+) -> Result<Vec<PortForward>, AdbError> {
+    let output = run_adb_command(app, &["forward", "--list"]).await?;
+
+    if !output.status.success() {
+        return Err(command_error(&output));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_forward_list(&stdout)
+        .into_iter()
+        .filter(|f| device_id.is_none_or(|id| f.device_id == id))
+        .collect())
+}
+
+fn parse_forward_list(output: &str) -> Vec<PortForward> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let (device_id, local, remote) = (parts.next()?, parts.next()?, parts.next()?);
+            Some(PortForward {
+                device_id: device_id.to_string(),
+                local: local.to_string(),
+                remote: remote.to_string(),
+                local_port: local.strip_prefix("tcp:").and_then(|p| p.parse().ok()),
+                socket_name: remote.strip_prefix("localabstract:").map(str::to_string),
+            })
+        })
+        .collect()
+}
+
 pub async fn remove_forward<R: Runtime>(
     app: &AppHandle<R>,
     device_id: &str,
     local_port: u16,
 ) -> Result<(), AdbError> {
+    require_device_id(device_id)?;
+
     let tcp_arg = format!("tcp:{}", local_port);
     let output = run_adb_command(app, &["-s", device_id, "forward", "--remove", &tcp_arg]).await?;
 
     if !output.status.success() {
-        return Err(AdbError::CommandFailed(
-            String::from_utf8_lossy(&output.stderr).to_string(),
-        ));
+        return Err(command_error(&output));
     }
 
     Ok(())
@@ -231,17 +725,137 @@ pub async fn remove_all_forwards<R: Runtime>(
     app: &AppHandle<R>,
     device_id: &str,
 ) -> Result<(), AdbError> {
+    require_device_id(device_id)?;
+
     let output = run_adb_command(app, &["-s", device_id, "forward", "--remove-all"]).await?;
 
     if !output.status.success() {
-        return Err(AdbError::CommandFailed(
-            String::from_utf8_lossy(&output.stderr).to_string(),
-        ));
+        return Err(command_error(&output));
+    }
+
+    Ok(())
+}
+
+/// Connect to a device over TCP/IP (`adb connect host:port`).
+/// The resulting device ID is `host:port` and works like any USB serial.
+/// adb exits 0 even when the connection fails, so the output decides.
+pub async fn connect_wireless<R: Runtime>(
+    app: &AppHandle<R>,
+    host: &str,
+    port: u16,
+) -> Result<String, AdbError> {
+    let address = format!("{}:{}", host, port);
+    let output = run_adb_command(app, &["connect", &address]).await?;
+    let text = combined_output(&output);
+
+    if !output.status.success() || !text.to_lowercase().contains("connected to") {
+        return Err(AdbError::WirelessConnectFailed(text.trim().to_string()));
+    }
+
+    Ok(address)
+}
+
+/// Pair with a device using the Android 11+ Wireless debugging pairing code.
+/// `port` is the pairing port, which differs from the connect port.
+pub async fn pair_wireless<R: Runtime>(
+    app: &AppHandle<R>,
+    host: &str,
+    port: u16,
+    code: &str,
+) -> Result<(), AdbError> {
+    let address = format!("{}:{}", host, port);
+    let output = run_adb_command(app, &["pair", &address, code]).await?;
+    let text = combined_output(&output);
+
+    // Some adb versions exit 0 after a failed pairing, so check both
+    if !output.status.success() || !text.to_lowercase().contains("successfully paired") {
+        return Err(wireless_error(&text, &address));
     }
 
     Ok(())
 }
 
+/// Disconnect a TCP/IP device (`adb disconnect host:port`)
+pub async fn disconnect_wireless<R: Runtime>(
+    app: &AppHandle<R>,
+    host: &str,
+    port: u16,
+) -> Result<(), AdbError> {
+    let address = format!("{}:{}", host, port);
+    let output = run_adb_command(app, &["disconnect", &address]).await?;
+    let text = combined_output(&output);
+
+    if !output.status.success() || !text.to_lowercase().contains("disconnected") {
+        return Err(wireless_error(&text, &address));
+    }
+
+    Ok(())
+}
+
+/// Classify the output of a failed `adb pair` or `adb disconnect`
+fn wireless_error(text: &str, address: &str) -> AdbError {
+    let lower = text.to_lowercase();
+    let message = text.trim().to_string();
+
+    if lower.contains("wrong password") {
+        AdbError::PairingRejected(message)
+    } else if lower.contains("failed to connect") || lower.contains("unable to connect") {
+        AdbError::WirelessConnectFailed(message)
+    } else if lower.contains("no such device") {
+        AdbError::DeviceNotFound(address.to_string())
+    } else {
+        AdbError::from_output_text(text)
+    }
+}
+
+/// Output of `adb version`
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AdbVersion {
+    /// Custom adb path in use; None for system adb or the bundled sidecar
+    pub custom_path: Option<String>,
+    /// Protocol version, e.g. "1.0.41"
+    pub version: Option<String>,
+    /// Platform-tools release, e.g. "34.0.5-10900879"
+    pub platform_tools: Option<String>,
+    /// Absolute path the running binary reports
+    pub installed_as: Option<String>,
+}
+
+/// Run `adb version` so users can confirm which binary is active
+pub async fn adb_version<R: Runtime>(app: &AppHandle<R>) -> Result<AdbVersion, AdbError> {
+    let output = run_adb_command(app, &["version"]).await?;
+
+    if !output.status.success() {
+        return Err(command_error(&output));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let field = |prefix: &str| {
+        stdout
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(prefix))
+            .map(|value| value.trim().to_string())
+    };
+
+    Ok(AdbVersion {
+        custom_path: app
+            .try_state::<AdbConfig>()
+            .and_then(|config| config.path()),
+        version: field("Android Debug Bridge version "),
+        platform_tools: field("Version "),
+        installed_as: field("Installed as "),
+    })
+}
+
+/// stdout and stderr together, for commands that report errors on either
+fn combined_output(output: &Output) -> String {
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
+
 /// Memory trim levels for Android's onTrimMemory callback
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub enum TrimMemoryLevel {
@@ -292,6 +906,8 @@ pub async fn send_trim_memory<R: Runtime>(
     package_name: &str,
     level: TrimMemoryLevel,
 ) -> Result<(), AdbError> {
+    require_device_id(device_id)?;
+
     let output = run_adb_command(
         app,
         &[
@@ -309,7 +925,7 @@ pub async fn send_trim_memory<R: Runtime>(
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         let stdout = String::from_utf8_lossy(&output.stdout);
-        return Err(AdbError::CommandFailed(format!("{}{}", stderr, stdout)));
+        return Err(AdbError::from_output_text(&format!("{}{}", stderr, stdout)));
     }
 
     Ok(())
@@ -320,12 +936,12 @@ pub async fn get_meminfo<R: Runtime>(
     app: &AppHandle<R>,
     device_id: &str,
 ) -> Result<MemoryInfo, AdbError> {
+    require_device_id(device_id)?;
+
     let output = run_adb_command(app, &["-s", device_id, "shell", "cat", "/proc/meminfo"]).await?;
 
     if !output.status.success() {
-        return Err(AdbError::CommandFailed(
-            String::from_utf8_lossy(&output.stderr).to_string(),
-        ));
+        return Err(command_error(&output));
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -358,3 +974,522 @@ pub async fn get_meminfo<R: Runtime>(
         cached_kb,
     })
 }
+
+/// Hardware and OS context of a device, recorded with each session so runs
+/// can be compared by SDK level and RAM tier
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DeviceProps {
+    /// `ro.product.model`
+    pub model: Option<String>,
+    /// `ro.build.version.release`, e.g. "14"
+    pub android_version: Option<String>,
+    /// `ro.build.version.sdk`, e.g. 34
+    pub sdk_level: Option<u32>,
+    /// MemTotal from /proc/meminfo
+    pub total_ram_kb: Option<u64>,
+    /// Cores the kernel can bring online, including ones parked right now
+    pub cpu_cores: Option<u32>,
+}
+
+/// Read model, Android version, SDK level, total RAM and CPU core count
+pub async fn get_device_props<R: Runtime>(
+    app: &AppHandle<R>,
+    device_id: &str,
+) -> Result<DeviceProps, AdbError> {
+    require_device_id(device_id)?;
+
+    let output = run_adb_command(app, &["-s", device_id, "shell", "getprop"]).await?;
+    if !output.status.success() {
+        return Err(command_error(&output));
+    }
+    let props = String::from_utf8_lossy(&output.stdout);
+    let prop = |name: &str| getprop_value(&props, name);
+
+    let total_ram_kb = match get_meminfo(app, device_id).await {
+        Ok(meminfo) => Some(meminfo.total_kb),
+        Err(e) => {
+            tracing::debug!("meminfo unavailable on {}: {}", device_id, e);
+            None
+        }
+    };
+
+    let cpu_cores = match run_adb_command(
+        app,
+        &[
+            "-s",
+            device_id,
+            "shell",
+            "cat",
+            "/sys/devices/system/cpu/possible",
+        ],
+    )
+    .await
+    {
+        Ok(output) if output.status.success() => {
+            count_cpu_list(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => None,
+    };
+
+    Ok(DeviceProps {
+        model: prop("ro.product.model"),
+        android_version: prop("ro.build.version.release"),
+        sdk_level: prop("ro.build.version.sdk").and_then(|sdk| sdk.parse().ok()),
+        total_ram_kb,
+        cpu_cores,
+    })
+}
+
+/// Value of `name` in `getprop` output (`[name]: [value]` lines)
+fn getprop_value(props: &str, name: &str) -> Option<String> {
+    let key = format!("[{}]:", name);
+    props
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(&key))
+        .map(|rest| rest.trim().trim_start_matches('[').trim_end_matches(']'))
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Count the CPUs in a kernel CPU list such as "0-3,6,7"
+fn count_cpu_list(list: &str) -> Option<u32> {
+    list.trim()
+        .split(',')
+        .map(|part| match part.split_once('-') {
+            Some((start, end)) => {
+                let start: u32 = start.parse().ok()?;
+                let end: u32 = end.parse().ok()?;
+                end.checked_sub(start).map(|span| span + 1)
+            }
+            None => part.parse::<u32>().ok().map(|_| 1),
+        })
+        .sum()
+}
+
+/// Read a process's resident set size (VmRSS) from /proc/[pid]/status, in KB
+pub async fn get_process_rss<R: Runtime>(
+    app: &AppHandle<R>,
+    device_id: &str,
+    pid: u32,
+) -> Result<u64, AdbError> {
+    require_device_id(device_id)?;
+
+    let status = read_proc_file(app, device_id, pid, "status").await?;
+
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|kb| kb.parse().ok())
+        .ok_or_else(|| AdbError::CommandFailed(format!("No VmRSS in /proc/{}/status", pid)))
+}
+
+/// Fallback for `_SC_CLK_TCK`; Android kernels use 100 Hz accounting
+const DEFAULT_CLOCK_TICKS: u64 = 100;
+
+/// Process CPU usage over one sampling interval
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CpuUsage {
+    pub pid: u32,
+    /// Sample end time in ms since epoch
+    pub timestamp: i64,
+    /// CPU time over wall time, in percent of one core (can exceed 100)
+    pub cpu_percent: f64,
+    /// Wall time between the two /proc reads
+    pub interval_ms: u64,
+}
+
+/// Sample a process's CPU usage from /proc/[pid]/stat.
+/// Reads utime+stime twice, `interval` apart, and converts the tick delta
+/// using the device's `_SC_CLK_TCK`. Fails with `ProcessNotFound` if the
+/// process exits before or during sampling.
+pub async fn sample_cpu<R: Runtime>(
+    app: &AppHandle<R>,
+    device_id: &str,
+    pid: u32,
+    interval: Duration,
+) -> Result<CpuUsage, AdbError> {
+    require_device_id(device_id)?;
+
+    let clock_ticks = get_clock_ticks(app, device_id).await;
+
+    let first_ticks = read_cpu_ticks(app, device_id, pid).await?;
+    let started = Instant::now();
+    tokio::time::sleep(interval).await;
+    let second_ticks = read_cpu_ticks(app, device_id, pid).await?;
+    let elapsed = started.elapsed();
+
+    let cpu_seconds = second_ticks.saturating_sub(first_ticks) as f64 / clock_ticks as f64;
+    let cpu_percent = if elapsed.is_zero() {
+        0.0
+    } else {
+        cpu_seconds / elapsed.as_secs_f64() * 100.0
+    };
+
+    Ok(CpuUsage {
+        pid,
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        cpu_percent,
+        interval_ms: elapsed.as_millis() as u64,
+    })
+}
+
+/// `_SC_CLK_TCK` per device; it is fixed for the kernel, so it is only
+/// queried once instead of on every sample
+static CLOCK_TICKS: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
+
+/// Query `_SC_CLK_TCK` on the device, falling back to 100 Hz
+async fn get_clock_ticks<R: Runtime>(app: &AppHandle<R>, device_id: &str) -> u64 {
+    let cache = CLOCK_TICKS.get_or_init(Default::default);
+    if let Some(&ticks) = cache.lock().unwrap().get(device_id) {
+        return ticks;
+    }
+
+    let queried =
+        match run_adb_command(app, &["-s", device_id, "shell", "getconf", "CLK_TCK"]).await {
+            Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+                .trim()
+                .parse()
+                .ok()
+                .filter(|&ticks| ticks > 0),
+            _ => None,
+        };
+    match queried {
+        Some(ticks) => {
+            cache.lock().unwrap().insert(device_id.to_string(), ticks);
+            ticks
+        }
+        // Not cached, so a transient failure is retried next sample
+        None => DEFAULT_CLOCK_TICKS,
+    }
+}
+
+/// Read utime+stime (in clock ticks) for a process
+async fn read_cpu_ticks<R: Runtime>(
+    app: &AppHandle<R>,
+    device_id: &str,
+    pid: u32,
+) -> Result<u64, AdbError> {
+    let stat = read_proc_file(app, device_id, pid, "stat").await?;
+
+    parse_cpu_ticks(&stat)
+        .ok_or_else(|| AdbError::CommandFailed(format!("Unexpected /proc/{}/stat format", pid)))
+}
+
+/// Read /proc/[pid]/<name>, mapping a missing file to `ProcessNotFound`
+async fn read_proc_file<R: Runtime>(
+    app: &AppHandle<R>,
+    device_id: &str,
+    pid: u32,
+    name: &str,
+) -> Result<String, AdbError> {
+    let path = format!("/proc/{}/{}", pid, name);
+    let output = run_adb_command(app, &["-s", device_id, "shell", "cat", &path]).await?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // Older adb shells report a missing file on stdout with a zero exit code
+    if stdout.contains("No such file") || stderr.contains("No such file") {
+        return Err(AdbError::ProcessNotFound(pid));
+    }
+    if !output.status.success() {
+        return Err(command_error(&output));
+    }
+
+    Ok(stdout)
+}
+
+/// Sum utime and stime (fields 14 and 15) from a /proc/[pid]/stat line.
+/// The command name (field 2) may contain spaces, so fields are counted
+/// from the closing parenthesis.
+fn parse_cpu_ticks(stat: &str) -> Option<u64> {
+    let after_comm = &stat[stat.rfind(')')? + 1..];
+    let fields: Vec<&str> = after_comm.split_whitespace().collect();
+    // fields[0] is the state (field 3), so utime/stime are at 11/12
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(utime + stime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wireless_error_maps_pair_and_disconnect_failures() {
+        let address = "192.168.1.20:37099";
+        let cases = [
+            (
+                "Enter pairing code: Failed: Wrong password or connection was dropped.\n",
+                "PairingRejected(\"Enter pairing code: Failed: Wrong password",
+            ),
+            (
+                "Failed: Unable to start pairing client.\n\
+                 failed to connect to '192.168.1.20:37099': Connection refused\n",
+                "WirelessConnectFailed(\"Failed: Unable to start pairing client.",
+            ),
+            (
+                "error: no such device '192.168.1.20:37099'\n",
+                "DeviceNotFound(\"192.168.1.20:37099\")",
+            ),
+            ("adb: more than one device/emulator\n", "MultipleDevices"),
+            (
+                "Enter pairing code: \n",
+                "CommandFailed(\"Enter pairing code",
+            ),
+        ];
+        for (output, expected) in cases {
+            let error = format!("{:?}", wireless_error(output, address));
+            assert!(error.starts_with(expected), "{:?} -> {}", output, error);
+        }
+    }
+
+    #[test]
+    fn parse_forward_list_reads_every_forward_kind() {
+        let output = "emulator-5554 tcp:9222 localabstract:webview_devtools_remote_4321\n\
+                      emulator-5554 tcp:9223 localabstract:chrome_devtools_remote\n\
+                      R58M123ABC tcp:8081 tcp:8081\n\
+                      R58M123ABC localabstract:proxy jdwp:4321\n\
+                      \n\
+                      R58M123ABC tcp:9230\n";
+        let forwards: Vec<_> = parse_forward_list(output)
+            .into_iter()
+            .map(|f| (f.device_id, f.local, f.remote, f.local_port, f.socket_name))
+            .collect();
+        assert_eq!(
+            forwards,
+            [
+                (
+                    "emulator-5554".to_string(),
+                    "tcp:9222".to_string(),
+                    "localabstract:webview_devtools_remote_4321".to_string(),
+                    Some(9222),
+                    Some("webview_devtools_remote_4321".to_string()),
+                ),
+                (
+                    "emulator-5554".to_string(),
+                    "tcp:9223".to_string(),
+                    "localabstract:chrome_devtools_remote".to_string(),
+                    Some(9223),
+                    Some("chrome_devtools_remote".to_string()),
+                ),
+                (
+                    "R58M123ABC".to_string(),
+                    "tcp:8081".to_string(),
+                    "tcp:8081".to_string(),
+                    Some(8081),
+                    None,
+                ),
+                (
+                    "R58M123ABC".to_string(),
+                    "localabstract:proxy".to_string(),
+                    "jdwp:4321".to_string(),
+                    None,
+                    None,
+                ),
+            ]
+        );
+        assert!(parse_forward_list("").is_empty());
+        assert!(parse_forward_list("\n  \n").is_empty());
+    }
+
+    #[test]
+    fn parse_cpu_ticks_sums_utime_and_stime() {
+        let stat = "4321 (com.example.app) S 612 612 0 0 -1 1077952832 51201 0 1 0 \
+                    1520 380 0 0 10 -10 52 0 123456 15839297536 45210";
+        assert_eq!(parse_cpu_ticks(stat), Some(1900));
+
+        // The command name may hold spaces and parentheses of its own
+        let stat = "4321 (Web Content) (1)) R 612 612 0 0 -1 1077952832 51201 0 1 0 \
+                    7 3 0 0 10 -10 52 0 123456 15839297536 45210";
+        assert_eq!(parse_cpu_ticks(stat), Some(10));
+    }
+
+    #[test]
+    fn parse_cpu_ticks_rejects_malformed_lines() {
+        // Truncated before stime, and before the command name ends
+        assert_eq!(
+            parse_cpu_ticks("4321 (com.example.app) S 612 612 0 0 -1 1077952832 51201 0 1 0 1520"),
+            None
+        );
+        assert_eq!(parse_cpu_ticks("4321 (com.example"), None);
+        assert_eq!(parse_cpu_ticks(""), None);
+
+        // Non-numeric utime or stime
+        assert_eq!(
+            parse_cpu_ticks("4321 (app) S 612 612 0 0 -1 1077952832 51201 0 1 0 abc 380"),
+            None
+        );
+        assert_eq!(
+            parse_cpu_ticks("4321 (app) S 612 612 0 0 -1 1077952832 51201 0 1 0 1520 -4"),
+            None
+        );
+    }
+
+    #[test]
+    fn device_status_parses_every_adb_state() {
+        let cases = [
+            ("device", None, DeviceStatus::Device),
+            ("unauthorized", None, DeviceStatus::Unauthorized),
+            ("offline", None, DeviceStatus::Offline),
+            ("no", Some("permissions"), DeviceStatus::NoPermissions),
+            ("no", Some("permissions;"), DeviceStatus::NoPermissions),
+            ("recovery", None, DeviceStatus::Recovery),
+            ("bootloader", None, DeviceStatus::Unknown),
+            ("sideload", None, DeviceStatus::Unknown),
+            ("authorizing", None, DeviceStatus::Unknown),
+            ("no", None, DeviceStatus::Unknown),
+        ];
+        for (state, next, expected) in cases {
+            assert_eq!(DeviceStatus::from_adb(state, next), expected, "{}", state);
+        }
+    }
+
+    #[test]
+    fn parse_device_list_reads_states_and_models() {
+        let stdout = "List of devices attached\n\
+            R58M123ABC             device usb:1-1 product:p3s model:SM_G998B device:p3s transport_id:1\n\
+            emulator-5554          offline transport_id:2\n\
+            0A1B2C3D               no permissions (missing udev rules? user is in the plugdev group); see [http://developer.android.com/tools/device.html] usb:1-2\n\
+            9B081FFAZ001WX         unauthorized usb:1-3 transport_id:4\n\
+            ZY22GXTX4L             recovery usb:1-4 product:sunfish model:Pixel_4a device:sunfish transport_id:5\n\
+            \n";
+        let devices = parse_device_list(stdout);
+        let summary: Vec<(&str, &str, DeviceStatus, bool)> = devices
+            .iter()
+            .map(|d| (d.id.as_str(), d.name.as_str(), d.status, d.is_emulator))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("R58M123ABC", "SM_G998B", DeviceStatus::Device, false),
+                (
+                    "emulator-5554",
+                    "emulator-5554",
+                    DeviceStatus::Offline,
+                    true
+                ),
+                ("0A1B2C3D", "0A1B2C3D", DeviceStatus::NoPermissions, false),
+                (
+                    "9B081FFAZ001WX",
+                    "9B081FFAZ001WX",
+                    DeviceStatus::Unauthorized,
+                    false
+                ),
+                ("ZY22GXTX4L", "Pixel_4a", DeviceStatus::Recovery, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn from_output_text_classifies_adb_stderr() {
+        // Captured stderr, and the start of the expected error's Debug form
+        let cases = [
+            ("adb: device offline\n", "DeviceOffline"),
+            (
+                "* daemon not running; starting now at tcp:5037\n\
+                 * daemon started successfully\n\
+                 adb: no devices/emulators found\n",
+                "NoDevices",
+            ),
+            (
+                "adb: device unauthorized.\n\
+                 This adb server's $ADB_VENDOR_KEYS is not set\n\
+                 Try 'adb kill-server' if that seems wrong.\n\
+                 Otherwise check for a confirmation dialog on your device.\n",
+                "DeviceUnauthorized",
+            ),
+            ("adb: more than one device/emulator\n", "MultipleDevices"),
+            (
+                "adb: insufficient permissions for device: user in plugdev group; \
+                 are your udev rules wrong?\n\
+                 See [http://developer.android.com/tools/device.html] for more information\n",
+                "DeviceNoPermissions",
+            ),
+            (
+                "adb: error: cannot bind listener: cannot bind to 127.0.0.1:9222: \
+                 Address already in use (98)\n",
+                "CannotBindListener(\"adb: error: cannot bind listener: cannot bind to 127.0.0.1:9222",
+            ),
+            (
+                "adb: device 'emulator-5556' not found\n",
+                "DeviceNotFound(\"emulator-5556\")",
+            ),
+            (
+                "error: device '192.168.1.20:5555' not found\n",
+                "DeviceNotFound(\"192.168.1.20:5555\")",
+            ),
+            (
+                "/system/bin/sh: pidof: inaccessible or not found\n",
+                "CommandFailed(\"/system/bin/sh: pidof",
+            ),
+        ];
+        for (stderr, expected) in cases {
+            let error = format!("{:?}", AdbError::from_output_text(stderr));
+            assert!(error.starts_with(expected), "{:?} -> {}", stderr, error);
+        }
+    }
+
+    #[test]
+    fn getprop_value_reads_bracketed_values() {
+        let props = "[ro.build.version.sdk]: [34]\n\
+                     [ro.product.model]: [Pixel 7 Pro]\n\
+                     [ro.product.brand]: []\n";
+        assert_eq!(
+            getprop_value(props, "ro.build.version.sdk").as_deref(),
+            Some("34")
+        );
+        assert_eq!(
+            getprop_value(props, "ro.product.model").as_deref(),
+            Some("Pixel 7 Pro")
+        );
+        assert_eq!(getprop_value(props, "ro.product.brand"), None);
+        assert_eq!(getprop_value(props, "ro.product"), None);
+    }
+
+    #[test]
+    fn classify_socket_recognizes_devtools_sockets() {
+        assert_eq!(
+            classify_socket("webview_devtools_remote_123"),
+            Some((WebViewKind::WebView, Some(123)))
+        );
+        assert_eq!(
+            classify_socket("chrome_devtools_remote"),
+            Some((WebViewKind::Chrome, None))
+        );
+        assert_eq!(
+            classify_socket("chrome_devtools_remote_456"),
+            Some((WebViewKind::CustomTabs, Some(456)))
+        );
+        // Other Chromium browsers (Samsung Internet, Brave, ...)
+        assert_eq!(
+            classify_socket("com.sec.android.app.sbrowser_devtools_remote"),
+            Some((WebViewKind::Chrome, None))
+        );
+    }
+
+    #[test]
+    fn classify_socket_ignores_other_sockets() {
+        assert_eq!(classify_socket("webview_devtools_remote"), None);
+        assert_eq!(classify_socket("webview_devtools_remote_abc"), None);
+        assert_eq!(classify_socket("chrome_devtools_remote_x"), None);
+        assert_eq!(classify_socket("adbd"), None);
+        assert_eq!(classify_socket("jdwp-control"), None);
+        assert_eq!(classify_socket(""), None);
+    }
+
+    #[test]
+    fn count_cpu_list_handles_ranges_and_singles() {
+        assert_eq!(count_cpu_list("0-7\n"), Some(8));
+        assert_eq!(count_cpu_list("0,2-3"), Some(3));
+        assert_eq!(count_cpu_list("0"), Some(1));
+    }
+
+    #[test]
+    fn count_cpu_list_rejects_malformed_lists() {
+        assert_eq!(count_cpu_list(""), None);
+        assert_eq!(count_cpu_list("0-"), None);
+        assert_eq!(count_cpu_list("3-1"), None);
+        assert_eq!(count_cpu_list("0,x"), None);
+    }
+}
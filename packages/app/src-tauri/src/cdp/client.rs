@@ -1,18 +1,72 @@
-use super::types::{CdpTarget, ConnectionState, PerformanceMetrics};
+use super::coverage;
+use super::frames::{FrameAggregator, FRAME_TRACE_CATEGORIES};
+use super::types::{
+    BrowserVersionInfo, CdpTarget, ConnectionState, ConsoleLevel, DeviceMetrics, FrameStats,
+    HeapSnapshotProgress, JsCoverage, LayoutMetrics, MemoryMetrics, NetworkThrottle,
+    PageLoadTiming, PageLocation, PerformanceMetrics, PresetConfig, RequestTiming,
+    ResponseBodyContent, ScreencastFrame, WebViewVersion, WebVitals,
+};
+use super::vitals::{
+    RawNavigationTiming, RawWebVitals, NAVIGATION_TIMING_SCRIPT, WEB_VITALS_OBSERVER_SCRIPT,
+    WEB_VITALS_READ_SCRIPT,
+};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use chromiumoxide::cdp::browser_protocol::browser::GetVersionParams;
+use chromiumoxide::cdp::browser_protocol::emulation::{
+    SetCpuThrottlingRateParams, SetDeviceMetricsOverrideParams,
+};
+use chromiumoxide::cdp::browser_protocol::log::{
+    EnableParams as LogEnableParams, EventEntryAdded, LogEntryLevel,
+};
+use chromiumoxide::cdp::browser_protocol::memory::GetDomCountersParams;
 use chromiumoxide::cdp::browser_protocol::network::EnableParams as NetworkEnableParams;
 use chromiumoxide::cdp::browser_protocol::network::{
-    EventLoadingFinished, EventRequestWillBeSent, EventResponseReceived,
+    EmulateNetworkConditionsParams, EventDataReceived, EventLoadingFailed, EventLoadingFinished,
+    EventRequestWillBeSent, EventResponseReceived, GetResponseBodyParams, Headers, ResourceTiming,
+    SetCacheDisabledParams, SetUserAgentOverrideParams,
+};
+use chromiumoxide::cdp::browser_protocol::page::{
+    CaptureScreenshotFormat, CaptureScreenshotParams, EnableParams as PageEnableParams,
+    EventDomContentEventFired, EventFrameNavigated, EventLoadEventFired, EventScreencastFrame,
+    GetLayoutMetricsParams, ScreencastFrameAckParams, StartScreencastFormat, StartScreencastParams,
+    StopScreencastParams,
 };
 use chromiumoxide::cdp::browser_protocol::performance::{
     EnableParams as PerfEnableParams, GetMetricsParams,
 };
+use chromiumoxide::cdp::browser_protocol::security::{
+    EnableParams as SecurityEnableParams, EventVisibleSecurityStateChanged, VisibleSecurityState,
+};
+use chromiumoxide::cdp::browser_protocol::tracing::{
+    EndParams as TracingEndParams, EventDataCollected, EventTracingComplete,
+    StartParams as TracingStartParams, StartTransferMode, TraceConfig,
+};
+use chromiumoxide::cdp::js_protocol::heap_profiler::{
+    EnableParams as HeapProfilerEnableParams, EventAddHeapSnapshotChunk,
+    EventReportHeapSnapshotProgress, TakeHeapSnapshotParams,
+};
+use chromiumoxide::cdp::js_protocol::profiler::{
+    DisableParams as ProfilerDisableParams, EnableParams as ProfilerEnableParams,
+    SetSamplingIntervalParams, StartParams as ProfilerStartParams, StartPreciseCoverageParams,
+    StopParams as ProfilerStopParams, StopPreciseCoverageParams, TakePreciseCoverageParams,
+};
+use chromiumoxide::cdp::js_protocol::runtime::{
+    ConsoleApiCalledType, EnableParams as RuntimeEnableParams, EvaluateParams,
+    EventConsoleApiCalled, RemoteObject,
+};
 use chromiumoxide::page::Page;
 use chromiumoxide::Browser;
-use futures_util::StreamExt;
-use std::sync::Arc;
-use std::time::Duration;
+use futures_util::future::BoxFuture;
+use futures_util::{FutureExt, StreamExt};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
+use tokio::io::AsyncWriteExt;
 use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
 use tokio::time::timeout;
 
 #[derive(Error, Debug)]
@@ -25,28 +79,112 @@ pub enum CdpError {
     NotConnected,
     #[error("Browser error: {0}")]
     BrowserError(String),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
+/// Options controlling how `connect` opens the socket and locates the page
+#[derive(Debug, Clone)]
+pub struct ConnectOptions {
+    /// How long to wait for the WebSocket handshake per attempt
+    pub timeout: Duration,
+    /// Further attempts after the first one fails
+    pub retries: u32,
+    /// How many times to poll `pages()` before giving up
+    pub page_attempts: u32,
+    /// Delay between `pages()` polls
+    pub page_retry_delay: Duration,
+    /// Open `about:blank` when no page appears (wrong for most real WebViews)
+    pub create_blank_page: bool,
+}
+
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            retries: 0,
+            page_attempts: 5,
+            page_retry_delay: Duration::from_millis(200),
+            create_blank_page: false,
+        }
+    }
+}
+
+/// First delay before trying to reconnect a dropped connection
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_millis(500);
+
+/// Upper bound for the reconnect backoff
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// How long `ping` waits for the page to answer
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long to wait for trace data to flush after `Tracing.end`
+const TRACING_COMPLETE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Clones share the same connection; the handler task holds one to recover
+/// from a dropped socket.
+#[derive(Clone)]
 pub struct CdpClient {
     state: Arc<RwLock<ConnectionState>>,
     browser: Arc<RwLock<Option<Browser>>>,
     page: Arc<RwLock<Option<Page>>>,
     event_tx: broadcast::Sender<CdpEvent>,
+    /// Background handler/listener tasks, aborted on disconnect
+    tasks: Arc<Mutex<Vec<JoinHandle<()>>>>,
+    /// Screencast frame listener, aborted on stop_screencast
+    screencast_task: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// URL and options of the last successful `connect`, cleared on disconnect
+    last_connection: Arc<Mutex<Option<(String, ConnectOptions)>>>,
+    auto_reconnect: Arc<AtomicBool>,
+    /// Set while a reconnect loop is running
+    reconnecting: Arc<AtomicBool>,
+    /// Bumped on every connect so stale handler tasks can tell they're stale
+    generation: Arc<AtomicU64>,
+    /// Coverage and CPU profiling share the Profiler domain; whichever
+    /// stops last disables it
+    js_coverage_running: Arc<AtomicBool>,
+    cpu_profile_running: Arc<AtomicBool>,
 }
 
 #[derive(Debug, Clone)]
 pub enum CdpEvent {
     Connected,
     Disconnected,
+    /// The socket dropped on its own and won't be re-established, either
+    /// because auto reconnect is off or it was turned off while retrying
+    ConnectionLost,
     NetworkRequest {
         request_id: String,
         url: String,
         method: String,
         timestamp: f64,
+        /// Wall-clock send time in epoch seconds (`wallTime`)
+        wall_time: f64,
+        initiator_type: String,
+        initiator_url: Option<String>,
+        /// Line of the initiating script or markup (0-based)
+        initiator_line: Option<i64>,
+        /// Function in the top JS stack frame, for script-initiated requests
+        initiator_function: Option<String>,
+        priority: String,
+        /// Lowercased CDP resource type (document, script, xhr, fetch, ...)
+        resource_type: Option<String>,
     },
     NetworkResponse {
         request_id: String,
         status: i32,
+        status_text: String,
+        timestamp: f64,
+        headers: HashMap<String, String>,
+        mime_type: String,
+        /// None for cached responses
+        timing: Option<RequestTiming>,
+    },
+    NetworkDataReceived {
+        request_id: String,
+        data_length: i64,
+        encoded_data_length: i64,
         timestamp: f64,
     },
     NetworkFinished {
@@ -54,6 +192,32 @@ pub enum CdpEvent {
         encoded_data_length: f64,
         timestamp: f64,
     },
+    /// `Network.loadingFailed`; the request will never finish
+    NetworkFailed {
+        request_id: String,
+        timestamp: f64,
+        /// Net error, plus the blocked or CORS reason when there is one
+        error_text: String,
+        /// Aborted by the page or the user rather than a network error
+        canceled: bool,
+    },
+    /// The page fired its load event
+    PageLoad(PageLoadTiming),
+    SecurityState {
+        state: String,
+        summary: Option<String>,
+    },
+    ConsoleMessage {
+        level: ConsoleLevel,
+        text: String,
+        /// Milliseconds since epoch
+        timestamp: f64,
+        url: Option<String>,
+        /// 0-based line in `url`
+        line: Option<i64>,
+    },
+    ScreencastFrame(ScreencastFrame),
+    ScreencastStopped,
 }
 
 impl CdpClient {
@@ -64,12 +228,37 @@ impl CdpClient {
             browser: Arc::new(RwLock::new(None)),
             page: Arc::new(RwLock::new(None)),
             event_tx,
+            tasks: Arc::new(Mutex::new(Vec::new())),
+            screencast_task: Arc::new(Mutex::new(None)),
+            last_connection: Arc::new(Mutex::new(None)),
+            auto_reconnect: Arc::new(AtomicBool::new(true)),
+            reconnecting: Arc::new(AtomicBool::new(false)),
+            generation: Arc::new(AtomicU64::new(0)),
+            js_coverage_running: Arc::new(AtomicBool::new(false)),
+            cpu_profile_running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn track_task(&self, task: JoinHandle<()>) {
+        self.tasks.lock().unwrap().push(task);
+    }
+
+    fn abort_tasks(&self) {
+        for task in self.tasks.lock().unwrap().drain(..) {
+            task.abort();
         }
+        self.stop_screencast_listener();
     }
 
-    /// Get targets from CDP endpoint
-    pub async fn get_targets(port: u16) -> Result<Vec<CdpTarget>, CdpError> {
-        let url = format!("http://localhost:{}/json/list", port);
+    /// Enable or disable reconnecting after the socket drops (on by default)
+    pub fn set_auto_reconnect(&self, enabled: bool) {
+        self.auto_reconnect.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Get targets from a CDP endpoint on an arbitrary host
+    /// (e.g. desktop Chrome started with --remote-debugging-port)
+    pub async fn get_targets_on_host(host: &str, port: u16) -> Result<Vec<CdpTarget>, CdpError> {
+        let url = format!("http://{}:{}/json/list", host, port);
         let response = reqwest::get(&url)
             .await
             .map_err(|e| CdpError::FetchTargetsFailed(e.to_string()))?;
@@ -82,9 +271,61 @@ impl CdpClient {
         Ok(targets)
     }
 
-    /// Connect to a CDP target via WebSocket
+    /// Get browser-level endpoint info from /json/version
+    pub async fn get_version_info(host: &str, port: u16) -> Result<BrowserVersionInfo, CdpError> {
+        let url = format!("http://{}:{}/json/version", host, port);
+        let response = reqwest::get(&url)
+            .await
+            .map_err(|e| CdpError::FetchTargetsFailed(e.to_string()))?;
+
+        let info: BrowserVersionInfo = response
+            .json()
+            .await
+            .map_err(|e| CdpError::FetchTargetsFailed(e.to_string()))?;
+
+        Ok(info)
+    }
+
+    /// Get the browser version of the connected target via `Browser.getVersion`
+    pub async fn get_browser_version(&self) -> Result<WebViewVersion, CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        let result = page
+            .execute(GetVersionParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let version = result.result;
+        Ok(WebViewVersion {
+            product: version.product,
+            revision: Some(version.revision),
+            js_version: Some(version.js_version),
+            user_agent: Some(version.user_agent),
+            protocol_version: Some(version.protocol_version),
+        })
+    }
+
+    /// Connect to a CDP target via WebSocket, retrying up to
+    /// `options.retries` times. The client is `Disconnected` after each
+    /// failed attempt.
+    pub async fn connect(&self, ws_url: &str, options: &ConnectOptions) -> Result<(), CdpError> {
+        let mut attempt = 0;
+        loop {
+            match self.connect_once(ws_url, options).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < options.retries => {
+                    attempt += 1;
+                    tracing::warn!("CDP connect attempt {} failed, retrying: {}", attempt, e);
+                    tokio::time::sleep(RECONNECT_INITIAL_DELAY).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
     /// For Android Chrome/WebView, we connect directly to the page's WebSocket URL
-    pub async fn connect(&self, ws_url: &str) -> Result<(), CdpError> {
+    async fn connect_once(&self, ws_url: &str, options: &ConnectOptions) -> Result<(), CdpError> {
         {
             let mut state = self.state.write().await;
             *state = ConnectionState::Connecting;
@@ -93,7 +334,7 @@ impl CdpClient {
         // For Android Chrome/WebView, connect directly to the page URL
         // chromiumoxide can connect to individual page targets
         // Add timeout to prevent hanging connections
-        let connect_result = timeout(Duration::from_secs(10), Browser::connect(ws_url)).await;
+        let connect_result = timeout(options.timeout, Browser::connect(ws_url)).await;
 
         let (browser, mut handler) = match connect_result {
             Ok(Ok(result)) => result,
@@ -109,58 +350,256 @@ impl CdpClient {
             }
         };
 
-        // Spawn handler task
-        tokio::spawn(async move {
+        // Spawn handler task. The stream ends when the socket closes, e.g.
+        // when the WebView is recreated; treat that as a lost connection.
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let client = self.clone();
+        let handler_task = tokio::spawn(async move {
             while let Some(event) = handler.next().await {
                 if let Err(e) = event {
                     tracing::warn!("CDP handler error: {}", e);
                 }
             }
+            if client.generation.load(Ordering::SeqCst) == generation {
+                tokio::spawn(client.on_connection_lost());
+            }
         });
 
         // For page-level connections, create a Page wrapper directly
-        // Since we connected to a page URL, the browser IS the page essentially
-        let pages = browser
-            .pages()
-            .await
-            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
-
-        // Get the page - for direct page connections, there should be one
-        let page = if let Some(p) = pages.into_iter().next() {
-            Some(p)
-        } else {
-            // If no pages found, try to create a new page context
-            // This is a fallback for some CDP implementations
-            browser.new_page("about:blank").await.ok()
-        };
+        // Since we connected to a page URL, the browser IS the page essentially.
+        // Android WebViews can briefly report no pages right after connecting,
+        // so poll a few times before giving up.
+        let mut page = None;
+        for attempt in 1..=options.page_attempts.max(1) {
+            match browser.pages().await {
+                Ok(pages) => page = pages.into_iter().next(),
+                Err(e) => tracing::debug!("pages() attempt {} failed: {}", attempt, e),
+            }
+            if page.is_some() || attempt == options.page_attempts {
+                break;
+            }
+            tokio::time::sleep(options.page_retry_delay).await;
+        }
 
-        {
-            let mut browser_lock = self.browser.write().await;
-            *browser_lock = Some(browser);
+        if page.is_none() && options.create_blank_page {
+            // Fallback for CDP implementations that expose no page targets
+            page = browser.new_page("about:blank").await.ok();
         }
 
-        if let Some(p) = page {
-            let mut page_lock = self.page.write().await;
-            *page_lock = Some(p);
-        } else {
-            // Reset state if no page available
+        let Some(page) = page else {
+            // Close the socket without it counting as a lost connection
+            self.generation.fetch_add(1, Ordering::SeqCst);
+            handler_task.abort();
+            drop(browser);
             let mut state = self.state.write().await;
             *state = ConnectionState::Disconnected;
             return Err(CdpError::ConnectionFailed(
                 "No page available to connect".into(),
             ));
-        }
+        };
+
+        self.track_task(handler_task);
+        *self.browser.write().await = Some(browser);
+        *self.page.write().await = Some(page);
 
         {
             let mut state = self.state.write().await;
             *state = ConnectionState::Connected;
         }
 
+        // Profiler state belongs to the previous socket's session
+        self.js_coverage_running.store(false, Ordering::SeqCst);
+        self.cpu_profile_running.store(false, Ordering::SeqCst);
+
+        *self.last_connection.lock().unwrap() = Some((ws_url.to_string(), options.clone()));
         let _ = self.event_tx.send(CdpEvent::Connected);
 
         Ok(())
     }
 
+    /// Move a connected client to another page target, e.g. after picking
+    /// the wrong one or the WebView recreating its page. Subscribers and the
+    /// connect options carry over; the old page's listeners are dropped, and
+    /// the `Connected` event that follows makes the collector re-enable its
+    /// domains on the new page. On failure the client is left `Disconnected`.
+    pub async fn switch_target(&self, ws_url: &str) -> Result<(), CdpError> {
+        let options = self
+            .last_connection
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|(_, options)| options.clone())
+            .ok_or(CdpError::NotConnected)?;
+
+        // Aborting the old handler first keeps it from reporting a lost connection
+        self.abort_tasks();
+        *self.page.write().await = None;
+        *self.browser.write().await = None;
+
+        self.connect(ws_url, &options).await
+    }
+
+    /// Mark the client disconnected after the socket dropped on its own, then
+    /// reconnect to the last URL with exponential backoff unless auto
+    /// reconnect is off or `disconnect` is called meanwhile.
+    /// Boxed because it calls back into `connect`, which spawns it.
+    fn on_connection_lost(self) -> BoxFuture<'static, ()> {
+        Box::pin(async move {
+            if self.reconnecting.swap(true, Ordering::SeqCst) {
+                return;
+            }
+
+            self.abort_tasks();
+            *self.page.write().await = None;
+            *self.browser.write().await = None;
+            *self.state.write().await = ConnectionState::Disconnected;
+            let _ = self.event_tx.send(CdpEvent::Disconnected);
+            tracing::warn!("CDP connection lost");
+
+            let mut delay = RECONNECT_INITIAL_DELAY;
+            let mut reconnected = false;
+            while self.auto_reconnect.load(Ordering::Relaxed) {
+                tokio::time::sleep(delay).await;
+
+                let Some((ws_url, options)) = self.last_connection.lock().unwrap().clone() else {
+                    break;
+                };
+                if !self.auto_reconnect.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                // Drop leftovers from a previous failed attempt; this loop
+                // does its own retrying
+                self.abort_tasks();
+                let options = ConnectOptions {
+                    retries: 0,
+                    ..options
+                };
+                match self.connect(&ws_url, &options).await {
+                    Ok(()) => {
+                        tracing::info!("CDP reconnected to {}", ws_url);
+                        reconnected = true;
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::warn!("CDP reconnect failed, retrying in {:?}: {}", delay, e);
+                        delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                    }
+                }
+            }
+
+            // `disconnect` clears the last connection; anything else is a loss
+            if !reconnected && self.last_connection.lock().unwrap().is_some() {
+                let _ = self.event_tx.send(CdpEvent::ConnectionLost);
+            }
+
+            self.reconnecting.store(false, Ordering::SeqCst);
+        })
+    }
+
+    /// Forward `console.*` calls and browser log entries (interventions,
+    /// violations, failed loads) as `ConsoleMessage` events
+    pub async fn enable_console(&self) -> Result<(), CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        page.execute(RuntimeEnableParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+        page.execute(LogEnableParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let mut console_events = page
+            .event_listener::<EventConsoleApiCalled>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let tx1 = self.event_tx.clone();
+        self.track_task(tokio::spawn(async move {
+            while let Some(event) = console_events.next().await {
+                let level = match event.r#type {
+                    ConsoleApiCalledType::Debug => ConsoleLevel::Debug,
+                    ConsoleApiCalledType::Warning => ConsoleLevel::Warning,
+                    ConsoleApiCalledType::Error | ConsoleApiCalledType::Assert => {
+                        ConsoleLevel::Error
+                    }
+                    _ => ConsoleLevel::Info,
+                };
+                let text = event
+                    .args
+                    .iter()
+                    .map(remote_object_text)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let top_frame = event
+                    .stack_trace
+                    .as_ref()
+                    .and_then(|stack| stack.call_frames.first());
+
+                let _ = tx1.send(CdpEvent::ConsoleMessage {
+                    level,
+                    text,
+                    timestamp: *event.timestamp.inner(),
+                    url: top_frame.map(|frame| frame.url.clone()),
+                    line: top_frame.map(|frame| frame.line_number),
+                });
+            }
+        }));
+
+        let mut log_events = page
+            .event_listener::<EventEntryAdded>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let tx2 = self.event_tx.clone();
+        self.track_task(tokio::spawn(async move {
+            while let Some(event) = log_events.next().await {
+                let entry = &event.entry;
+                let level = match entry.level {
+                    LogEntryLevel::Verbose => ConsoleLevel::Debug,
+                    LogEntryLevel::Info => ConsoleLevel::Info,
+                    LogEntryLevel::Warning => ConsoleLevel::Warning,
+                    LogEntryLevel::Error => ConsoleLevel::Error,
+                };
+
+                let _ = tx2.send(CdpEvent::ConsoleMessage {
+                    level,
+                    text: entry.text.clone(),
+                    timestamp: *entry.timestamp.inner(),
+                    url: entry.url.clone(),
+                    line: entry.line_number,
+                });
+            }
+        }));
+
+        Ok(())
+    }
+
+    /// Round-trip a trivial `Runtime.evaluate` and return the latency in ms.
+    /// A failed or timed-out ping puts the client in the `Error` state and
+    /// hands over to the reconnect loop when auto reconnect is on.
+    pub async fn ping(&self) -> Result<f64, CdpError> {
+        let started = Instant::now();
+        let result = {
+            let page_lock = self.page.read().await;
+            let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+            timeout(PING_TIMEOUT, evaluate_by_value(page, "1+1")).await
+        };
+
+        let error = match result {
+            Ok(Ok(_)) => return Ok(started.elapsed().as_secs_f64() * 1000.0),
+            Ok(Err(e)) => e,
+            Err(_) => CdpError::BrowserError(format!("Ping timed out after {:?}", PING_TIMEOUT)),
+        };
+
+        *self.state.write().await = ConnectionState::Error(error.to_string());
+        if self.auto_reconnect.load(Ordering::Relaxed) {
+            tokio::spawn(self.clone().on_connection_lost());
+        }
+        Err(error)
+    }
+
     /// Enable Performance domain and start collecting metrics
     pub async fn enable_performance(&self) -> Result<(), CdpError> {
         let page_lock = self.page.read().await;
@@ -192,16 +631,44 @@ impl CdpClient {
             .map_err(|e| CdpError::BrowserError(e.to_string()))?;
 
         let tx1 = event_tx.clone();
-        tokio::spawn(async move {
+        self.track_task(tokio::spawn(async move {
             while let Some(event) = request_events.next().await {
+                // Prefer the explicit initiator URL, else the top JS stack frame
+                let top_frame = event
+                    .initiator
+                    .stack
+                    .as_ref()
+                    .and_then(|stack| stack.call_frames.iter().find(|f| !f.url.is_empty()));
+                let (initiator_url, initiator_line, initiator_function) =
+                    match (&event.initiator.url, top_frame) {
+                        (Some(url), _) => (
+                            Some(url.clone()),
+                            event.initiator.line_number.map(|line| line as i64),
+                            None,
+                        ),
+                        (None, Some(frame)) => (
+                            Some(frame.url.clone()),
+                            Some(frame.line_number),
+                            Some(frame.function_name.clone()).filter(|name| !name.is_empty()),
+                        ),
+                        (None, None) => (None, None, None),
+                    };
+
                 let _ = tx1.send(CdpEvent::NetworkRequest {
                     request_id: event.request_id.inner().clone(),
                     url: event.request.url.clone(),
                     method: event.request.method.clone(),
                     timestamp: *event.timestamp.inner(),
+                    wall_time: *event.wall_time.inner(),
+                    initiator_type: event.initiator.r#type.as_ref().to_string(),
+                    initiator_url,
+                    initiator_line,
+                    initiator_function,
+                    priority: event.request.initial_priority.as_ref().to_string(),
+                    resource_type: event.r#type.as_ref().map(|t| t.as_ref().to_lowercase()),
                 });
             }
-        });
+        }));
 
         // Response received
         let mut response_events = page
@@ -210,15 +677,37 @@ impl CdpClient {
             .map_err(|e| CdpError::BrowserError(e.to_string()))?;
 
         let tx2 = event_tx.clone();
-        tokio::spawn(async move {
+        self.track_task(tokio::spawn(async move {
             while let Some(event) = response_events.next().await {
                 let _ = tx2.send(CdpEvent::NetworkResponse {
                     request_id: event.request_id.inner().clone(),
                     status: event.response.status as i32,
+                    status_text: event.response.status_text.clone(),
                     timestamp: *event.timestamp.inner(),
+                    headers: header_map(&event.response.headers),
+                    mime_type: event.response.mime_type.clone(),
+                    timing: event.response.timing.as_ref().map(request_timing),
                 });
             }
-        });
+        }));
+
+        // Data received (streamed chunks)
+        let mut data_events = page
+            .event_listener::<EventDataReceived>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let tx_data = event_tx.clone();
+        self.track_task(tokio::spawn(async move {
+            while let Some(event) = data_events.next().await {
+                let _ = tx_data.send(CdpEvent::NetworkDataReceived {
+                    request_id: event.request_id.inner().clone(),
+                    data_length: event.data_length,
+                    encoded_data_length: event.encoded_data_length,
+                    timestamp: *event.timestamp.inner(),
+                });
+            }
+        }));
 
         // Loading finished
         let mut finished_events = page
@@ -227,7 +716,7 @@ impl CdpClient {
             .map_err(|e| CdpError::BrowserError(e.to_string()))?;
 
         let tx3 = event_tx.clone();
-        tokio::spawn(async move {
+        self.track_task(tokio::spawn(async move {
             while let Some(event) = finished_events.next().await {
                 let _ = tx3.send(CdpEvent::NetworkFinished {
                     request_id: event.request_id.inner().clone(),
@@ -235,13 +724,73 @@ impl CdpClient {
                     timestamp: *event.timestamp.inner(),
                 });
             }
-        });
+        }));
+
+        // Loading failed (DNS, CORS, blocked, aborted, ...)
+        let mut failed_events = page
+            .event_listener::<EventLoadingFailed>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let tx4 = event_tx.clone();
+        self.track_task(tokio::spawn(async move {
+            while let Some(event) = failed_events.next().await {
+                // The net error alone doesn't say why a request was blocked
+                let mut error_text = event.error_text.clone();
+                if let Some(reason) = &event.blocked_reason {
+                    error_text = format!("{} (blocked: {})", error_text, reason.as_ref());
+                }
+                if let Some(cors) = &event.cors_error_status {
+                    error_text = format!("{} (CORS: {})", error_text, cors.cors_error.as_ref());
+                }
+
+                let _ = tx4.send(CdpEvent::NetworkFailed {
+                    request_id: event.request_id.inner().clone(),
+                    timestamp: *event.timestamp.inner(),
+                    error_text,
+                    canceled: event.canceled.unwrap_or(false),
+                });
+            }
+        }));
+
+        Ok(())
+    }
+
+    /// Enable Security domain and listen for page security state changes
+    pub async fn enable_security(&self) -> Result<(), CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        page.execute(SecurityEnableParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let mut security_events = page
+            .event_listener::<EventVisibleSecurityStateChanged>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let tx = self.event_tx.clone();
+        self.track_task(tokio::spawn(async move {
+            while let Some(event) = security_events.next().await {
+                let visible = &event.visible_security_state;
+                let _ = tx.send(CdpEvent::SecurityState {
+                    state: visible.security_state.as_ref().to_string(),
+                    summary: security_summary(visible),
+                });
+            }
+        }));
 
         Ok(())
     }
 
     /// Get current performance metrics
-    pub async fn get_performance_metrics(&self) -> Result<PerformanceMetrics, CdpError> {
+    /// With `include_all`, metrics without a typed field are kept in
+    /// `extras` instead of being dropped
+    pub async fn get_performance_metrics(
+        &self,
+        include_all: bool,
+    ) -> Result<PerformanceMetrics, CdpError> {
         let page_lock = self.page.read().await;
         let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
 
@@ -258,6 +807,11 @@ impl CdpClient {
             layout_count: None,
             script_duration: None,
             task_duration: None,
+            js_event_listeners: None,
+            documents: None,
+            frames: None,
+            recalc_style_count: None,
+            extras: HashMap::new(),
         };
 
         for metric in &result.metrics {
@@ -268,6 +822,13 @@ impl CdpClient {
                 "LayoutCount" => metrics.layout_count = Some(metric.value),
                 "ScriptDuration" => metrics.script_duration = Some(metric.value),
                 "TaskDuration" => metrics.task_duration = Some(metric.value),
+                "JSEventListeners" => metrics.js_event_listeners = Some(metric.value),
+                "Documents" => metrics.documents = Some(metric.value),
+                "Frames" => metrics.frames = Some(metric.value),
+                "RecalcStyleCount" => metrics.recalc_style_count = Some(metric.value),
+                name if include_all => {
+                    metrics.extras.insert(name.to_string(), metric.value);
+                }
                 _ => {}
             }
         }
@@ -275,6 +836,620 @@ impl CdpClient {
         Ok(metrics)
     }
 
+    /// Get every metric `Performance.getMetrics` reports, unfiltered.
+    /// Useful for discovering which counters a WebView build exposes.
+    pub async fn get_all_performance_metrics(&self) -> Result<Vec<(String, f64)>, CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        let result = page
+            .execute(GetMetricsParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        Ok(result
+            .metrics
+            .iter()
+            .map(|metric| (metric.name.clone(), metric.value))
+            .collect())
+    }
+
+    /// Fetch the body of a finished request. Only valid after its
+    /// `loadingFinished`, and only while the browser still holds the
+    /// resource, so capture soon after the request completes.
+    pub async fn get_response_body(
+        &self,
+        request_id: &str,
+    ) -> Result<ResponseBodyContent, CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        let result = page
+            .execute(GetResponseBodyParams::new(request_id.to_string()))
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let result = result.result;
+        Ok(ResponseBodyContent {
+            body: result.body,
+            base64_encoded: result.base64_encoded,
+        })
+    }
+
+    /// Get page layout metrics (viewport and content size)
+    pub async fn get_layout_metrics(&self) -> Result<LayoutMetrics, CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        let result = page
+            .execute(GetLayoutMetricsParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        Ok(result.result.into())
+    }
+
+    /// Get document, node and event listener counts via Memory.getDOMCounters.
+    /// `rss_kb` is left empty; it comes from the device, not CDP.
+    pub async fn get_memory_info(&self) -> Result<MemoryMetrics, CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        let result = page
+            .execute(GetDomCountersParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        Ok(MemoryMetrics {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            documents: result.result.documents,
+            nodes: result.result.nodes,
+            js_event_listeners: result.result.js_event_listeners,
+            rss_kb: None,
+        })
+    }
+
+    /// Install the Web Vitals observers and re-install them whenever the
+    /// main frame navigates, since a new document starts without them
+    pub async fn enable_web_vitals(&self) -> Result<(), CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        let mut navigations = page
+            .event_listener::<EventFrameNavigated>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        evaluate_by_value(page, WEB_VITALS_OBSERVER_SCRIPT).await?;
+
+        let nav_page = page.clone();
+        self.track_task(tokio::spawn(async move {
+            while let Some(event) = navigations.next().await {
+                if event.frame.parent_id.is_none() {
+                    if let Err(e) = evaluate_by_value(&nav_page, WEB_VITALS_OBSERVER_SCRIPT).await {
+                        tracing::debug!("Web Vitals re-injection failed: {}", e);
+                    }
+                }
+            }
+        }));
+
+        Ok(())
+    }
+
+    /// Report TTFB, DOMContentLoaded and load times (`PageLoad`) each time
+    /// a document finishes loading
+    pub async fn enable_page_lifecycle(&self) -> Result<(), CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        page.execute(PageEnableParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let mut dom_content_events = page
+            .event_listener::<EventDomContentEventFired>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+        let mut load_events = page
+            .event_listener::<EventLoadEventFired>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let tx = self.event_tx.clone();
+        let load_page = page.clone();
+        self.track_task(tokio::spawn(async move {
+            let mut dom_content_at: Option<f64> = None;
+            loop {
+                tokio::select! {
+                    Some(event) = dom_content_events.next() => {
+                        dom_content_at = Some(*event.timestamp.inner());
+                    }
+                    Some(event) = load_events.next() => {
+                        let timing = page_load_timing(
+                            &load_page,
+                            dom_content_at.take(),
+                            *event.timestamp.inner(),
+                        )
+                        .await;
+                        let _ = tx.send(CdpEvent::PageLoad(timing));
+                    }
+                    else => break,
+                }
+            }
+        }));
+
+        Ok(())
+    }
+
+    /// URL and title of the page as loaded now, after any redirects or
+    /// client-side navigation since the target was listed
+    pub async fn get_page_location(&self) -> Result<PageLocation, CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        let value = evaluate_by_value(page, "({ url: location.href, title: document.title })")
+            .await?
+            .ok_or_else(|| CdpError::BrowserError("Page location unavailable".into()))?;
+        serde_json::from_value(value).map_err(|e| CdpError::BrowserError(e.to_string()))
+    }
+
+    /// Read LCP, CLS, FID and worst INP accumulated since the last
+    /// navigation. Installs the observers first if they aren't there yet.
+    pub async fn collect_web_vitals(&self) -> Result<WebVitals, CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        evaluate_by_value(page, WEB_VITALS_OBSERVER_SCRIPT).await?;
+        let value = evaluate_by_value(page, WEB_VITALS_READ_SCRIPT)
+            .await?
+            .ok_or_else(|| CdpError::BrowserError("Web Vitals observer missing".into()))?;
+        let raw: RawWebVitals =
+            serde_json::from_value(value).map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        Ok(raw.into_web_vitals(chrono::Utc::now().timestamp_millis()))
+    }
+
+    /// Emulate network conditions (latency and throughput limits)
+    pub async fn set_network_conditions(&self, throttle: &NetworkThrottle) -> Result<(), CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        // Network emulation only takes effect once the domain is enabled
+        page.execute(NetworkEnableParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        page.execute(EmulateNetworkConditionsParams::new(
+            throttle.offline,
+            throttle.latency_ms,
+            throttle.download_throughput,
+            throttle.upload_throughput,
+        ))
+        .await
+        .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Slow down the page's CPU by the given factor (1 = no throttling)
+    pub async fn set_cpu_throttling_rate(&self, rate: f64) -> Result<(), CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        page.execute(SetCpuThrottlingRateParams::new(rate))
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Override screen size, pixel ratio and mobile emulation
+    pub async fn set_device_metrics(&self, metrics: &DeviceMetrics) -> Result<(), CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        page.execute(SetDeviceMetricsOverrideParams::new(
+            metrics.width as i64,
+            metrics.height as i64,
+            metrics.device_scale_factor,
+            metrics.mobile,
+        ))
+        .await
+        .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Override the User-Agent sent by the page
+    pub async fn set_user_agent(&self, user_agent: &str) -> Result<(), CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        page.execute(SetUserAgentOverrideParams::new(user_agent))
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Toggle ignoring the HTTP cache for each request
+    pub async fn set_cache_disabled(&self, disabled: bool) -> Result<(), CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        page.execute(SetCacheDisabledParams::new(disabled))
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Apply every setting in a preset, in a fixed order
+    pub async fn apply_preset_config(&self, config: &PresetConfig) -> Result<(), CdpError> {
+        if let Some(ref throttle) = config.network_throttle {
+            self.set_network_conditions(throttle).await?;
+        }
+        if let Some(rate) = config.cpu_throttle_rate {
+            self.set_cpu_throttling_rate(rate).await?;
+        }
+        if let Some(ref metrics) = config.device_metrics {
+            self.set_device_metrics(metrics).await?;
+        }
+        if let Some(ref user_agent) = config.user_agent {
+            self.set_user_agent(user_agent).await?;
+        }
+        if let Some(disabled) = config.cache_disabled {
+            self.set_cache_disabled(disabled).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Start streaming JPEG frames of the page as `CdpEvent::ScreencastFrame`.
+    /// Frames are acked as they arrive so the browser keeps sending them;
+    /// `every_nth_frame` and `max_width` bound the bandwidth.
+    pub async fn start_screencast(
+        &self,
+        quality: u8,
+        max_width: Option<u32>,
+        every_nth_frame: u32,
+    ) -> Result<(), CdpError> {
+        self.stop_screencast_listener();
+
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        let mut frames = page
+            .event_listener::<EventScreencastFrame>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let params = StartScreencastParams {
+            format: Some(StartScreencastFormat::Jpeg),
+            quality: Some(quality.min(100) as i64),
+            max_width: max_width.map(|w| w as i64),
+            max_height: None,
+            every_nth_frame: Some(every_nth_frame.max(1) as i64),
+        };
+        page.execute(params)
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let ack_page = page.clone();
+        let tx = self.event_tx.clone();
+        let task = tokio::spawn(async move {
+            while let Some(event) = frames.next().await {
+                let _ = ack_page
+                    .execute(ScreencastFrameAckParams::new(event.session_id))
+                    .await;
+
+                let data: &str = event.data.as_ref();
+                let _ = tx.send(CdpEvent::ScreencastFrame(ScreencastFrame {
+                    data: data.to_string(),
+                    device_width: event.metadata.device_width,
+                    device_height: event.metadata.device_height,
+                    timestamp: event.metadata.timestamp.as_ref().map(|t| *t.inner()),
+                }));
+            }
+        });
+        *self.screencast_task.lock().unwrap() = Some(task);
+
+        Ok(())
+    }
+
+    /// Stop the screencast started by `start_screencast`
+    pub async fn stop_screencast(&self) -> Result<(), CdpError> {
+        self.stop_screencast_listener();
+        let _ = self.event_tx.send(CdpEvent::ScreencastStopped);
+
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        page.execute(StopScreencastParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn stop_screencast_listener(&self) {
+        if let Some(task) = self.screencast_task.lock().unwrap().take() {
+            task.abort();
+        }
+    }
+
+    /// Capture the viewport as PNG with Page.captureScreenshot. CDP sends
+    /// the image base64-encoded; the returned bytes are the decoded PNG.
+    pub async fn capture_screenshot(&self) -> Result<Vec<u8>, CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        let params = CaptureScreenshotParams {
+            format: Some(CaptureScreenshotFormat::Png),
+            ..Default::default()
+        };
+        let result = page
+            .execute(params)
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let data: &str = result.result.data.as_ref();
+        BASE64_STANDARD
+            .decode(data)
+            .map_err(|e| CdpError::BrowserError(format!("Invalid screenshot data: {}", e)))
+    }
+
+    /// Record a frame trace for `duration` and summarize frame timing.
+    /// Trace chunks are aggregated as they stream in until
+    /// `Tracing.tracingComplete` rather than buffered whole.
+    pub async fn capture_frames(&self, duration: Duration) -> Result<FrameStats, CdpError> {
+        // Clone the page so the lock isn't held for the whole capture
+        let page = self
+            .page
+            .read()
+            .await
+            .clone()
+            .ok_or(CdpError::NotConnected)?;
+
+        let mut data_events = page
+            .event_listener::<EventDataCollected>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+        let mut complete_events = page
+            .event_listener::<EventTracingComplete>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let params = TracingStartParams {
+            transfer_mode: Some(StartTransferMode::ReportEvents),
+            trace_config: Some(TraceConfig {
+                included_categories: Some(
+                    FRAME_TRACE_CATEGORIES
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect(),
+                ),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        page.execute(params)
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let mut aggregator = FrameAggregator::default();
+        let capture_end = tokio::time::sleep(duration);
+        tokio::pin!(capture_end);
+
+        // Fold chunks reported during the capture window
+        loop {
+            tokio::select! {
+                Some(event) = data_events.next() => aggregator.add_events(&event.value),
+                _ = &mut capture_end => break,
+            }
+        }
+
+        page.execute(TracingEndParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        // The remaining chunks arrive before tracingComplete
+        let data_loss = timeout(TRACING_COMPLETE_TIMEOUT, async {
+            loop {
+                tokio::select! {
+                    biased;
+                    Some(event) = data_events.next() => aggregator.add_events(&event.value),
+                    complete = complete_events.next() => {
+                        return complete.is_some_and(|c| c.data_loss_occurred);
+                    }
+                }
+            }
+        })
+        .await
+        .map_err(|_| CdpError::BrowserError("Timed out waiting for trace data".into()))?;
+
+        Ok(aggregator.finish(
+            chrono::Utc::now().timestamp_millis(),
+            duration.as_millis() as u64,
+            data_loss,
+        ))
+    }
+
+    /// Start block-level JS coverage. Scripts parsed afterwards, eval'd
+    /// code included, are covered as well; read it with `stop_js_coverage`.
+    pub async fn start_js_coverage(&self) -> Result<(), CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        page.execute(ProfilerEnableParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+        page.execute(
+            StartPreciseCoverageParams::builder()
+                .call_count(true)
+                .detailed(true)
+                .build(),
+        )
+        .await
+        .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+        self.js_coverage_running.store(true, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Collect coverage since `start_js_coverage` as used vs. total bytes
+    /// per script, then stop coverage
+    pub async fn stop_js_coverage(&self) -> Result<JsCoverage, CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        let taken = page
+            .execute(TakePreciseCoverageParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        // Coverage slows execution down; turn it off even if summarizing fails
+        let _ = page.execute(StopPreciseCoverageParams::default()).await;
+        self.js_coverage_running.store(false, Ordering::SeqCst);
+        if !self.cpu_profile_running.load(Ordering::SeqCst) {
+            let _ = page.execute(ProfilerDisableParams::default()).await;
+        }
+
+        Ok(coverage::summarize(
+            chrono::Utc::now().timestamp_millis(),
+            &taken.result.result,
+        ))
+    }
+
+    /// Start sampling the page's JS call stacks, every `sampling_interval_us`
+    /// microseconds if given (V8 defaults to 1000)
+    pub async fn start_cpu_profile(
+        &self,
+        sampling_interval_us: Option<u32>,
+    ) -> Result<(), CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        page.execute(ProfilerEnableParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+        // Only takes effect before the profile starts
+        if let Some(interval) = sampling_interval_us {
+            page.execute(SetSamplingIntervalParams::new(interval))
+                .await
+                .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+        }
+        page.execute(ProfilerStartParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+        self.cpu_profile_running.store(true, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Stop the CPU profile started by `start_cpu_profile` and return it as
+    /// `.cpuprofile` JSON, which DevTools' Performance panel opens directly
+    pub async fn stop_cpu_profile(&self) -> Result<String, CdpError> {
+        let page_lock = self.page.read().await;
+        let page = page_lock.as_ref().ok_or(CdpError::NotConnected)?;
+
+        let stopped = page.execute(ProfilerStopParams::default()).await;
+        self.cpu_profile_running.store(false, Ordering::SeqCst);
+        if !self.js_coverage_running.load(Ordering::SeqCst) {
+            let _ = page.execute(ProfilerDisableParams::default()).await;
+        }
+        let stopped = stopped.map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        serde_json::to_string(&stopped.result.profile)
+            .map_err(|e| CdpError::BrowserError(e.to_string()))
+    }
+
+    /// Take a V8 heap snapshot and write it to `path` as `.heapsnapshot`
+    /// JSON. Snapshots can run to hundreds of MB, so chunks are streamed to
+    /// a `.part` file as they arrive and renamed into place when complete.
+    /// Returns the number of bytes written.
+    pub async fn take_heap_snapshot<F>(
+        &self,
+        path: &Path,
+        mut on_progress: F,
+    ) -> Result<u64, CdpError>
+    where
+        F: FnMut(HeapSnapshotProgress) + Send,
+    {
+        // Clone the page so the lock isn't held for the whole snapshot
+        let page = self
+            .page
+            .read()
+            .await
+            .clone()
+            .ok_or(CdpError::NotConnected)?;
+
+        let mut chunks = page
+            .event_listener::<EventAddHeapSnapshotChunk>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+        let mut progress = page
+            .event_listener::<EventReportHeapSnapshotProgress>()
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        page.execute(HeapProfilerEnableParams::default())
+            .await
+            .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+        let part_path = path.with_extension("heapsnapshot.part");
+        let written = async {
+            let mut file = tokio::io::BufWriter::new(tokio::fs::File::create(&part_path).await?);
+            let mut written = 0u64;
+
+            let snapshot = page.execute(
+                TakeHeapSnapshotParams::builder()
+                    .report_progress(true)
+                    .build(),
+            );
+            tokio::pin!(snapshot);
+
+            let result = loop {
+                tokio::select! {
+                    biased;
+                    Some(event) = chunks.next() => {
+                        file.write_all(event.chunk.as_bytes()).await?;
+                        written += event.chunk.len() as u64;
+                    }
+                    Some(event) = progress.next() => on_progress(HeapSnapshotProgress {
+                        done: event.done,
+                        total: event.total,
+                        finished: event.finished.unwrap_or(false),
+                    }),
+                    result = &mut snapshot => break result,
+                }
+            };
+            result.map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+            // Chunks are dispatched before the command response, so any not
+            // yet written are already queued on the listener
+            while let Some(Some(event)) = chunks.next().now_or_never() {
+                file.write_all(event.chunk.as_bytes()).await?;
+                written += event.chunk.len() as u64;
+            }
+            file.flush().await?;
+
+            Ok::<_, CdpError>(written)
+        }
+        .await;
+
+        match written {
+            Ok(written) => {
+                tokio::fs::rename(&part_path, path).await?;
+                Ok(written)
+            }
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&part_path).await;
+                Err(e)
+            }
+        }
+    }
+
     /// Subscribe to CDP events
     pub fn subscribe(&self) -> broadcast::Receiver<CdpEvent> {
         self.event_tx.subscribe()
@@ -287,6 +1462,10 @@ impl CdpClient {
 
     /// Disconnect from CDP
     pub async fn disconnect(&self) -> Result<(), CdpError> {
+        // Clear first so a running reconnect loop gives up
+        *self.last_connection.lock().unwrap() = None;
+        self.abort_tasks();
+
         {
             let mut page_lock = self.page.write().await;
             *page_lock = None;
@@ -308,6 +1487,152 @@ impl CdpClient {
     }
 }
 
+/// Load milestones for the document that just fired `load`. Prefers the
+/// page's Navigation Timing; without it, falls back to the CDP event times
+/// measured from Performance's `NavigationStart` (same monotonic clock).
+async fn page_load_timing(
+    page: &Page,
+    dom_content_at: Option<f64>,
+    load_at: f64,
+) -> PageLoadTiming {
+    let timestamp = chrono::Utc::now().timestamp_millis();
+
+    if let Ok(Some(value)) = evaluate_by_value(page, NAVIGATION_TIMING_SCRIPT).await {
+        if let Ok(raw) = serde_json::from_value::<RawNavigationTiming>(value) {
+            return raw.into_page_load_timing(timestamp);
+        }
+    }
+
+    let navigation_start = page
+        .execute(GetMetricsParams::default())
+        .await
+        .ok()
+        .and_then(|result| {
+            result
+                .metrics
+                .iter()
+                .find(|metric| metric.name == "NavigationStart")
+                .map(|metric| metric.value)
+        });
+    let since_start = |at: f64| navigation_start.map(|start| (at - start) * 1000.0);
+
+    PageLoadTiming {
+        timestamp,
+        url: None,
+        ttfb_ms: None,
+        dom_content_loaded_ms: dom_content_at.and_then(since_start),
+        load_ms: since_start(load_at),
+    }
+}
+
+/// Run `expression` with Runtime.evaluate and return the result as JSON.
+/// Script exceptions are reported as errors.
+async fn evaluate_by_value(
+    page: &Page,
+    expression: &str,
+) -> Result<Option<serde_json::Value>, CdpError> {
+    let params = EvaluateParams::builder()
+        .expression(expression)
+        .return_by_value(true)
+        .build()
+        .map_err(CdpError::BrowserError)?;
+    let result = page
+        .execute(params)
+        .await
+        .map_err(|e| CdpError::BrowserError(e.to_string()))?;
+
+    if let Some(details) = &result.result.exception_details {
+        return Err(CdpError::BrowserError(details.text.clone()));
+    }
+    Ok(result.result.result.value.clone())
+}
+
+/// Render a console argument the way DevTools prints it: strings unquoted,
+/// other primitives as JSON, objects by their description
+fn remote_object_text(object: &RemoteObject) -> String {
+    match &object.value {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(value) => value.to_string(),
+        None => object
+            .unserializable_value
+            .as_ref()
+            .map(|v| v.inner().clone())
+            .or_else(|| object.description.clone())
+            .unwrap_or_else(|| object.r#type.as_ref().to_string()),
+    }
+}
+
+/// Flatten a CDP header object into a map keyed by lowercased name.
+/// Names differing only in case are merged newline-separated, the same way
+/// CDP itself joins repeated headers such as `set-cookie`.
+fn header_map(headers: &Headers) -> HashMap<String, String> {
+    let mut map: HashMap<String, String> = HashMap::new();
+    let Some(object) = headers.inner().as_object() else {
+        return map;
+    };
+
+    for (name, value) in object {
+        let value = match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        map.entry(name.to_lowercase())
+            .and_modify(|existing| {
+                existing.push('\n');
+                existing.push_str(&value);
+            })
+            .or_insert(value);
+    }
+
+    map
+}
+
+/// Turn CDP's offsets from `requestTime` into phase durations. Offsets
+/// are -1 for phases that didn't happen.
+fn request_timing(timing: &ResourceTiming) -> RequestTiming {
+    let span = |start: f64, end: f64| (start >= 0.0 && end >= start).then_some(end - start);
+    let connect_end = if timing.ssl_start >= 0.0 {
+        timing.ssl_start
+    } else {
+        timing.connect_end
+    };
+
+    RequestTiming {
+        dns_ms: span(timing.dns_start, timing.dns_end),
+        connect_ms: span(timing.connect_start, connect_end),
+        ssl_ms: span(timing.ssl_start, timing.ssl_end),
+        send_ms: span(timing.send_start, timing.send_end),
+        wait_ms: span(timing.send_end, timing.receive_headers_end),
+        download_ms: None,
+    }
+}
+
+/// Describe why a page isn't fully secure, if it isn't
+fn security_summary(state: &VisibleSecurityState) -> Option<String> {
+    let mut reasons = state.security_state_issue_ids.clone();
+
+    if let Some(ref cert) = state.certificate_security_state {
+        if let Some(ref error) = cert.certificate_network_error {
+            reasons.push(format!("certificate error: {}", error));
+        }
+        if cert.certificate_has_weak_signature {
+            reasons.push("weak certificate signature".to_string());
+        }
+        if cert.obsolete_ssl_protocol {
+            reasons.push(format!("obsolete protocol {}", cert.protocol));
+        }
+        if cert.obsolete_ssl_cipher {
+            reasons.push(format!("obsolete cipher {}", cert.cipher));
+        }
+    }
+
+    if reasons.is_empty() {
+        None
+    } else {
+        Some(reasons.join("; "))
+    }
+}
+
 impl Default for CdpClient {
     fn default() -> Self {
         Self::new()
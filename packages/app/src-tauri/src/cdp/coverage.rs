@@ -0,0 +1,129 @@
+use chromiumoxide::cdp::js_protocol::profiler::{CoverageRange, ScriptCoverage};
+
+use super::types::{JsCoverage, ScriptCoverageSummary};
+
+/// Summarize `Profiler.takePreciseCoverage` output as executed vs. total
+/// bytes per script, largest unused first. Scripts without a URL (eval,
+/// `new Function`, inline handlers) are kept under their script id.
+pub fn summarize(timestamp: i64, scripts: &[ScriptCoverage]) -> JsCoverage {
+    let mut summaries: Vec<ScriptCoverageSummary> = scripts
+        .iter()
+        .map(|script| {
+            let ranges: Vec<&CoverageRange> = script
+                .functions
+                .iter()
+                .flat_map(|function| &function.ranges)
+                .collect();
+            // The top-level function's range spans the whole script
+            let total_bytes = ranges.iter().map(|r| r.end_offset).max().unwrap_or(0);
+            let used_bytes = used_bytes(&ranges);
+
+            ScriptCoverageSummary {
+                script_id: script.script_id.inner().clone(),
+                url: (!script.url.is_empty()).then(|| script.url.clone()),
+                total_bytes,
+                used_bytes,
+                unused_bytes: total_bytes - used_bytes,
+            }
+        })
+        .collect();
+    summaries.sort_by_key(|s| std::cmp::Reverse(s.unused_bytes));
+
+    let total_bytes = summaries.iter().map(|s| s.total_bytes).sum::<i64>();
+    let used_bytes = summaries.iter().map(|s| s.used_bytes).sum::<i64>();
+    JsCoverage {
+        timestamp,
+        total_bytes,
+        used_bytes,
+        unused_bytes: total_bytes - used_bytes,
+        scripts: summaries,
+    }
+}
+
+/// Bytes covered by ranges with a non-zero count. Block coverage ranges
+/// nest, and the innermost range decides the count of the bytes it spans;
+/// should two ranges only overlap, the one opened last decides.
+fn used_bytes(ranges: &[&CoverageRange]) -> i64 {
+    // (offset, is_start, range length, range index); at equal offsets ends
+    // come before starts, outer ranges open first and inner ranges close
+    // first
+    let mut points: Vec<(i64, bool, i64, usize)> = ranges
+        .iter()
+        .enumerate()
+        // Empty ranges cover nothing, and their end would sort before
+        // their start
+        .filter(|(_, r)| r.end_offset > r.start_offset)
+        .flat_map(|(i, r)| {
+            let len = r.end_offset - r.start_offset;
+            [
+                (r.start_offset, true, len, i),
+                (r.end_offset, false, len, i),
+            ]
+        })
+        .collect();
+    points.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then(a.1.cmp(&b.1))
+            .then(if a.1 { b.2.cmp(&a.2) } else { a.2.cmp(&b.2) })
+    });
+
+    // Open ranges, innermost last
+    let mut open: Vec<usize> = Vec::new();
+    let mut last_offset = 0;
+    let mut used = 0;
+    for (offset, is_start, _, index) in points {
+        if open.last().is_some_and(|&top| ranges[top].count > 0) {
+            used += offset - last_offset;
+        }
+        last_offset = offset;
+        if is_start {
+            open.push(index);
+        } else if let Some(pos) = open.iter().rposition(|&i| i == index) {
+            open.remove(pos);
+        }
+    }
+    used
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn used(ranges: &[(i64, i64, i64)]) -> i64 {
+        let ranges: Vec<CoverageRange> = ranges
+            .iter()
+            .map(|&(start_offset, end_offset, count)| CoverageRange {
+                start_offset,
+                end_offset,
+                count,
+            })
+            .collect();
+        used_bytes(&ranges.iter().collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn nested_ranges_take_the_innermost_count() {
+        // Script ran, a function in it didn't, a block in that function did
+        assert_eq!(used(&[(0, 100, 1), (20, 60, 0), (30, 40, 2)]), 70);
+        assert_eq!(used(&[(0, 100, 0), (10, 20, 1)]), 10);
+    }
+
+    #[test]
+    fn adjacent_ranges_are_not_double_counted() {
+        assert_eq!(used(&[(0, 10, 1), (10, 20, 1)]), 20);
+        assert_eq!(used(&[(0, 100, 1), (0, 50, 0), (50, 100, 0)]), 0);
+    }
+
+    #[test]
+    fn overlapping_ranges_follow_the_later_one() {
+        assert_eq!(used(&[(0, 10, 1), (5, 15, 0)]), 5);
+        assert_eq!(used(&[(0, 10, 0), (5, 15, 1)]), 10);
+    }
+
+    #[test]
+    fn zero_count_ranges_are_unused() {
+        assert_eq!(used(&[(0, 100, 0)]), 0);
+        assert_eq!(used(&[(0, 100, 1), (40, 40, 0)]), 100);
+        assert_eq!(used(&[]), 0);
+    }
+}
@@ -0,0 +1,67 @@
+use super::types::FrameStats;
+
+/// Trace categories that carry compositor frame events
+pub const FRAME_TRACE_CATEGORIES: &[&str] = &[
+    "benchmark",
+    "rail",
+    "devtools.timeline",
+    "disabled-by-default-devtools.timeline.frame",
+];
+
+/// A frame slower than this misses a 60 Hz vsync
+const LONG_FRAME_MS: f64 = 1000.0 / 60.0;
+
+/// A frame slower than this is a visible hitch
+const JANK_FRAME_MS: f64 = 50.0;
+
+/// Folds `Tracing.dataCollected` chunks into frame timing as they arrive.
+/// Only frame timestamps are kept; every other trace event is dropped on
+/// the spot, so memory stays proportional to the frame count.
+#[derive(Debug, Default)]
+pub struct FrameAggregator {
+    /// `DrawFrame` timestamps (µs)
+    draw_frames: Vec<f64>,
+    /// `BeginFrame` timestamps (µs), used when no `DrawFrame` was seen
+    begin_frames: Vec<f64>,
+}
+
+impl FrameAggregator {
+    /// Consume one chunk of trace events
+    pub fn add_events(&mut self, events: &[serde_json::Value]) {
+        for event in events {
+            let Some(ts) = event.get("ts").and_then(|ts| ts.as_f64()) else {
+                continue;
+            };
+            match event.get("name").and_then(|name| name.as_str()) {
+                Some("DrawFrame") => self.draw_frames.push(ts),
+                Some("BeginFrame") => self.begin_frames.push(ts),
+                _ => {}
+            }
+        }
+    }
+
+    /// Compute frame statistics for a capture that lasted `duration_ms`
+    pub fn finish(self, timestamp: i64, duration_ms: u64, data_loss: bool) -> FrameStats {
+        let mut frames = if self.draw_frames.is_empty() {
+            self.begin_frames
+        } else {
+            self.draw_frames
+        };
+        // Chunks are not guaranteed to be in timestamp order
+        frames.sort_by(f64::total_cmp);
+
+        let intervals: Vec<f64> = frames.windows(2).map(|w| (w[1] - w[0]) / 1000.0).collect();
+        let span_ms: f64 = intervals.iter().sum();
+
+        FrameStats {
+            timestamp,
+            duration_ms,
+            frame_count: frames.len() as u32,
+            avg_fps: (span_ms > 0.0).then(|| intervals.len() as f64 / (span_ms / 1000.0)),
+            long_frames: intervals.iter().filter(|&&ms| ms > LONG_FRAME_MS).count() as u32,
+            jank_frames: intervals.iter().filter(|&&ms| ms > JANK_FRAME_MS).count() as u32,
+            max_frame_ms: intervals.iter().copied().reduce(f64::max),
+            data_loss,
+        }
+    }
+}
@@ -1,11 +1,47 @@
-use super::client::{CdpClient, CdpEvent};
-use super::types::PerformanceMetrics;
-use crate::storage::{Database, StoredMetric, StoredNetworkRequest};
-use std::collections::HashMap;
+use super::client::{CdpClient, CdpError, CdpEvent};
+use super::types::{
+    CollectionStats, CollectorStatus, LiveCounters, MetricsSnapshot, NetworkRequestInfo,
+    NetworkResponseInfo, PageLoadTiming, PerformanceMetrics, RequestTiming, Screenshot, WebVitals,
+};
+use crate::adb::{self, AdbError};
+use crate::storage::{
+    Alert, AlertKind, AlertThresholds, ConsoleLog, Database, MarkerKind, MetricType,
+    RequestDataChunk, ResponseBody, SecurityEvent, SessionMarker, StorageError, StoredMetric,
+    StoredNetworkRequest,
+};
+use regex::Regex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Runtime};
-use tokio::sync::{broadcast, RwLock};
-use tokio::time::{interval, Duration};
+use tokio::sync::{broadcast, watch, RwLock};
+use tokio::time::{interval, interval_at, Duration, Instant};
+
+/// Default minimum spacing between `metrics:performance` UI events
+pub const DEFAULT_EMIT_INTERVAL_MS: u64 = 100;
+
+/// Default time after which an unfinished request is considered stalled
+pub const DEFAULT_STALL_TIMEOUT_MS: u64 = 30_000;
+
+/// Default spacing between `metrics:snapshot` UI events (0 turns them off)
+pub const DEFAULT_SNAPSHOT_INTERVAL_MS: u64 = 1000;
+
+/// Default window over which network events are coalesced into one
+/// `metrics:network_batch` UI event
+pub const DEFAULT_NETWORK_BATCH_MS: u64 = 100;
+
+/// Most recent responses carried in a `MetricsSnapshot`
+const SNAPSHOT_RECENT_RESPONSES: usize = 50;
+
+/// Trim a capped session once this fraction of the cap has accumulated
+const TRIM_FRACTION: u32 = 10;
+
+/// Console messages kept per session; chatty pages roll the oldest off
+const MAX_CONSOLE_LOGS: u32 = 10_000;
+
+/// Minimum spacing (CDP seconds) between stored transfer samples per request
+const DATA_CHUNK_INTERVAL_SECS: f64 = 0.25;
 
 /// Network request tracking
 #[derive(Debug, Clone)]
@@ -14,15 +50,48 @@ pub struct TrackedRequest {
     pub url: String,
     pub method: String,
     pub request_timestamp: f64,
+    /// Wall-clock send time in epoch seconds
+    pub wall_time: f64,
     pub response_timestamp: Option<f64>,
     pub status: Option<i32>,
+    pub data_length: i64,
+    pub encoded_data_length: i64,
+    pub last_chunk_timestamp: Option<f64>,
+    pub initiator_type: String,
+    pub initiator_url: Option<String>,
+    pub initiator_line: Option<i64>,
+    pub initiator_function: Option<String>,
+    pub priority: String,
+    pub resource_type: Option<String>,
+    pub response_headers: Option<HashMap<String, String>>,
+    pub mime_type: Option<String>,
+    pub timing: Option<RequestTiming>,
+    /// When the collector first saw the request (CDP timestamps are
+    /// monotonic browser time, not comparable to the local clock)
+    pub tracked_at: Instant,
+}
+
+impl TrackedRequest {
+    /// Time since the request last made progress: its latest data chunk if
+    /// one arrived, otherwise since it was sent. The chunk's CDP timestamp is
+    /// placed on the local clock by its offset from the request's own.
+    fn idle_for(&self) -> Duration {
+        let since_sent = self.tracked_at.elapsed();
+        let Some(last_chunk) = self.last_chunk_timestamp else {
+            return since_sent;
+        };
+        let offset =
+            Duration::try_from_secs_f64(last_chunk - self.request_timestamp).unwrap_or_default();
+        since_sent.saturating_sub(offset)
+    }
 }
 
 /// Metrics event for frontend
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
 #[serde(tag = "type")]
 pub enum MetricsEvent {
     Performance(PerformanceMetrics),
+    Alert(Alert),
     NetworkRequest {
         request_id: String,
         url: String,
@@ -32,6 +101,7 @@ pub enum MetricsEvent {
     NetworkResponse {
         request_id: String,
         status: i32,
+        status_text: String,
         timestamp: f64,
         duration_ms: Option<f64>,
     },
@@ -43,16 +113,309 @@ pub enum MetricsEvent {
         duration_ms: f64,
         size_bytes: f64,
     },
+    /// `loadingFailed` from the browser; stored as failed
+    NetworkFailed {
+        request_id: String,
+        url: String,
+        method: String,
+        error_text: String,
+        canceled: bool,
+    },
+    /// No `loadingFinished` within the stall timeout; stored as failed
+    NetworkStalled {
+        request_id: String,
+        url: String,
+        method: String,
+        status: Option<i32>,
+        elapsed_ms: f64,
+    },
+    /// A document finished loading; metrics after this belong to the new page
+    PageLoad(PageLoadTiming),
+}
+
+/// Payload of every UI event a collector emits. Several connections can
+/// collect at once, so each event says which connection and session it
+/// came from.
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct CollectorEvent<T> {
+    pub connection_id: String,
+    pub session_id: String,
+    pub payload: T,
+}
+
+/// Emits a collector's Tauri events wrapped in `CollectorEvent`
+struct EventSink<R: Runtime> {
+    app_handle: AppHandle<R>,
+    connection_id: String,
+    session_id: String,
+}
+
+// A derive would require `R: Clone`
+impl<R: Runtime> Clone for EventSink<R> {
+    fn clone(&self) -> Self {
+        Self {
+            app_handle: self.app_handle.clone(),
+            connection_id: self.connection_id.clone(),
+            session_id: self.session_id.clone(),
+        }
+    }
+}
+
+impl<R: Runtime> EventSink<R> {
+    fn emit<T: serde::Serialize + Clone>(&self, event: &str, payload: T) {
+        let _ = self.app_handle.emit(
+            event,
+            CollectorEvent {
+                connection_id: self.connection_id.clone(),
+                session_id: self.session_id.clone(),
+                payload,
+            },
+        );
+    }
+}
+
+impl MetricsEvent {
+    /// Request lifecycle events, which reach the UI batched
+    fn is_network(&self) -> bool {
+        matches!(
+            self,
+            MetricsEvent::NetworkRequest { .. }
+                | MetricsEvent::NetworkResponse { .. }
+                | MetricsEvent::NetworkComplete { .. }
+                | MetricsEvent::NetworkFailed { .. }
+                | MetricsEvent::NetworkStalled { .. }
+        )
+    }
+}
+
+/// Events kept for `poll_events`; older ones are dropped
+const EVENT_LOG_CAPACITY: usize = 2000;
+
+/// A `MetricsEvent` and its position in the collector's event log
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct SequencedEvent {
+    pub seq: u64,
+    pub event: MetricsEvent,
+}
+
+/// Events after a caller's position, for consumers that pull instead of
+/// listening to Tauri events
+#[derive(Debug, Clone, serde::Serialize, specta::Type)]
+pub struct EventPage {
+    pub events: Vec<SequencedEvent>,
+    /// Highest sequence number assigned so far (0 before the first event);
+    /// pass it back as `since_seq` to continue
+    pub max_seq: u64,
+    /// Events between `since_seq` and the first returned one were dropped
+    /// from the buffer (or never recorded because the log lagged)
+    pub truncated: bool,
+}
+
+/// Bounded log of recent events with monotonic sequence numbers starting at 1.
+/// Owned by the connection so numbering continues across collector restarts.
+#[derive(Default)]
+pub struct EventLog {
+    events: VecDeque<SequencedEvent>,
+    max_seq: u64,
+}
+
+impl EventLog {
+    fn push(&mut self, event: MetricsEvent) {
+        self.max_seq += 1;
+        if self.events.len() == EVENT_LOG_CAPACITY {
+            self.events.pop_front();
+        }
+        self.events.push_back(SequencedEvent {
+            seq: self.max_seq,
+            event,
+        });
+    }
+
+    /// Account for `count` events that went by without being recorded
+    fn skip(&mut self, count: u64) {
+        self.max_seq += count;
+    }
+
+    /// Events with a sequence number above `since_seq`, oldest first, at
+    /// most `limit` of them
+    pub fn since(&self, since_seq: u64, limit: usize) -> EventPage {
+        let events: Vec<SequencedEvent> = self
+            .events
+            .iter()
+            .filter(|e| e.seq > since_seq)
+            .take(limit)
+            .cloned()
+            .collect();
+        let next_expected = since_seq + 1;
+        // A position past `max_seq` comes from another log (e.g. before an
+        // app restart); the caller missed everything we have
+        let truncated = since_seq > self.max_seq
+            || match events.first() {
+                Some(first) => first.seq > next_expected,
+                None => self.max_seq >= next_expected,
+            };
+        EventPage {
+            events,
+            max_seq: self.max_seq,
+            truncated,
+        }
+    }
+}
+
+/// Latest values for `MetricsSnapshot`, folded from the collector's own
+/// event stream
+#[derive(Default)]
+struct SnapshotState {
+    performance: Option<PerformanceMetrics>,
+    responses: VecDeque<NetworkResponseInfo>,
+}
+
+impl SnapshotState {
+    fn apply(&mut self, event: &MetricsEvent) {
+        match event {
+            MetricsEvent::Performance(metrics) => self.performance = Some(metrics.clone()),
+            MetricsEvent::NetworkResponse {
+                request_id,
+                status,
+                status_text,
+                timestamp,
+                ..
+            } => {
+                if self.responses.len() == SNAPSHOT_RECENT_RESPONSES {
+                    self.responses.pop_front();
+                }
+                self.responses.push_back(NetworkResponseInfo {
+                    request_id: request_id.clone(),
+                    status: *status,
+                    status_text: status_text.clone(),
+                    timestamp: *timestamp,
+                    encoded_data_length: None,
+                });
+            }
+            MetricsEvent::NetworkComplete {
+                request_id,
+                size_bytes,
+                ..
+            } => {
+                if let Some(response) = self
+                    .responses
+                    .iter_mut()
+                    .rev()
+                    .find(|r| &r.request_id == request_id)
+                {
+                    response.encoded_data_length = Some(*size_bytes);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Lock-free counters behind `CollectionStats`
+#[derive(Default)]
+struct CollectionCounters {
+    samples_stored: AtomicU64,
+    network_events_processed: AtomicU64,
+    events_dropped: AtomicU64,
+    poll_errors: AtomicU64,
+    storage_errors: AtomicU64,
+    requests_completed: AtomicU64,
+    bytes_completed: AtomicU64,
+    error_responses: AtomicU64,
+    console_logs_stored: AtomicU64,
+}
+
+impl CollectionCounters {
+    fn reset(&self) {
+        self.samples_stored.store(0, Ordering::Relaxed);
+        self.network_events_processed.store(0, Ordering::Relaxed);
+        self.events_dropped.store(0, Ordering::Relaxed);
+        self.poll_errors.store(0, Ordering::Relaxed);
+        self.storage_errors.store(0, Ordering::Relaxed);
+        self.requests_completed.store(0, Ordering::Relaxed);
+        self.bytes_completed.store(0, Ordering::Relaxed);
+        self.error_responses.store(0, Ordering::Relaxed);
+        self.console_logs_stored.store(0, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> CollectionStats {
+        CollectionStats {
+            samples_stored: self.samples_stored.load(Ordering::Relaxed),
+            network_events_processed: self.network_events_processed.load(Ordering::Relaxed),
+            events_dropped: self.events_dropped.load(Ordering::Relaxed),
+            poll_errors: self.poll_errors.load(Ordering::Relaxed),
+            storage_errors: self.storage_errors.load(Ordering::Relaxed),
+        }
+    }
+
+    fn live(&self) -> LiveCounters {
+        LiveCounters {
+            request_count: self.requests_completed.load(Ordering::Relaxed),
+            total_bytes: self.bytes_completed.load(Ordering::Relaxed),
+            error_count: self.error_responses.load(Ordering::Relaxed),
+        }
+    }
 }
 
 pub struct MetricsCollector<R: Runtime> {
     client: Arc<CdpClient>,
     database: Arc<Database>,
     session_id: String,
-    app_handle: Option<AppHandle<R>>,
+    /// Emits the UI events; None when running without a Tauri app
+    sink: Option<EventSink<R>>,
     requests: Arc<RwLock<HashMap<String, TrackedRequest>>>,
     event_tx: broadcast::Sender<MetricsEvent>,
     collecting: Arc<RwLock<bool>>,
+    /// Set by `stop` so background tasks exit without waiting for their
+    /// next event or tick
+    shutdown: Arc<watch::Sender<bool>>,
+    paused: Arc<RwLock<bool>>,
+    stats: Arc<CollectionCounters>,
+    /// Device and PID to sample /proc CPU and RSS for, if known
+    process_target: Option<(String, u32)>,
+    /// Where interval screenshots are written
+    screenshot_dir: Option<PathBuf>,
+    /// Take a screenshot every N polls (0 = off)
+    screenshot_every: Arc<AtomicU32>,
+    /// Unfinished requests older than this are written off as failed
+    stall_timeout: Duration,
+    /// Limits that raise `metrics:alert`, adjustable while running
+    alert_thresholds: Arc<RwLock<AlertThresholds>>,
+    /// Performance and CPU poll interval, re-read by the loops every tick
+    poll_interval: Arc<RwLock<Duration>>,
+    snapshot_state: Arc<RwLock<SnapshotState>>,
+    /// Spacing between `metrics:snapshot` events (zero = pull only)
+    snapshot_interval: Duration,
+    /// Network events are emitted to the UI in batches over this window
+    network_batch_window: Duration,
+    /// Milliseconds since epoch of the last `start`
+    started_at: Arc<RwLock<Option<i64>>>,
+    /// URL pattern and size cap of response bodies to store
+    body_capture: Option<(Regex, u32)>,
+    /// Recent events for `poll_events`, shared with the connection
+    event_log: Arc<RwLock<EventLog>>,
+}
+
+/// Resolves once the collector is stopped or dropped; every background
+/// loop selects on this so `stop` takes effect immediately
+async fn stopped(shutdown: &mut watch::Receiver<bool>) {
+    // The guard wait_for returns isn't Send; don't keep it
+    let _ = shutdown.wait_for(|stopped| *stopped).await;
+}
+
+/// Capture a screenshot and write it to `dir` as `<timestamp>.png`
+pub async fn save_screenshot(client: &CdpClient, dir: &Path) -> Result<Screenshot, CdpError> {
+    let png = client.capture_screenshot().await?;
+    let timestamp = chrono::Utc::now().timestamp_millis();
+    let path = dir.join(format!("{}.png", timestamp));
+
+    tokio::fs::create_dir_all(dir).await?;
+    tokio::fs::write(&path, png).await?;
+
+    Ok(Screenshot {
+        timestamp,
+        path: path.to_string_lossy().into_owned(),
+    })
 }
 
 impl<R: Runtime> MetricsCollector<R> {
@@ -60,85 +423,358 @@ impl<R: Runtime> MetricsCollector<R> {
         client: Arc<CdpClient>,
         database: Arc<Database>,
         session_id: String,
+        connection_id: String,
         app_handle: Option<AppHandle<R>>,
     ) -> Self {
         let (event_tx, _) = broadcast::channel(1000);
+        let sink = app_handle.map(|app_handle| EventSink {
+            app_handle,
+            connection_id,
+            session_id: session_id.clone(),
+        });
         Self {
             client,
             database,
             session_id,
-            app_handle,
+            sink,
             requests: Arc::new(RwLock::new(HashMap::new())),
             event_tx,
             collecting: Arc::new(RwLock::new(false)),
+            shutdown: Arc::new(watch::channel(false).0),
+            paused: Arc::new(RwLock::new(false)),
+            stats: Arc::new(CollectionCounters::default()),
+            process_target: None,
+            screenshot_dir: None,
+            screenshot_every: Arc::new(AtomicU32::new(0)),
+            stall_timeout: Duration::from_millis(DEFAULT_STALL_TIMEOUT_MS),
+            alert_thresholds: Arc::new(RwLock::new(AlertThresholds::default())),
+            poll_interval: Arc::new(RwLock::new(Duration::from_secs(1))),
+            snapshot_state: Arc::new(RwLock::new(SnapshotState::default())),
+            snapshot_interval: Duration::from_millis(DEFAULT_SNAPSHOT_INTERVAL_MS),
+            network_batch_window: Duration::from_millis(DEFAULT_NETWORK_BATCH_MS),
+            started_at: Arc::new(RwLock::new(None)),
+            body_capture: None,
+            event_log: Arc::new(RwLock::new(EventLog::default())),
         }
     }
 
-    /// Start collecting metrics
-    pub async fn start(&self, poll_interval_ms: u64) -> Result<(), super::client::CdpError> {
-        // Enable domains
-        self.client.enable_performance().await?;
-        self.client.enable_network().await?;
+    /// Record events into `log` instead of a log private to this collector
+    pub fn with_event_log(mut self, log: Arc<RwLock<EventLog>>) -> Self {
+        self.event_log = log;
+        self
+    }
+
+    /// Emit a consolidated `metrics:snapshot` every `interval`; zero only
+    /// keeps `snapshot` up to date
+    pub fn with_snapshot_interval(mut self, interval: Duration) -> Self {
+        self.snapshot_interval = interval;
+        self
+    }
+
+    /// Coalesce network events over `window` before emitting them as one
+    /// `metrics:network_batch`; zero emits each event in its own batch
+    pub fn with_network_batch_window(mut self, window: Duration) -> Self {
+        self.network_batch_window = window;
+        self
+    }
+
+    /// Store the bodies of finished requests whose URL matches
+    /// `url_pattern`; bodies over `max_bytes` are skipped
+    pub fn with_body_capture(mut self, url_pattern: Regex, max_bytes: u32) -> Self {
+        self.body_capture = Some((url_pattern, max_bytes));
+        self
+    }
+
+    /// Check incoming samples and requests against `thresholds`
+    pub fn with_alert_thresholds(mut self, thresholds: AlertThresholds) -> Self {
+        self.alert_thresholds = Arc::new(RwLock::new(thresholds));
+        self
+    }
+
+    /// Replace the alert thresholds; takes effect on the running collector
+    pub async fn set_alert_thresholds(&self, thresholds: AlertThresholds) {
+        *self.alert_thresholds.write().await = thresholds;
+    }
+
+    /// Override how long a request may run before it is marked stalled
+    pub fn with_stall_timeout(mut self, timeout: Duration) -> Self {
+        self.stall_timeout = timeout;
+        self
+    }
+
+    /// Also sample process CPU usage and RSS over ADB while collecting
+    pub fn with_process_sampling(mut self, device_id: String, pid: u32) -> Self {
+        self.process_target = Some((device_id, pid));
+        self
+    }
+
+    /// Write interval screenshots (see `set_screenshot_interval`) to `dir`
+    pub fn with_screenshot_dir(mut self, dir: PathBuf) -> Self {
+        self.screenshot_dir = Some(dir);
+        self
+    }
+
+    /// Take a screenshot every `every_n_polls` polls; 0 turns it off.
+    /// Takes effect on the running poll loop.
+    pub fn set_screenshot_interval(&self, every_n_polls: u32) {
+        self.screenshot_every
+            .store(every_n_polls, Ordering::Relaxed);
+    }
+
+    /// Change the poll interval of a running collector. Takes effect after
+    /// the current tick; network tracking is unaffected.
+    pub async fn set_interval(&self, poll_interval_ms: u64) {
+        *self.poll_interval.write().await = Duration::from_millis(poll_interval_ms.max(1));
+    }
+
+    /// Start collecting metrics.
+    /// With `max_samples` set, the session keeps only the most recent samples
+    /// (a rolling window), trimmed periodically rather than on every insert.
+    /// Every sample is stored, but `metrics:performance` UI events are
+    /// throttled to at most one per `emit_interval_ms`.
+    pub async fn start(
+        &self,
+        poll_interval_ms: u64,
+        max_samples: Option<u32>,
+        emit_interval_ms: u64,
+    ) -> Result<(), super::client::CdpError> {
+        Self::enable_domains(&self.client).await?;
+        self.stats.reset();
+        self.set_interval(poll_interval_ms).await;
 
         {
             let mut collecting = self.collecting.write().await;
             *collecting = true;
         }
+        self.shutdown.send_replace(false);
+        *self.started_at.write().await = Some(chrono::Utc::now().timestamp_millis());
+        self.record_marker(MarkerKind::CollectionStarted);
+        self.spawn_snapshot_task();
+        self.spawn_event_logger();
+        self.spawn_network_batcher();
+        self.spawn_body_capture();
 
         // Start performance polling
         let client = self.client.clone();
         let event_tx = self.event_tx.clone();
-        let collecting = self.collecting.clone();
+        let paused = self.paused.clone();
         let database = self.database.clone();
         let session_id = self.session_id.clone();
-        let app_handle = self.app_handle.clone();
+        let sink = self.sink.clone();
+        let stats = self.stats.clone();
+        let process_target = self.process_target.clone();
+        let screenshot_dir = self.screenshot_dir.clone();
+        let screenshot_every = self.screenshot_every.clone();
+        let requests = self.requests.clone();
+        let stall_timeout = self.stall_timeout;
+        let alert_thresholds = self.alert_thresholds.clone();
+        let poll_interval = self.poll_interval.clone();
+        let mut shutdown = self.shutdown.subscribe();
 
         tokio::spawn(async move {
-            let mut ticker = interval(Duration::from_millis(poll_interval_ms));
+            let mut current_interval = *poll_interval.read().await;
+            let mut ticker = interval(current_interval);
+            let trim_every = max_samples.map(|max| (max / TRIM_FRACTION).max(1));
+            let mut stored_since_trim = 0u32;
+            let emit_interval = Duration::from_millis(emit_interval_ms);
+            let mut last_emit: Option<Instant> = None;
+            let mut last_vitals: Option<WebVitals> = None;
+            let mut polls = 0u32;
+            // Performance alerts fire once on crossing a limit and re-arm
+            // when the value drops back under it
+            let mut over_limit: HashSet<AlertKind> = HashSet::new();
 
             loop {
-                ticker.tick().await;
+                tokio::select! {
+                    biased;
+                    _ = stopped(&mut shutdown) => break,
+                    _ = ticker.tick() => {}
+                }
+
+                let wanted_interval = *poll_interval.read().await;
+                if wanted_interval != current_interval {
+                    current_interval = wanted_interval;
+                    ticker = interval_at(Instant::now() + current_interval, current_interval);
+                }
 
-                let is_collecting = *collecting.read().await;
-                if !is_collecting {
-                    break;
+                if *paused.read().await {
+                    continue;
                 }
 
-                if let Ok(metrics) = client.get_performance_metrics().await {
+                Self::sweep_stalled_requests(
+                    &requests,
+                    stall_timeout,
+                    &event_tx,
+                    &database,
+                    &session_id,
+                )
+                .await;
+
+                if let Ok(metrics) = client.get_performance_metrics(false).await {
                     // Store to database
                     if let Ok(stored_metric) = StoredMetric::from_performance(&session_id, &metrics)
                     {
-                        let _ = database.store_metric(&stored_metric);
+                        if database.store_metric(&stored_metric).is_ok() {
+                            stored_since_trim += 1;
+                            stats.samples_stored.fetch_add(1, Ordering::Relaxed);
+                        } else {
+                            stats.storage_errors.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+
+                    let exceeded = alert_thresholds.read().await.exceeded_by(&metrics);
+                    over_limit.retain(|kind| exceeded.iter().any(|(k, _, _)| k == kind));
+                    for (kind, value, threshold) in exceeded {
+                        if over_limit.insert(kind) {
+                            let alert = Alert::new(&session_id, kind, value, threshold);
+                            Self::raise_alert(alert, &event_tx, &database, &sink);
+                        }
+                    }
+
+                    // Enforce the rolling sample window
+                    if let (Some(max), Some(every)) = (max_samples, trim_every) {
+                        if stored_since_trim >= every {
+                            stored_since_trim = 0;
+                            let _ = database.prune_metrics(&session_id, max);
+                        }
                     }
 
-                    // Emit Tauri event
-                    if let Some(ref handle) = app_handle {
-                        let _ = handle.emit("metrics:performance", &metrics);
+                    // Emit Tauri events (throttled; the UI only needs the latest value)
+                    let due = last_emit.is_none_or(|at| at.elapsed() >= emit_interval);
+                    if let (Some(sink), true) = (&sink, due) {
+                        last_emit = Some(Instant::now());
+                        sink.emit("metrics:performance", &metrics);
                     }
 
                     // Broadcast internally
                     let _ = event_tx.send(MetricsEvent::Performance(metrics));
+
+                    // DOM counters and RSS on the same interval
+                    if let Ok(mut memory) = client.get_memory_info().await {
+                        if let (Some(sink), Some((device_id, pid))) = (&sink, &process_target) {
+                            memory.rss_kb = adb::get_process_rss(&sink.app_handle, device_id, *pid)
+                                .await
+                                .ok();
+                        }
+                        if let Ok(stored_metric) = StoredMetric::from_memory(&session_id, &memory) {
+                            if database.store_metric(&stored_metric).is_ok() {
+                                stats.samples_stored.fetch_add(1, Ordering::Relaxed);
+                            } else {
+                                stats.storage_errors.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                        if let (Some(sink), true) = (&sink, due) {
+                            sink.emit("metrics:memory", &memory);
+                        }
+                    }
+
+                    // Web Vitals only change on paints, shifts and input;
+                    // store a snapshot only when a value moved
+                    if let Ok(vitals) = client.collect_web_vitals().await {
+                        let changed = last_vitals.as_ref().is_none_or(|last| {
+                            WebVitals {
+                                timestamp: vitals.timestamp,
+                                ..last.clone()
+                            } != vitals
+                        });
+                        if changed {
+                            if let Ok(stored_metric) =
+                                StoredMetric::from_web_vitals(&session_id, &vitals)
+                            {
+                                if database.store_metric(&stored_metric).is_ok() {
+                                    stats.samples_stored.fetch_add(1, Ordering::Relaxed);
+                                } else {
+                                    stats.storage_errors.fetch_add(1, Ordering::Relaxed);
+                                }
+                            }
+                            if let Some(sink) = &sink {
+                                sink.emit("metrics:webvitals", &vitals);
+                            }
+                            last_vitals = Some(vitals);
+                        }
+                    }
+
+                    // Screenshots are slow; take them off the poll loop
+                    polls = polls.wrapping_add(1);
+                    let every = screenshot_every.load(Ordering::Relaxed);
+                    if let (Some(dir), true) =
+                        (&screenshot_dir, every > 0 && polls.is_multiple_of(every))
+                    {
+                        let client = client.clone();
+                        let dir = dir.clone();
+                        let sink = sink.clone();
+                        tokio::spawn(async move {
+                            match save_screenshot(&client, &dir).await {
+                                Ok(screenshot) => {
+                                    if let Some(sink) = &sink {
+                                        sink.emit("metrics:screenshot", &screenshot);
+                                    }
+                                }
+                                Err(e) => tracing::warn!("Interval screenshot failed: {}", e),
+                            }
+                        });
+                    }
+                } else {
+                    stats.poll_errors.fetch_add(1, Ordering::Relaxed);
                 }
             }
         });
 
+        if let (Some(sink), Some((device_id, pid))) = (&self.sink, &self.process_target) {
+            self.spawn_cpu_sampler(sink.clone(), device_id.clone(), *pid);
+        }
+
         // Start processing CDP events
         let mut cdp_rx = self.client.subscribe();
+        let client = self.client.clone();
         let requests = self.requests.clone();
         let event_tx = self.event_tx.clone();
         let collecting = self.collecting.clone();
+        let paused = self.paused.clone();
         let database = self.database.clone();
         let session_id = self.session_id.clone();
-        let app_handle = self.app_handle.clone();
+        let sink = self.sink.clone();
+        let stats = self.stats.clone();
+        let alert_thresholds = self.alert_thresholds.clone();
+        let shutdown = self.shutdown.clone();
+        let mut stop_rx = self.shutdown.subscribe();
 
         tokio::spawn(async move {
             loop {
-                let is_collecting = *collecting.read().await;
-                if !is_collecting {
-                    break;
-                }
+                let event = tokio::select! {
+                    biased;
+                    _ = stopped(&mut stop_rx) => break,
+                    event = cdp_rx.recv() => event,
+                };
 
-                match cdp_rx.recv().await {
+                match event {
+                    // Domains are per connection; re-enable after an auto reconnect
+                    Ok(CdpEvent::Connected) => {
+                        if let Err(e) = Self::enable_domains(&client).await {
+                            tracing::warn!("Failed to re-enable domains after reconnect: {}", e);
+                        }
+                    }
+                    // The page is gone for good; don't leave the session active
+                    Ok(CdpEvent::ConnectionLost) => {
+                        *collecting.write().await = false;
+                        shutdown.send_replace(true);
+                        let ended_at = chrono::Utc::now().timestamp_millis();
+                        match database.abort_session(&session_id, ended_at, Some("connection lost"))
+                        {
+                            Ok(true) => {
+                                tracing::warn!("Session {} aborted: connection lost", session_id);
+                                if let Some(sink) = &sink {
+                                    sink.emit("session:aborted", "connection lost");
+                                }
+                            }
+                            Ok(false) => {}
+                            Err(e) => {
+                                tracing::warn!("Failed to abort session {}: {}", session_id, e);
+                            }
+                        }
+                        break;
+                    }
+                    Ok(_) if *paused.read().await => continue,
                     Ok(event) => {
                         Self::process_cdp_event(
                             event,
@@ -146,11 +782,15 @@ impl<R: Runtime> MetricsCollector<R> {
                             &event_tx,
                             &database,
                             &session_id,
-                            &app_handle,
+                            &sink,
+                            &alert_thresholds,
+                            &stats,
                         )
                         .await;
                     }
-                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        stats.events_dropped.fetch_add(skipped, Ordering::Relaxed);
+                    }
                     Err(broadcast::error::RecvError::Closed) => break,
                 }
             }
@@ -159,20 +799,421 @@ impl<R: Runtime> MetricsCollector<R> {
         Ok(())
     }
 
+    async fn enable_domains(client: &CdpClient) -> Result<(), super::client::CdpError> {
+        client.enable_performance().await?;
+        client.enable_network().await?;
+        // Not every WebView exposes the Security domain; collect without it
+        if let Err(e) = client.enable_security().await {
+            tracing::warn!("Security domain unavailable: {}", e);
+        }
+        if let Err(e) = client.enable_web_vitals().await {
+            tracing::warn!("Web Vitals observers unavailable: {}", e);
+        }
+        if let Err(e) = client.enable_console().await {
+            tracing::warn!("Console capture unavailable: {}", e);
+        }
+        if let Err(e) = client.enable_page_lifecycle().await {
+            tracing::warn!("Page lifecycle events unavailable: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Latest performance sample with in-flight requests and recent
+    /// responses, as one coherent frame
+    pub async fn snapshot(&self) -> MetricsSnapshot {
+        Self::build_snapshot(
+            &*self.requests.read().await,
+            &*self.snapshot_state.read().await,
+        )
+    }
+
+    fn build_snapshot(
+        requests: &HashMap<String, TrackedRequest>,
+        state: &SnapshotState,
+    ) -> MetricsSnapshot {
+        let mut network_requests: Vec<NetworkRequestInfo> = requests
+            .values()
+            .map(|req| NetworkRequestInfo {
+                request_id: req.request_id.clone(),
+                url: req.url.clone(),
+                method: req.method.clone(),
+                timestamp: req.request_timestamp,
+                resource_type: req.resource_type.clone(),
+            })
+            .collect();
+        network_requests.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+
+        MetricsSnapshot {
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            performance: state.performance.clone(),
+            network_requests,
+            network_responses: state.responses.iter().cloned().collect(),
+        }
+    }
+
+    /// Keep the snapshot state current from the internal event stream and
+    /// emit `metrics:snapshot` on the configured cadence
+    fn spawn_snapshot_task(&self) {
+        let emit = self
+            .sink
+            .clone()
+            .map(|sink| move |snapshot: &MetricsSnapshot| sink.emit("metrics:snapshot", snapshot));
+        self.spawn_snapshot_loop(emit);
+    }
+
+    /// Body of the snapshot task; `emit` receives each periodic snapshot and
+    /// is skipped entirely when the snapshot interval is zero
+    fn spawn_snapshot_loop<F>(&self, emit: Option<F>)
+    where
+        F: Fn(&MetricsSnapshot) + Send + 'static,
+    {
+        let mut events = self.event_tx.subscribe();
+        let mut shutdown = self.shutdown.subscribe();
+        let requests = self.requests.clone();
+        let state = self.snapshot_state.clone();
+        let emit = emit.filter(|_| !self.snapshot_interval.is_zero());
+        // interval() rejects zero; the tick branch is disabled then anyway
+        let mut ticker = interval(self.snapshot_interval.max(Duration::from_millis(1)));
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = stopped(&mut shutdown) => break,
+                    event = events.recv() => match event {
+                        Ok(event) => state.write().await.apply(&event),
+                        Err(broadcast::error::RecvError::Lagged(_)) => {}
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = ticker.tick(), if emit.is_some() => {
+                        let snapshot =
+                            Self::build_snapshot(&*requests.read().await, &*state.read().await);
+                        if let Some(emit) = &emit {
+                            emit(&snapshot);
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Record the internal event stream into the event log. Events missed
+    /// because the logger lagged still consume sequence numbers, so pollers
+    /// see the gap.
+    fn spawn_event_logger(&self) {
+        let mut events = self.event_tx.subscribe();
+        let mut shutdown = self.shutdown.subscribe();
+        let log = self.event_log.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    biased;
+                    _ = stopped(&mut shutdown) => break,
+                    event = events.recv() => event,
+                };
+                match event {
+                    Ok(event) => log.write().await.push(event),
+                    Err(broadcast::error::RecvError::Lagged(n)) => log.write().await.skip(n),
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    /// Forward network events from the internal stream to the UI as
+    /// `metrics:network_batch` arrays, flushed once per batch window and a
+    /// last time when collection stops. The broadcast itself stays per-event.
+    fn spawn_network_batcher(&self) {
+        let Some(sink) = self.sink.clone() else {
+            return;
+        };
+        let mut events = self.event_tx.subscribe();
+        let mut shutdown = self.shutdown.subscribe();
+        let window = self.network_batch_window;
+        let mut ticker = interval(window.max(Duration::from_millis(1)));
+
+        tokio::spawn(async move {
+            let mut batch: Vec<MetricsEvent> = Vec::new();
+            let flush = |batch: &mut Vec<MetricsEvent>| {
+                if !batch.is_empty() {
+                    sink.emit("metrics:network_batch", &*batch);
+                    batch.clear();
+                }
+            };
+
+            loop {
+                tokio::select! {
+                    biased;
+                    _ = stopped(&mut shutdown) => break,
+                    event = events.recv() => match event {
+                        Ok(event) if event.is_network() => {
+                            batch.push(event);
+                            if window.is_zero() {
+                                flush(&mut batch);
+                            }
+                        }
+                        Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = ticker.tick() => flush(&mut batch),
+                }
+            }
+
+            // Events that were already broadcast still reach the UI
+            while let Ok(event) = events.try_recv() {
+                if event.is_network() {
+                    batch.push(event);
+                }
+            }
+            flush(&mut batch);
+        });
+    }
+
+    /// Fetch and store bodies of matching requests as they finish. Bodies
+    /// are only available until the browser evicts them, so each fetch
+    /// runs on its own task instead of queueing behind slower ones.
+    fn spawn_body_capture(&self) {
+        let Some((url_pattern, max_bytes)) = self.body_capture.clone() else {
+            return;
+        };
+        let mut events = self.event_tx.subscribe();
+        let mut shutdown = self.shutdown.subscribe();
+        let client = self.client.clone();
+        let database = self.database.clone();
+        let session_id = self.session_id.clone();
+        let stats = self.stats.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    biased;
+                    _ = stopped(&mut shutdown) => break,
+                    event = events.recv() => event,
+                };
+                let (request_id, url, size_bytes) = match event {
+                    Ok(MetricsEvent::NetworkComplete {
+                        request_id,
+                        url,
+                        size_bytes,
+                        ..
+                    }) => (request_id, url, size_bytes),
+                    Err(broadcast::error::RecvError::Closed) => break,
+                    _ => continue,
+                };
+                // The transfer size is only a hint (compression, headers);
+                // the decoded size is checked again when storing
+                if !url_pattern.is_match(&url) || size_bytes > f64::from(max_bytes) {
+                    continue;
+                }
+
+                let client = client.clone();
+                let database = database.clone();
+                let session_id = session_id.clone();
+                let stats = stats.clone();
+                tokio::spawn(async move {
+                    let content = match client.get_response_body(&request_id).await {
+                        Ok(content) => content,
+                        Err(e) => {
+                            tracing::debug!("No body for request {}: {}", request_id, e);
+                            return;
+                        }
+                    };
+                    let body = ResponseBody {
+                        id: None,
+                        session_id,
+                        request_id,
+                        url,
+                        timestamp: chrono::Utc::now().timestamp_millis(),
+                        size_bytes: content.size_bytes() as i64,
+                        base64_encoded: content.base64_encoded,
+                        body: content.body,
+                    };
+                    match database.store_response_body(&body, i64::from(max_bytes)) {
+                        Ok(_) | Err(StorageError::BodyTooLarge { .. }) => {}
+                        Err(_) => {
+                            stats.storage_errors.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    /// Sample CPU usage back to back; each sample spans one poll interval,
+    /// so the loop paces itself. Stops when the process goes away.
+    fn spawn_cpu_sampler(&self, sink: EventSink<R>, device_id: String, pid: u32) {
+        let mut shutdown = self.shutdown.subscribe();
+        let paused = self.paused.clone();
+        let database = self.database.clone();
+        let session_id = self.session_id.clone();
+        let stats = self.stats.clone();
+        let poll_interval = self.poll_interval.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let interval = *poll_interval.read().await;
+                if *paused.read().await {
+                    tokio::select! {
+                        biased;
+                        _ = stopped(&mut shutdown) => break,
+                        _ = tokio::time::sleep(interval) => continue,
+                    }
+                }
+
+                let sample = tokio::select! {
+                    biased;
+                    _ = stopped(&mut shutdown) => break,
+                    sample = adb::sample_cpu(&sink.app_handle, &device_id, pid, interval) => sample,
+                };
+                match sample {
+                    Ok(usage) => {
+                        let Ok(data) = serde_json::to_string(&usage) else {
+                            continue;
+                        };
+                        let metric = StoredMetric {
+                            id: None,
+                            session_id: session_id.clone(),
+                            timestamp: usage.timestamp,
+                            metric_type: MetricType::Cpu,
+                            data,
+                        };
+                        if database.store_metric(&metric).is_ok() {
+                            stats.samples_stored.fetch_add(1, Ordering::Relaxed);
+                        } else {
+                            stats.storage_errors.fetch_add(1, Ordering::Relaxed);
+                        }
+                        sink.emit("metrics:cpu", &usage);
+                    }
+                    Err(AdbError::ProcessNotFound(pid)) => {
+                        tracing::warn!("Process {} exited; stopping CPU sampling", pid);
+                        break;
+                    }
+                    Err(_) => {
+                        stats.poll_errors.fetch_add(1, Ordering::Relaxed);
+                        tokio::select! {
+                            biased;
+                            _ = stopped(&mut shutdown) => break,
+                            _ = tokio::time::sleep(interval) => {}
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Write off requests with no progress for `stall_timeout` so the map
+    /// can't grow without bound and hung requests show up as failed
+    async fn sweep_stalled_requests(
+        requests: &Arc<RwLock<HashMap<String, TrackedRequest>>>,
+        stall_timeout: Duration,
+        event_tx: &broadcast::Sender<MetricsEvent>,
+        database: &Arc<Database>,
+        session_id: &str,
+    ) {
+        let stalled: Vec<TrackedRequest> = {
+            let mut reqs = requests.write().await;
+            let ids: Vec<String> = reqs
+                .values()
+                .filter(|req| req.idle_for() >= stall_timeout)
+                .map(|req| req.request_id.clone())
+                .collect();
+            ids.iter().filter_map(|id| reqs.remove(id)).collect()
+        };
+
+        for req in stalled {
+            let idle_ms = req.idle_for().as_millis();
+            let stored_request = StoredNetworkRequest {
+                id: req.request_id.clone(),
+                session_id: session_id.to_string(),
+                url: req.url.clone(),
+                method: Some(req.method.clone()),
+                status_code: None,
+                request_time: (req.request_timestamp * 1000.0) as i64,
+                wall_time: Some((req.wall_time * 1000.0) as i64),
+                response_time: None,
+                duration_ms: None,
+                size_bytes: None,
+                decoded_size_bytes: None,
+                headers: req.response_headers,
+                initiator_type: Some(req.initiator_type),
+                initiator_url: req.initiator_url,
+                initiator_line: req.initiator_line,
+                initiator_function: req.initiator_function,
+                priority: Some(req.priority),
+                mime_type: req.mime_type,
+                resource_type: req.resource_type,
+                timing: req.timing,
+                failed: true,
+                error_text: Some(format!("stalled: no data for {} ms", idle_ms)),
+            };
+            let _ = database.store_network_request(&stored_request);
+
+            let metrics_event = MetricsEvent::NetworkStalled {
+                request_id: req.request_id,
+                url: req.url,
+                method: req.method,
+                status: req.status,
+                elapsed_ms: req.tracked_at.elapsed().as_secs_f64() * 1000.0,
+            };
+
+            let _ = event_tx.send(metrics_event);
+        }
+    }
+
+    /// Store an alert and emit it as `metrics:alert`
+    fn raise_alert(
+        mut alert: Alert,
+        event_tx: &broadcast::Sender<MetricsEvent>,
+        database: &Arc<Database>,
+        sink: &Option<EventSink<R>>,
+    ) {
+        alert.id = database.store_alert(&alert).ok();
+
+        // Emit Tauri event
+        if let Some(sink) = sink {
+            sink.emit("metrics:alert", &alert);
+        }
+
+        let _ = event_tx.send(MetricsEvent::Alert(alert));
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn process_cdp_event(
         event: CdpEvent,
         requests: &Arc<RwLock<HashMap<String, TrackedRequest>>>,
         event_tx: &broadcast::Sender<MetricsEvent>,
         database: &Arc<Database>,
         session_id: &str,
-        app_handle: &Option<AppHandle<R>>,
+        sink: &Option<EventSink<R>>,
+        alert_thresholds: &Arc<RwLock<AlertThresholds>>,
+        stats: &CollectionCounters,
     ) {
+        if matches!(
+            event,
+            CdpEvent::NetworkRequest { .. }
+                | CdpEvent::NetworkResponse { .. }
+                | CdpEvent::NetworkDataReceived { .. }
+                | CdpEvent::NetworkFinished { .. }
+                | CdpEvent::NetworkFailed { .. }
+        ) {
+            stats
+                .network_events_processed
+                .fetch_add(1, Ordering::Relaxed);
+        }
+
         match event {
             CdpEvent::NetworkRequest {
                 request_id,
                 url,
                 method,
                 timestamp,
+                wall_time,
+                initiator_type,
+                initiator_url,
+                initiator_line,
+                initiator_function,
+                priority,
+                resource_type,
             } => {
                 let mut reqs = requests.write().await;
                 reqs.insert(
@@ -182,8 +1223,22 @@ impl<R: Runtime> MetricsCollector<R> {
                         url: url.clone(),
                         method: method.clone(),
                         request_timestamp: timestamp,
+                        wall_time,
                         response_timestamp: None,
                         status: None,
+                        data_length: 0,
+                        encoded_data_length: 0,
+                        last_chunk_timestamp: None,
+                        initiator_type: initiator_type.clone(),
+                        initiator_url: initiator_url.clone(),
+                        initiator_line,
+                        initiator_function: initiator_function.clone(),
+                        priority: priority.clone(),
+                        resource_type: resource_type.clone(),
+                        response_headers: None,
+                        mime_type: None,
+                        timing: None,
+                        tracked_at: Instant::now(),
                     },
                 );
 
@@ -196,10 +1251,22 @@ impl<R: Runtime> MetricsCollector<R> {
                     method: Some(method.clone()),
                     status_code: None,
                     request_time,
+                    wall_time: Some((wall_time * 1000.0) as i64),
                     response_time: None,
                     duration_ms: None,
                     size_bytes: None,
+                    decoded_size_bytes: None,
                     headers: None,
+                    initiator_type: Some(initiator_type),
+                    initiator_url,
+                    initiator_line,
+                    initiator_function,
+                    priority: Some(priority),
+                    mime_type: None,
+                    resource_type,
+                    timing: None,
+                    failed: false,
+                    error_text: None,
                 };
                 let _ = database.store_network_request(&stored_request);
 
@@ -210,22 +1277,24 @@ impl<R: Runtime> MetricsCollector<R> {
                     timestamp,
                 };
 
-                // Emit Tauri event
-                if let Some(ref handle) = app_handle {
-                    let _ = handle.emit("metrics:network", &metrics_event);
-                }
-
                 let _ = event_tx.send(metrics_event);
             }
             CdpEvent::NetworkResponse {
                 request_id,
                 status,
+                status_text,
                 timestamp,
+                headers,
+                mime_type,
+                timing,
             } => {
                 let mut reqs = requests.write().await;
                 let duration_ms = if let Some(req) = reqs.get_mut(&request_id) {
                     req.response_timestamp = Some(timestamp);
                     req.status = Some(status);
+                    req.response_headers = Some(headers);
+                    req.mime_type = Some(mime_type);
+                    req.timing = timing;
                     Some((timestamp - req.request_timestamp) * 1000.0)
                 } else {
                     None
@@ -234,17 +1303,42 @@ impl<R: Runtime> MetricsCollector<R> {
                 let metrics_event = MetricsEvent::NetworkResponse {
                     request_id: request_id.clone(),
                     status,
+                    status_text,
                     timestamp,
                     duration_ms,
                 };
 
-                // Emit Tauri event
-                if let Some(ref handle) = app_handle {
-                    let _ = handle.emit("metrics:network", &metrics_event);
-                }
-
                 let _ = event_tx.send(metrics_event);
             }
+            CdpEvent::NetworkDataReceived {
+                request_id,
+                data_length,
+                encoded_data_length,
+                timestamp,
+            } => {
+                let mut reqs = requests.write().await;
+                if let Some(req) = reqs.get_mut(&request_id) {
+                    req.data_length += data_length;
+                    req.encoded_data_length += encoded_data_length;
+
+                    // Coalesce so fast streams don't write a row per CDP event
+                    let due = req
+                        .last_chunk_timestamp
+                        .is_none_or(|last| timestamp - last >= DATA_CHUNK_INTERVAL_SECS);
+                    if due {
+                        req.last_chunk_timestamp = Some(timestamp);
+                        let chunk = RequestDataChunk {
+                            id: None,
+                            session_id: session_id.to_string(),
+                            request_id,
+                            timestamp: (timestamp * 1000.0) as i64,
+                            data_length: req.data_length,
+                            encoded_data_length: req.encoded_data_length,
+                        };
+                        let _ = database.store_request_data_chunk(&chunk);
+                    }
+                }
+            }
             CdpEvent::NetworkFinished {
                 request_id,
                 encoded_data_length,
@@ -252,8 +1346,27 @@ impl<R: Runtime> MetricsCollector<R> {
             } => {
                 let mut reqs = requests.write().await;
                 if let Some(req) = reqs.remove(&request_id) {
+                    // Close the transfer timeline with the final totals
+                    if req.last_chunk_timestamp.is_some() {
+                        let chunk = RequestDataChunk {
+                            id: None,
+                            session_id: session_id.to_string(),
+                            request_id: req.request_id.clone(),
+                            timestamp: (timestamp * 1000.0) as i64,
+                            data_length: req.data_length,
+                            encoded_data_length: encoded_data_length as i64,
+                        };
+                        let _ = database.store_request_data_chunk(&chunk);
+                    }
+
                     let duration_ms = (timestamp - req.request_timestamp) * 1000.0;
                     let response_time = (timestamp * 1000.0) as i64;
+                    let timing = req.timing.clone().map(|timing| RequestTiming {
+                        download_ms: req
+                            .response_timestamp
+                            .map(|headers_at| ((timestamp - headers_at) * 1000.0).max(0.0)),
+                        ..timing
+                    });
 
                     // Update network request in database with complete info
                     let stored_request = StoredNetworkRequest {
@@ -263,13 +1376,45 @@ impl<R: Runtime> MetricsCollector<R> {
                         method: Some(req.method.clone()),
                         status_code: req.status,
                         request_time: (req.request_timestamp * 1000.0) as i64,
+                        wall_time: Some((req.wall_time * 1000.0) as i64),
                         response_time: Some(response_time),
                         duration_ms: Some(duration_ms),
                         size_bytes: Some(encoded_data_length),
-                        headers: None,
+                        decoded_size_bytes: req
+                            .last_chunk_timestamp
+                            .map(|_| req.data_length as f64),
+                        headers: req.response_headers.clone(),
+                        initiator_type: Some(req.initiator_type.clone()),
+                        initiator_url: req.initiator_url.clone(),
+                        initiator_line: req.initiator_line,
+                        initiator_function: req.initiator_function.clone(),
+                        priority: Some(req.priority.clone()),
+                        mime_type: req.mime_type.clone(),
+                        resource_type: req.resource_type.clone(),
+                        timing,
+                        failed: false,
+                        error_text: None,
                     };
                     let _ = database.store_network_request(&stored_request);
 
+                    let alert = alert_thresholds.read().await.check_request(
+                        session_id,
+                        &req.request_id,
+                        &req.url,
+                        duration_ms,
+                    );
+                    if let Some(alert) = alert {
+                        Self::raise_alert(alert, event_tx, database, sink);
+                    }
+
+                    stats.requests_completed.fetch_add(1, Ordering::Relaxed);
+                    stats
+                        .bytes_completed
+                        .fetch_add(encoded_data_length as u64, Ordering::Relaxed);
+                    if req.status.is_some_and(|status| status >= 400) {
+                        stats.error_responses.fetch_add(1, Ordering::Relaxed);
+                    }
+
                     let metrics_event = MetricsEvent::NetworkComplete {
                         request_id: req.request_id,
                         url: req.url,
@@ -279,12 +1424,108 @@ impl<R: Runtime> MetricsCollector<R> {
                         size_bytes: encoded_data_length,
                     };
 
-                    // Emit Tauri event
-                    if let Some(ref handle) = app_handle {
-                        let _ = handle.emit("metrics:network", &metrics_event);
+                    let _ = event_tx.send(metrics_event);
+                }
+            }
+            CdpEvent::NetworkFailed {
+                request_id,
+                timestamp,
+                error_text,
+                canceled,
+            } => {
+                let Some(req) = requests.write().await.remove(&request_id) else {
+                    return;
+                };
+
+                let stored_request = StoredNetworkRequest {
+                    id: req.request_id.clone(),
+                    session_id: session_id.to_string(),
+                    url: req.url.clone(),
+                    method: Some(req.method.clone()),
+                    status_code: req.status,
+                    request_time: (req.request_timestamp * 1000.0) as i64,
+                    wall_time: Some((req.wall_time * 1000.0) as i64),
+                    response_time: Some((timestamp * 1000.0) as i64),
+                    duration_ms: Some((timestamp - req.request_timestamp) * 1000.0),
+                    size_bytes: None,
+                    decoded_size_bytes: None,
+                    headers: req.response_headers,
+                    initiator_type: Some(req.initiator_type),
+                    initiator_url: req.initiator_url,
+                    initiator_line: req.initiator_line,
+                    initiator_function: req.initiator_function,
+                    priority: Some(req.priority),
+                    mime_type: req.mime_type,
+                    resource_type: req.resource_type,
+                    timing: req.timing,
+                    failed: true,
+                    error_text: Some(error_text.clone()),
+                };
+                let _ = database.store_network_request(&stored_request);
+
+                let _ = event_tx.send(MetricsEvent::NetworkFailed {
+                    request_id: req.request_id,
+                    url: req.url,
+                    method: req.method,
+                    error_text,
+                    canceled,
+                });
+            }
+            CdpEvent::PageLoad(timing) => {
+                if let Ok(metric) = StoredMetric::from_page_load(session_id, &timing) {
+                    let _ = database.store_metric(&metric);
+                }
+
+                // Emit Tauri event
+                if let Some(sink) = sink {
+                    sink.emit("metrics:navigation", &timing);
+                }
+                let _ = event_tx.send(MetricsEvent::PageLoad(timing));
+            }
+            CdpEvent::SecurityState { state, summary } => {
+                let security_event = SecurityEvent {
+                    id: None,
+                    session_id: session_id.to_string(),
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                    state,
+                    summary,
+                };
+                let _ = database.store_security_event(&security_event);
+
+                // Emit Tauri event
+                if let Some(sink) = sink {
+                    sink.emit("metrics:security", &security_event);
+                }
+            }
+            CdpEvent::ConsoleMessage {
+                level,
+                text,
+                timestamp,
+                url,
+                line,
+            } => {
+                let mut console_log = ConsoleLog {
+                    id: None,
+                    session_id: session_id.to_string(),
+                    timestamp: timestamp as i64,
+                    level,
+                    text,
+                    url,
+                    line,
+                };
+                console_log.id = database.store_console_log(&console_log).ok();
+
+                // Trim every so many inserts of this session rather than per insert
+                if console_log.id.is_some() {
+                    let stored = stats.console_logs_stored.fetch_add(1, Ordering::Relaxed) + 1;
+                    if stored.is_multiple_of(u64::from(MAX_CONSOLE_LOGS / TRIM_FRACTION)) {
+                        let _ = database.prune_console_logs(session_id, MAX_CONSOLE_LOGS);
                     }
+                }
 
-                    let _ = event_tx.send(metrics_event);
+                // Emit Tauri event
+                if let Some(sink) = sink {
+                    sink.emit("metrics:console", &console_log);
                 }
             }
             _ => {}
@@ -293,7 +1534,442 @@ impl<R: Runtime> MetricsCollector<R> {
 
     /// Stop collecting metrics
     pub async fn stop(&self) {
+        self.shutdown.send_replace(true);
         let mut collecting = self.collecting.write().await;
-        *collecting = false;
+        if *collecting {
+            *collecting = false;
+            self.record_marker(MarkerKind::CollectionStopped);
+        }
+    }
+
+    /// Snapshot of the counters for the current run
+    pub fn stats(&self) -> CollectionStats {
+        self.stats.snapshot()
+    }
+
+    /// Requests, bytes and error responses finished so far this run
+    pub fn live_counters(&self) -> LiveCounters {
+        self.stats.live()
+    }
+
+    /// Requests that have started but not yet finished, failed or stalled
+    pub async fn get_pending_requests(&self) -> Vec<TrackedRequest> {
+        self.requests.read().await.values().cloned().collect()
+    }
+
+    /// Whether collection is running, for which session and how
+    pub async fn status(&self) -> CollectorStatus {
+        CollectorStatus {
+            running: *self.collecting.read().await,
+            paused: *self.paused.read().await,
+            session_id: Some(self.session_id.clone()),
+            started_at: *self.started_at.read().await,
+            poll_interval_ms: Some(self.poll_interval.read().await.as_millis() as u64),
+            tracked_request_count: self.get_pending_requests().await.len() as u32,
+        }
+    }
+
+    /// Pause collection while keeping CDP listeners attached
+    pub async fn pause(&self) {
+        let mut paused = self.paused.write().await;
+        if !*paused {
+            *paused = true;
+            self.record_marker(MarkerKind::Paused);
+        }
+    }
+
+    /// Resume a paused collection
+    pub async fn resume(&self) {
+        let mut paused = self.paused.write().await;
+        if *paused {
+            *paused = false;
+            self.record_marker(MarkerKind::Resumed);
+        }
+    }
+
+    /// Record a collection state transition on the session timeline
+    fn record_marker(&self, kind: MarkerKind) {
+        let marker = SessionMarker::new(&self.session_id, kind);
+        let _ = self.database.store_marker(&marker);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stalled() -> MetricsEvent {
+        MetricsEvent::NetworkStalled {
+            request_id: "1".into(),
+            url: "https://example.com/".into(),
+            method: "GET".into(),
+            status: None,
+            elapsed_ms: 30_000.0,
+        }
+    }
+
+    fn collector() -> MetricsCollector<tauri::Wry> {
+        MetricsCollector::new(
+            Arc::new(CdpClient::new()),
+            Arc::new(Database::in_memory().unwrap()),
+            "session-1".into(),
+            "conn".into(),
+            None,
+        )
+    }
+
+    fn tracked_request(request_id: &str, timestamp: f64) -> TrackedRequest {
+        TrackedRequest {
+            request_id: request_id.into(),
+            url: format!("https://example.com/{}", request_id),
+            method: "GET".into(),
+            request_timestamp: timestamp,
+            wall_time: 1_760_000_000.0,
+            response_timestamp: None,
+            status: None,
+            data_length: 0,
+            encoded_data_length: 0,
+            last_chunk_timestamp: None,
+            initiator_type: "script".into(),
+            initiator_url: None,
+            initiator_line: None,
+            initiator_function: None,
+            priority: "High".into(),
+            resource_type: None,
+            response_headers: None,
+            mime_type: None,
+            timing: None,
+            tracked_at: Instant::now(),
+        }
+    }
+
+    fn seqs(page: &EventPage) -> Vec<u64> {
+        page.events.iter().map(|e| e.seq).collect()
+    }
+
+    #[test]
+    fn event_log_numbering_continues_across_collector_restart() {
+        let log = Arc::new(RwLock::new(EventLog::default()));
+        let first = MetricsCollector::<tauri::Wry>::new(
+            Arc::new(CdpClient::new()),
+            Arc::new(Database::in_memory().unwrap()),
+            "session-1".into(),
+            "conn".into(),
+            None,
+        )
+        .with_event_log(log.clone());
+        for _ in 0..3 {
+            first.event_log.try_write().unwrap().push(stalled());
+        }
+        drop(first);
+
+        // Polling still works while nothing is collecting
+        let page = log.try_read().unwrap().since(0, 10);
+        assert_eq!(seqs(&page), vec![1, 2, 3]);
+        assert!(!page.truncated);
+
+        let second = MetricsCollector::<tauri::Wry>::new(
+            Arc::new(CdpClient::new()),
+            Arc::new(Database::in_memory().unwrap()),
+            "session-2".into(),
+            "conn".into(),
+            None,
+        )
+        .with_event_log(log.clone());
+        second.event_log.try_write().unwrap().push(stalled());
+
+        let page = log.try_read().unwrap().since(3, 10);
+        assert_eq!(seqs(&page), vec![4]);
+        assert_eq!(page.max_seq, 4);
+        assert!(!page.truncated);
+    }
+
+    #[test]
+    fn event_log_flags_gaps_and_foreign_positions() {
+        let mut log = EventLog::default();
+        log.push(stalled());
+        log.skip(2);
+        log.push(stalled());
+
+        let page = log.since(1, 10);
+        assert_eq!(seqs(&page), vec![4]);
+        assert!(page.truncated);
+
+        assert!(!log.since(4, 10).truncated);
+
+        // A position from a log that no longer exists
+        let page = log.since(50, 10);
+        assert!(page.events.is_empty());
+        assert_eq!(page.max_seq, 4);
+        assert!(page.truncated);
+    }
+
+    #[tokio::test]
+    async fn snapshot_task_emits_on_interval_until_stopped() {
+        let mut collector = collector();
+        collector.snapshot_interval = Duration::from_millis(20);
+        {
+            let mut requests = collector.requests.write().await;
+            requests.insert("b".into(), tracked_request("b", 2.0));
+            requests.insert("a".into(), tracked_request("a", 1.0));
+        }
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        collector.spawn_snapshot_loop(Some(move |snapshot: &MetricsSnapshot| {
+            let _ = tx.send((Instant::now(), snapshot.clone()));
+        }));
+
+        collector
+            .event_tx
+            .send(MetricsEvent::Performance(PerformanceMetrics {
+                timestamp: 1,
+                dom_nodes: Some(42.0),
+                ..Default::default()
+            }))
+            .unwrap();
+        collector
+            .event_tx
+            .send(MetricsEvent::NetworkResponse {
+                request_id: "a".into(),
+                status: 200,
+                status_text: "OK".into(),
+                timestamp: 1.5,
+                duration_ms: Some(500.0),
+            })
+            .unwrap();
+
+        async fn next<T>(rx: &mut tokio::sync::mpsc::UnboundedReceiver<T>) -> T {
+            tokio::time::timeout(Duration::from_secs(2), rx.recv())
+                .await
+                .unwrap()
+                .unwrap()
+        }
+        let (mut at, mut snapshot) = next(&mut rx).await;
+        while snapshot.network_responses.is_empty() {
+            (at, snapshot) = next(&mut rx).await;
+        }
+        assert_eq!(snapshot.performance.unwrap().dom_nodes, Some(42.0));
+        let ids: Vec<_> = snapshot
+            .network_requests
+            .iter()
+            .map(|r| &r.request_id)
+            .collect();
+        assert_eq!(ids, ["a", "b"]);
+        assert_eq!(snapshot.network_responses[0].status, 200);
+
+        // Later snapshots follow the interval rather than arriving in a burst
+        let (later, _) = next(&mut rx).await;
+        assert!(later - at >= Duration::from_millis(10));
+
+        // Stopping ends the task, which drops the emitter and closes the channel
+        collector.stop().await;
+        let drained = tokio::time::timeout(Duration::from_secs(2), async {
+            while rx.recv().await.is_some() {}
+        })
+        .await;
+        assert!(drained.is_ok());
+    }
+
+    #[tokio::test]
+    async fn snapshot_task_without_interval_never_emits() {
+        let mut collector = collector();
+        collector.snapshot_interval = Duration::ZERO;
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<MetricsSnapshot>();
+        collector.spawn_snapshot_loop(Some(move |snapshot: &MetricsSnapshot| {
+            let _ = tx.send(snapshot.clone());
+        }));
+
+        collector
+            .event_tx
+            .send(MetricsEvent::Performance(PerformanceMetrics::default()))
+            .unwrap();
+        // The state is still kept current for pull-based `snapshot`
+        let applied = tokio::time::timeout(Duration::from_secs(2), async {
+            while collector.snapshot().await.performance.is_none() {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await;
+        assert!(applied.is_ok());
+
+        collector.stop().await;
+        let received = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await;
+        assert!(matches!(received, Ok(None)));
+    }
+
+    #[tokio::test]
+    async fn sweep_times_out_from_the_last_data_chunk() {
+        let requests = Arc::new(RwLock::new(HashMap::new()));
+        let sent = Instant::now() - Duration::from_secs(10);
+        {
+            let mut reqs = requests.write().await;
+            let mut silent = tracked_request("silent", 100.0);
+            silent.tracked_at = sent;
+            reqs.insert("silent".into(), silent);
+
+            // Still streaming two seconds ago
+            let mut streaming = tracked_request("streaming", 100.0);
+            streaming.tracked_at = sent;
+            streaming.last_chunk_timestamp = Some(108.0);
+            reqs.insert("streaming".into(), streaming);
+        }
+        let (event_tx, mut events) = broadcast::channel(10);
+        let database = Arc::new(Database::in_memory().unwrap());
+        let session = crate::storage::Session::new("device-1".into(), None, None, None, None);
+        database.create_session(&session).unwrap();
+
+        MetricsCollector::<tauri::Wry>::sweep_stalled_requests(
+            &requests,
+            Duration::from_secs(5),
+            &event_tx,
+            &database,
+            &session.id,
+        )
+        .await;
+
+        // Stored as failed, with a reason telling it apart from other failures
+        let stored = database
+            .get_network_requests(&session.id, &Default::default(), None)
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+        assert!(stored[0].failed);
+        let reason = stored[0].error_text.as_deref().unwrap();
+        assert!(reason.starts_with("stalled: no data for 10"), "{}", reason);
+
+        let remaining: Vec<_> = requests.read().await.keys().cloned().collect();
+        assert_eq!(remaining, ["streaming"]);
+        assert!(matches!(
+            events.try_recv(),
+            Ok(MetricsEvent::NetworkStalled { request_id, .. }) if request_id == "silent"
+        ));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn event_logger_stops_recording_on_stop() {
+        let collector = collector();
+        collector.spawn_event_logger();
+
+        collector.event_tx.send(stalled()).unwrap();
+        let logged = tokio::time::timeout(Duration::from_secs(2), async {
+            while collector.event_log.read().await.since(0, 10).max_seq == 0 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await;
+        assert!(logged.is_ok());
+
+        collector.stop().await;
+        // Give a still-running logger the chance to pick this up
+        let _ = collector.event_tx.send(stalled());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(collector.event_log.read().await.since(0, 10).max_seq, 1);
+    }
+
+    #[tokio::test]
+    async fn console_trim_follows_session_inserts_not_row_ids() {
+        let database = Arc::new(Database::in_memory().unwrap());
+        let other = crate::storage::Session::new("device-2".into(), None, None, None, None);
+        let session = crate::storage::Session::new("device-1".into(), None, None, None, None);
+        database.create_session(&other).unwrap();
+        database.create_session(&session).unwrap();
+
+        let console_message = |timestamp: f64| CdpEvent::ConsoleMessage {
+            level: crate::cdp::types::ConsoleLevel::Info,
+            text: "tick".into(),
+            timestamp,
+            url: None,
+            line: None,
+        };
+        let requests = Arc::new(RwLock::new(HashMap::new()));
+        let (event_tx, _events) = broadcast::channel(10);
+        let thresholds = Arc::new(RwLock::new(AlertThresholds::default()));
+
+        // Another session's row shifts the shared autoincrement ids
+        let stats = CollectionCounters::default();
+        MetricsCollector::<tauri::Wry>::process_cdp_event(
+            console_message(0.0),
+            &requests,
+            &event_tx,
+            &database,
+            &other.id,
+            &None,
+            &thresholds,
+            &stats,
+        )
+        .await;
+
+        let stats = CollectionCounters::default();
+        let inserts = MAX_CONSOLE_LOGS + MAX_CONSOLE_LOGS / TRIM_FRACTION;
+        for i in 0..inserts {
+            MetricsCollector::<tauri::Wry>::process_cdp_event(
+                console_message(f64::from(i)),
+                &requests,
+                &event_tx,
+                &database,
+                &session.id,
+                &None,
+                &thresholds,
+                &stats,
+            )
+            .await;
+        }
+
+        let kept = database.get_console_logs(&session.id, None).unwrap();
+        assert_eq!(kept.len(), MAX_CONSOLE_LOGS as usize);
+        assert_eq!(kept[0].timestamp, i64::from(inserts - MAX_CONSOLE_LOGS));
+    }
+
+    #[tokio::test]
+    async fn only_network_events_count_as_processed() {
+        let database = Arc::new(Database::in_memory().unwrap());
+        let session = crate::storage::Session::new("device-1".into(), None, None, None, None);
+        database.create_session(&session).unwrap();
+        let requests = Arc::new(RwLock::new(HashMap::new()));
+        requests
+            .write()
+            .await
+            .insert("1".into(), tracked_request("1", 100.0));
+        let (event_tx, _events) = broadcast::channel(10);
+        let thresholds = Arc::new(RwLock::new(AlertThresholds::default()));
+        let stats = CollectionCounters::default();
+
+        for event in [
+            CdpEvent::ConsoleMessage {
+                level: crate::cdp::types::ConsoleLevel::Info,
+                text: "tick".into(),
+                timestamp: 0.0,
+                url: None,
+                line: None,
+            },
+            CdpEvent::NetworkDataReceived {
+                request_id: "1".into(),
+                data_length: 10,
+                encoded_data_length: 10,
+                timestamp: 100.5,
+            },
+            CdpEvent::SecurityState {
+                state: "secure".into(),
+                summary: None,
+            },
+            CdpEvent::NetworkFinished {
+                request_id: "1".into(),
+                encoded_data_length: 10.0,
+                timestamp: 101.0,
+            },
+        ] {
+            MetricsCollector::<tauri::Wry>::process_cdp_event(
+                event,
+                &requests,
+                &event_tx,
+                &database,
+                &session.id,
+                &None,
+                &thresholds,
+                &stats,
+            )
+            .await;
+        }
+
+        assert_eq!(stats.snapshot().network_events_processed, 2);
     }
 }
@@ -1,7 +1,13 @@
 mod client;
+mod coverage;
+mod frames;
 mod metrics;
 mod types;
+mod vitals;
 
-pub use client::CdpClient;
-pub use metrics::MetricsCollector;
+pub use client::{CdpClient, CdpError, CdpEvent, ConnectOptions};
+pub use metrics::{
+    save_screenshot, EventLog, EventPage, MetricsCollector, DEFAULT_EMIT_INTERVAL_MS,
+    DEFAULT_NETWORK_BATCH_MS, DEFAULT_SNAPSHOT_INTERVAL_MS, DEFAULT_STALL_TIMEOUT_MS,
+};
 pub use types::*;
@@ -1,5 +1,8 @@
+use chromiumoxide::cdp::browser_protocol::dom;
+use chromiumoxide::cdp::browser_protocol::page::{GetLayoutMetricsReturns, Viewport};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashMap;
 
 /// CDP target information from /json/list endpoint
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -15,8 +18,106 @@ pub struct CdpTarget {
     pub favicon_url: Option<String>,
 }
 
-/// Performance metrics from CDP
+impl CdpTarget {
+    /// DevTools frontend URL whose `ws=` endpoint points at the forwarded
+    /// port on `host`, so the inspector connects through the ADB forward
+    pub fn devtools_url_for_port(&self, host: &str, local_port: u16) -> String {
+        let local_host = format!("{}:{}", host, local_port);
+        let page_endpoint = format!("{}/devtools/page/{}", local_host, self.id);
+
+        let frontend = match self.devtools_frontend_url.as_deref() {
+            Some(url) if !url.is_empty() => url,
+            _ => {
+                return format!(
+                    "http://{}/devtools/inspector.html?ws={}",
+                    local_host, page_endpoint
+                )
+            }
+        };
+
+        // Relative frontend paths are served by the forwarded endpoint itself
+        let frontend = if frontend.starts_with('/') {
+            format!("http://{}{}", local_host, frontend)
+        } else {
+            frontend.to_string()
+        };
+
+        let Some((base, query)) = frontend.split_once('?') else {
+            return format!("{}?ws={}", frontend, page_endpoint);
+        };
+
+        let mut found_ws = false;
+        let params: Vec<String> = query
+            .split('&')
+            .map(|param| match param.split_once('=') {
+                Some((key @ ("ws" | "wss"), value)) => {
+                    found_ws = true;
+                    // Swap the host:port prefix, keep the /devtools/... path
+                    let path = value.find('/').map(|i| &value[i..]).unwrap_or_default();
+                    format!("{}={}{}", key, local_host, path)
+                }
+                _ => param.to_string(),
+            })
+            .collect();
+
+        let mut query = params.join("&");
+        if !found_ws {
+            query = format!("{}&ws={}", query, page_endpoint);
+        }
+        format!("{}?{}", base, query)
+    }
+}
+
+/// Browser endpoint information from /json/version endpoint
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BrowserVersionInfo {
+    #[serde(rename = "Browser")]
+    pub browser: String,
+    #[serde(rename = "Protocol-Version")]
+    pub protocol_version: Option<String>,
+    #[serde(rename = "User-Agent")]
+    pub user_agent: Option<String>,
+    #[serde(rename = "V8-Version")]
+    pub v8_version: Option<String>,
+    #[serde(rename = "webSocketDebuggerUrl")]
+    pub web_socket_debugger_url: Option<String>,
+}
+
+/// URL and title of the document currently loaded in the page
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PageLocation {
+    pub url: String,
+    pub title: String,
+}
+
+/// Browser build behind a target, from `Browser.getVersion` or, failing
+/// that, `/json/version`. WebViews report their Chromium version as
+/// `Chrome/<version>` in `product`.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WebViewVersion {
+    pub product: String,
+    /// Chromium revision; not reported by `/json/version`
+    pub revision: Option<String>,
+    /// V8 version
+    pub js_version: Option<String>,
+    pub user_agent: Option<String>,
+    pub protocol_version: Option<String>,
+}
+
+impl From<BrowserVersionInfo> for WebViewVersion {
+    fn from(info: BrowserVersionInfo) -> Self {
+        Self {
+            product: info.browser,
+            revision: None,
+            js_version: info.v8_version,
+            user_agent: info.user_agent,
+            protocol_version: info.protocol_version,
+        }
+    }
+}
+
+/// Performance metrics from CDP
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
 pub struct PerformanceMetrics {
     pub timestamp: i64,
     pub js_heap_used_size: Option<f64>,
@@ -25,6 +126,53 @@ pub struct PerformanceMetrics {
     pub layout_count: Option<f64>,
     pub script_duration: Option<f64>,
     pub task_duration: Option<f64>,
+    /// Only reported by some WebView builds
+    pub js_event_listeners: Option<f64>,
+    pub documents: Option<f64>,
+    pub frames: Option<f64>,
+    pub recalc_style_count: Option<f64>,
+    /// Metrics without a typed field, when requested with `include_all`
+    #[serde(default)]
+    pub extras: HashMap<String, f64>,
+}
+
+/// DOM counters from the Memory domain, plus process RSS when available.
+/// Steadily growing `nodes` or `js_event_listeners` usually means a leak.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MemoryMetrics {
+    pub timestamp: i64,
+    pub documents: i64,
+    pub nodes: i64,
+    pub js_event_listeners: i64,
+    /// Resident set size from /proc/[pid]/status (VmRSS), in KB
+    pub rss_kb: Option<u64>,
+}
+
+/// Classic load milestones for one navigation, in ms from navigation start
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct PageLoadTiming {
+    /// Milliseconds since epoch when the load event was seen
+    pub timestamp: i64,
+    pub url: Option<String>,
+    /// First response byte
+    pub ttfb_ms: Option<f64>,
+    pub dom_content_loaded_ms: Option<f64>,
+    pub load_ms: Option<f64>,
+}
+
+/// Core Web Vitals read back from the page's PerformanceObservers.
+/// Values reset when the page navigates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct WebVitals {
+    pub timestamp: i64,
+    /// Largest Contentful Paint, ms since navigation start
+    pub lcp_ms: Option<f64>,
+    /// Cumulative Layout Shift, excluding shifts right after input
+    pub cls: f64,
+    /// First Input Delay
+    pub fid_ms: Option<f64>,
+    /// Slowest interaction seen (upper bound on Interaction to Next Paint)
+    pub inp_ms: Option<f64>,
 }
 
 /// Network request info
@@ -49,6 +197,188 @@ pub struct NetworkResponseInfo {
     pub encoded_data_length: Option<f64>,
 }
 
+/// One JPEG frame from Page.screencastFrame
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ScreencastFrame {
+    /// Base64-encoded JPEG
+    pub data: String,
+    pub device_width: f64,
+    pub device_height: f64,
+    /// Capture time in seconds since epoch, when reported
+    pub timestamp: Option<f64>,
+}
+
+/// A screenshot written to disk
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Screenshot {
+    pub timestamp: i64,
+    /// Absolute path of the PNG file
+    pub path: String,
+}
+
+/// Frame timing derived from a Tracing capture
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FrameStats {
+    /// Capture end time in ms since epoch
+    pub timestamp: i64,
+    pub duration_ms: u64,
+    pub frame_count: u32,
+    /// None when fewer than two frames were drawn
+    pub avg_fps: Option<f64>,
+    /// Frames longer than 16.6ms (missed a 60 Hz vsync)
+    pub long_frames: u32,
+    /// Frames longer than 50ms
+    pub jank_frames: u32,
+    pub max_frame_ms: Option<f64>,
+    /// The trace buffer wrapped and some events were lost
+    pub data_loss: bool,
+}
+
+/// Progress of a heap snapshot, from HeapProfiler.reportHeapSnapshotProgress
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HeapSnapshotProgress {
+    pub done: i64,
+    pub total: i64,
+    pub finished: bool,
+}
+
+/// Executed vs. shipped JS for one script during a coverage run
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ScriptCoverageSummary {
+    pub script_id: String,
+    /// None for eval and other anonymous scripts
+    pub url: Option<String>,
+    pub total_bytes: i64,
+    pub used_bytes: i64,
+    pub unused_bytes: i64,
+}
+
+/// JS coverage from `start_js_coverage` to `stop_js_coverage`
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JsCoverage {
+    pub timestamp: i64,
+    pub total_bytes: i64,
+    pub used_bytes: i64,
+    pub unused_bytes: i64,
+    /// Largest unused first
+    pub scripts: Vec<ScriptCoverageSummary>,
+}
+
+/// Response body from `Network.getResponseBody`, as sent by the browser:
+/// text as-is, binary content base64-encoded
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ResponseBodyContent {
+    pub body: String,
+    pub base64_encoded: bool,
+}
+
+impl ResponseBodyContent {
+    /// Size of the body in bytes once base64 is decoded
+    pub fn size_bytes(&self) -> usize {
+        if self.base64_encoded {
+            self.body.trim_end_matches('=').len() * 3 / 4
+        } else {
+            self.body.len()
+        }
+    }
+}
+
+/// Opt-in response body capture while collecting
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BodyCaptureRule {
+    /// Regex matched against the request URL
+    pub url_pattern: String,
+    /// Bodies larger than this (decoded) are not stored
+    pub max_bytes: u32,
+}
+
+/// Severity of a console message, normalized across `console.*` calls and
+/// browser log entries
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsoleLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl ConsoleLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConsoleLevel::Debug => "debug",
+            ConsoleLevel::Info => "info",
+            ConsoleLevel::Warning => "warning",
+            ConsoleLevel::Error => "error",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "debug" => ConsoleLevel::Debug,
+            "warning" => ConsoleLevel::Warning,
+            "error" => ConsoleLevel::Error,
+            _ => ConsoleLevel::Info,
+        }
+    }
+}
+
+/// Per-phase request timings in ms, for waterfall segments. A phase is
+/// None when it didn't happen (e.g. DNS and connect on a reused connection).
+/// Requests served from cache carry no timing at all.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Type)]
+pub struct RequestTiming {
+    pub dns_ms: Option<f64>,
+    /// TCP connect, excluding the TLS handshake
+    pub connect_ms: Option<f64>,
+    pub ssl_ms: Option<f64>,
+    pub send_ms: Option<f64>,
+    /// Waiting for the first response byte after sending (TTFB)
+    pub wait_ms: Option<f64>,
+    /// Headers received to loading finished; set once the request completes
+    pub download_ms: Option<f64>,
+}
+
+/// Runtime counters for the current collection run
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct CollectionStats {
+    /// Performance samples written to the database
+    pub samples_stored: u64,
+    /// CDP network events handled
+    pub network_events_processed: u64,
+    /// CDP events skipped because the collector fell behind
+    pub events_dropped: u64,
+    /// Failed `Performance.getMetrics` polls
+    pub poll_errors: u64,
+    /// Failed database writes
+    pub storage_errors: u64,
+}
+
+/// Running network totals for the current collection run, kept in memory
+/// so header stats don't have to query the database
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct LiveCounters {
+    /// Requests that finished loading
+    pub request_count: u64,
+    /// Encoded (wire) bytes of finished requests
+    pub total_bytes: u64,
+    /// Finished requests with a 4xx or 5xx status
+    pub error_count: u64,
+}
+
+/// What the metrics collector is doing right now
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct CollectorStatus {
+    pub running: bool,
+    pub paused: bool,
+    pub session_id: Option<String>,
+    /// Milliseconds since epoch when collection started
+    pub started_at: Option<i64>,
+    pub poll_interval_ms: Option<u64>,
+    /// Requests seen but not yet finished
+    pub tracked_request_count: u32,
+}
+
 /// CDP connection state
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
 pub enum ConnectionState {
@@ -66,3 +396,183 @@ pub struct MetricsSnapshot {
     pub network_requests: Vec<NetworkRequestInfo>,
     pub network_responses: Vec<NetworkResponseInfo>,
 }
+
+/// Network condition emulation (Network.emulateNetworkConditions)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+pub struct NetworkThrottle {
+    pub offline: bool,
+    /// Additional round-trip latency in milliseconds
+    pub latency_ms: f64,
+    /// Download throughput in bytes/sec (-1 disables the limit)
+    pub download_throughput: f64,
+    /// Upload throughput in bytes/sec (-1 disables the limit)
+    pub upload_throughput: f64,
+}
+
+impl NetworkThrottle {
+    /// Preset names accepted by `preset`, matching Chrome DevTools
+    pub const PRESETS: [&'static str; 4] = ["No throttling", "Fast 3G", "Slow 3G", "Offline"];
+
+    /// Normal network conditions
+    pub fn none() -> Self {
+        Self {
+            offline: false,
+            latency_ms: 0.0,
+            download_throughput: -1.0,
+            upload_throughput: -1.0,
+        }
+    }
+
+    /// Look up a DevTools throttling preset by name (case-insensitive)
+    pub fn preset(name: &str) -> Option<Self> {
+        let throttle = match name.to_lowercase().as_str() {
+            "no throttling" => Self::none(),
+            "fast 3g" => Self {
+                offline: false,
+                latency_ms: 562.5,
+                download_throughput: 180_000.0,
+                upload_throughput: 84_375.0,
+            },
+            "slow 3g" => Self {
+                offline: false,
+                latency_ms: 2000.0,
+                download_throughput: 50_000.0,
+                upload_throughput: 50_000.0,
+            },
+            "offline" => Self {
+                offline: true,
+                latency_ms: 0.0,
+                download_throughput: 0.0,
+                upload_throughput: 0.0,
+            },
+            _ => return None,
+        };
+        Some(throttle)
+    }
+}
+
+/// Screen emulation (Emulation.setDeviceMetricsOverride)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+pub struct DeviceMetrics {
+    pub width: u32,
+    pub height: u32,
+    pub device_scale_factor: f64,
+    pub mobile: bool,
+}
+
+/// Emulation settings applied together as a test preset.
+/// Unset fields leave the corresponding page setting untouched.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct PresetConfig {
+    pub network_throttle: Option<NetworkThrottle>,
+    /// CPU slowdown factor (1 = no throttling)
+    pub cpu_throttle_rate: Option<f64>,
+    pub device_metrics: Option<DeviceMetrics>,
+    pub user_agent: Option<String>,
+    pub cache_disabled: Option<bool>,
+}
+
+/// Rectangle in CSS pixels
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Type)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Width/height pair in CSS pixels
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Type)]
+pub struct Size {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Page layout metrics from Page.getLayoutMetrics
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct LayoutMetrics {
+    /// Layout viewport position and size within the page
+    pub layout_viewport: Rect,
+    /// Visual viewport position and size within the page
+    pub visual_viewport: Rect,
+    /// Pinch-zoom scale of the visual viewport
+    pub visual_viewport_scale: f64,
+    /// Size of the scrollable content
+    pub content_size: Size,
+}
+
+impl Rect {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn size(&self) -> Size {
+        Size {
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Convert to a CDP viewport (e.g. a screenshot clip) at the given scale
+    pub fn to_viewport(self, scale: f64) -> Viewport {
+        Viewport {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+            scale,
+        }
+    }
+}
+
+impl Size {
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+}
+
+impl From<dom::Rect> for Rect {
+    fn from(rect: dom::Rect) -> Self {
+        Self::new(rect.x, rect.y, rect.width, rect.height)
+    }
+}
+
+impl From<Rect> for dom::Rect {
+    fn from(rect: Rect) -> Self {
+        dom::Rect::new(rect.x, rect.y, rect.width, rect.height)
+    }
+}
+
+impl From<Viewport> for Rect {
+    fn from(viewport: Viewport) -> Self {
+        Self::new(viewport.x, viewport.y, viewport.width, viewport.height)
+    }
+}
+
+impl From<GetLayoutMetricsReturns> for LayoutMetrics {
+    fn from(metrics: GetLayoutMetricsReturns) -> Self {
+        let layout = metrics.css_layout_viewport;
+        let visual = metrics.css_visual_viewport;
+        Self {
+            layout_viewport: Rect::new(
+                layout.page_x as f64,
+                layout.page_y as f64,
+                layout.client_width as f64,
+                layout.client_height as f64,
+            ),
+            visual_viewport: Rect::new(
+                visual.page_x,
+                visual.page_y,
+                visual.client_width,
+                visual.client_height,
+            ),
+            visual_viewport_scale: visual.scale,
+            content_size: Rect::from(metrics.css_content_size).size(),
+        }
+    }
+}
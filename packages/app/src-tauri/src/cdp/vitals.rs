@@ -0,0 +1,92 @@
+use serde::Deserialize;
+
+use super::types::{PageLoadTiming, WebVitals};
+
+/// Installs PerformanceObserver instances that accumulate Web Vitals on
+/// `window.__awpaVitals`. Idempotent, so it can be re-run after every
+/// navigation; `buffered: true` picks up entries from before injection.
+pub const WEB_VITALS_OBSERVER_SCRIPT: &str = r#"(() => {
+  if (window.__awpaVitals) return;
+  const vitals = { lcp: null, cls: 0, fid: null, inp: null };
+  window.__awpaVitals = vitals;
+  const observe = (type, callback, options) => {
+    try {
+      new PerformanceObserver((list) => list.getEntries().forEach(callback))
+        .observe(Object.assign({ type, buffered: true }, options));
+    } catch (e) {}
+  };
+  observe('largest-contentful-paint', (entry) => {
+    vitals.lcp = entry.renderTime || entry.loadTime || entry.startTime;
+  });
+  observe('layout-shift', (entry) => {
+    if (!entry.hadRecentInput) vitals.cls += entry.value;
+  });
+  observe('first-input', (entry) => {
+    if (vitals.fid === null) vitals.fid = entry.processingStart - entry.startTime;
+  });
+  observe('event', (entry) => {
+    if (entry.interactionId && (vitals.inp === null || entry.duration > vitals.inp)) {
+      vitals.inp = entry.duration;
+    }
+  }, { durationThreshold: 16 });
+})()"#;
+
+/// Reads the values accumulated by `WEB_VITALS_OBSERVER_SCRIPT`
+pub const WEB_VITALS_READ_SCRIPT: &str = "window.__awpaVitals || null";
+
+/// Shape of `window.__awpaVitals`
+#[derive(Debug, Deserialize)]
+pub struct RawWebVitals {
+    lcp: Option<f64>,
+    cls: f64,
+    fid: Option<f64>,
+    inp: Option<f64>,
+}
+
+impl RawWebVitals {
+    pub fn into_web_vitals(self, timestamp: i64) -> WebVitals {
+        WebVitals {
+            timestamp,
+            lcp_ms: self.lcp,
+            cls: self.cls,
+            fid_ms: self.fid,
+            inp_ms: self.inp,
+        }
+    }
+}
+
+/// Reads TTFB, DOMContentLoaded and load from the Navigation Timing API,
+/// falling back to the deprecated `performance.timing` on old WebViews
+pub const NAVIGATION_TIMING_SCRIPT: &str = r#"(() => {
+  const since = (value, start) => (value > 0 ? value - start : null);
+  const nav = performance.getEntriesByType && performance.getEntriesByType('navigation')[0];
+  const t = nav || performance.timing;
+  const start = nav ? 0 : t.navigationStart;
+  return {
+    url: location.href,
+    ttfb: since(t.responseStart, start),
+    dcl: since(t.domContentLoadedEventStart, start),
+    load: since(t.loadEventStart, start),
+  };
+})()"#;
+
+/// Shape returned by `NAVIGATION_TIMING_SCRIPT`
+#[derive(Debug, Deserialize)]
+pub struct RawNavigationTiming {
+    url: Option<String>,
+    ttfb: Option<f64>,
+    dcl: Option<f64>,
+    load: Option<f64>,
+}
+
+impl RawNavigationTiming {
+    pub fn into_page_load_timing(self, timestamp: i64) -> PageLoadTiming {
+        PageLoadTiming {
+            timestamp,
+            url: self.url,
+            ttfb_ms: self.ttfb,
+            dom_content_loaded_ms: self.dcl,
+            load_ms: self.load,
+        }
+    }
+}
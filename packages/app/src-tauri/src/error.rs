@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use thiserror::Error;
+
+use crate::adb::AdbError;
+use crate::cdp::CdpError;
+use crate::storage::StorageError;
+
+/// Error returned by every procedure. Serialized as
+/// `{ kind, message }` so the UI can branch on `kind` and show `message`.
+#[derive(Debug, Clone, Error, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum ApiError {
+    /// The RSA key prompt on the device hasn't been accepted
+    #[error("{0}")]
+    DeviceUnauthorized(String),
+    /// Retryable once the device comes back
+    #[error("{0}")]
+    DeviceOffline(String),
+    /// No such device, or none connected
+    #[error("{0}")]
+    DeviceNotFound(String),
+    /// Several devices are connected and none was chosen
+    #[error("{0}")]
+    DeviceSelectionRequired(String),
+    #[error("{0}")]
+    NoPermissions(String),
+    #[error("{0}")]
+    NoDebuggableWebViews(String),
+    /// Any other ADB failure
+    #[error("{0}")]
+    Adb(String),
+    /// No CDP connection (or not the requested one); connect first
+    #[error("{0}")]
+    NotConnected(String),
+    /// The connection has no metrics collector running; start one first
+    #[error("{0}")]
+    NotCollecting(String),
+    /// The connection isn't recording into a session; create one first
+    #[error("{0}")]
+    NoActiveSession(String),
+    /// Reaching the DevTools endpoint failed; usually retryable
+    #[error("{0}")]
+    ConnectionFailed(String),
+    /// The browser rejected or failed a CDP command
+    #[error("{0}")]
+    Cdp(String),
+    /// The session already completed or was aborted
+    #[error("{0}")]
+    AlreadyEnded(String),
+    /// A session, preset or other stored item doesn't exist
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Storage(String),
+    #[error("{0}")]
+    InvalidInput(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<AdbError> for ApiError {
+    fn from(e: AdbError) -> Self {
+        let message = e.to_string();
+        match e {
+            AdbError::DeviceUnauthorized => ApiError::DeviceUnauthorized(message),
+            AdbError::DeviceOffline => ApiError::DeviceOffline(message),
+            AdbError::NoDevices | AdbError::DeviceNotFound(_) => ApiError::DeviceNotFound(message),
+            AdbError::MultipleDevices | AdbError::MissingDeviceId => {
+                ApiError::DeviceSelectionRequired(message)
+            }
+            AdbError::DeviceNoPermissions => ApiError::NoPermissions(message),
+            AdbError::NoDebuggableWebViews => ApiError::NoDebuggableWebViews(message),
+            _ => ApiError::Adb(message),
+        }
+    }
+}
+
+impl From<CdpError> for ApiError {
+    fn from(e: CdpError) -> Self {
+        let message = e.to_string();
+        match e {
+            CdpError::NotConnected => ApiError::NotConnected(message),
+            CdpError::ConnectionFailed(_) | CdpError::FetchTargetsFailed(_) => {
+                ApiError::ConnectionFailed(message)
+            }
+            CdpError::BrowserError(_) | CdpError::Io(_) => ApiError::Cdp(message),
+        }
+    }
+}
+
+impl From<StorageError> for ApiError {
+    fn from(e: StorageError) -> Self {
+        let message = e.to_string();
+        match e {
+            StorageError::SessionNotFound(_)
+            | StorageError::PresetNotFound(_)
+            | StorageError::AnnotationNotFound(_) => ApiError::NotFound(message),
+            StorageError::UnsupportedBundleVersion(_) | StorageError::ChecksumMismatch => {
+                ApiError::InvalidInput(message)
+            }
+            _ => ApiError::Storage(message),
+        }
+    }
+}
+
+impl From<std::io::Error> for ApiError {
+    fn from(e: std::io::Error) -> Self {
+        ApiError::Other(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(e: serde_json::Error) -> Self {
+        ApiError::InvalidInput(e.to_string())
+    }
+}
+
+impl From<tauri::Error> for ApiError {
+    fn from(e: tauri::Error) -> Self {
+        ApiError::Other(e.to_string())
+    }
+}
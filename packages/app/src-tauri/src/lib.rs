@@ -1,12 +1,13 @@
 mod adb;
 mod cdp;
+mod error;
 mod procedures;
 mod storage;
 
-use cdp::CdpClient;
+use adb::AdbConfig;
 use procedures::{Api, ApiImpl, ManagedState, MetricsCollectorHolder};
+use std::collections::HashMap;
 use std::sync::Arc;
-use storage::Database;
 use tauri::Manager;
 use tokio::sync::RwLock;
 
@@ -35,16 +36,40 @@ pub fn run() {
                 .path()
                 .app_data_dir()
                 .expect("Failed to get app data dir");
-            let db_path = Database::get_db_path(&app_data_dir);
-            let db = Database::new(db_path).expect("Failed to initialize database");
+            let (db, settings) = procedures::open_app_database(&app_data_dir)
+                .expect("Failed to initialize database");
+            db.set_metric_compression(settings.compress_metrics);
+            if let Some(days) = settings.retention_days {
+                match db.prune_old_sessions(procedures::retention_cutoff(days)) {
+                    Ok(result) if result.sessions_deleted > 0 => tracing::info!(
+                        "Pruned {} sessions older than {} days",
+                        result.sessions_deleted,
+                        days
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Failed to prune old sessions: {}", e),
+                }
+            }
+            // Sessions a crash left active stay so; the UI offers to resume
+            // or abort them via `get_active_session`
+            if let Ok(dangling) = db.get_active_sessions() {
+                for session in dangling {
+                    tracing::warn!("Session {} was left active by a previous run", session.id);
+                }
+            }
+            let adb_config = AdbConfig::new(settings.adb_path.clone(), settings.adb_server.clone());
 
             // Create managed state
             let managed_state = ManagedState {
-                cdp_client: Arc::new(CdpClient::new()),
-                database: Arc::new(db),
-                current_session_id: Arc::new(RwLock::new(None)),
+                connections: RwLock::new(HashMap::new()),
+                default_connection: RwLock::new(None),
+                database: std::sync::RwLock::new(Arc::new(db)),
+                settings: Arc::new(RwLock::new(settings)),
+                device_watch: std::sync::Mutex::new(None),
+                forwards: std::sync::Mutex::new(Default::default()),
             };
             app.manage(managed_state);
+            app.manage(adb_config);
 
             // Create metrics collector holder (runtime-specific)
             app.manage(MetricsCollectorHolder::<tauri::Wry>::new());
@@ -57,6 +82,11 @@ pub fn run() {
             Ok(())
         })
         .invoke_handler(router.into_handler())
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                tauri::async_runtime::block_on(procedures::shutdown(app_handle));
+            }
+        });
 }
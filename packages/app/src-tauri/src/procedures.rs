@@ -1,11 +1,51 @@
-use crate::adb::{self, Device, MemoryInfo, TrimMemoryLevel, WebView};
-use crate::cdp::{CdpClient, CdpTarget, ConnectionState, MetricsCollector, PerformanceMetrics};
-use crate::storage::{Database, MetricType, Session, StoredMetric, StoredNetworkRequest};
+use crate::adb::{
+    self, AdbConfig, AdbServer, AdbVersion, CpuUsage, Device, DeviceProps, DeviceStatus,
+    EnrichedTarget, MemoryInfo, PortForward, TrimMemoryLevel, WebView,
+};
+use crate::cdp::{
+    save_screenshot, CdpClient, CdpEvent, CdpTarget, CollectionStats, CollectorStatus,
+    ConnectOptions, ConnectionState, ConsoleLevel, EventLog, EventPage, FrameStats, JsCoverage,
+    LayoutMetrics, LiveCounters, MetricsCollector, MetricsSnapshot, NetworkThrottle,
+    PerformanceMetrics, PresetConfig, ResponseBodyContent, Screenshot, WebViewVersion, WebVitals,
+};
+use crate::error::ApiError;
+use crate::storage::{
+    Alert, AlertThresholds, Annotation, BatchResult, CompactResult, ConsoleLog, CsvExportKind,
+    Database, DuplicateRequestGroup, HostNetworkSummary, MarkerKind, MetricType, MetricsSummary,
+    NetworkRequestCursor, NetworkRequestFilter, NetworkRequestPage, NetworkSummary, PruneResult,
+    RequestDataChunk, ResponseBody, SecurityEvent, Session, SessionComparison, SessionCounts,
+    SessionMarker, SessionSearchFilter, SessionStatus, Settings, SmoothedSeries, SmoothingOptions,
+    StorageError, StoredMetric, StoredNetworkRequest, TagCount, TestPreset, ThroughputBucket,
+    UrlNormalizer,
+};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tauri::{Manager, Runtime, Window};
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, Runtime, Window};
+use tokio::sync::{broadcast, RwLock};
+
+/// Default row limit for the recent data in `get_session_detail`
+const DEFAULT_DETAIL_LIMIT: u32 = 500;
+
+/// Default JPEG quality for the live screencast preview
+const DEFAULT_SCREENCAST_QUALITY: u8 = 60;
+
+/// Default screencast width cap, enough for a side-panel preview
+const DEFAULT_SCREENCAST_MAX_WIDTH: u32 = 720;
+
+/// Default page size for `poll_events`
+const DEFAULT_POLL_EVENTS_LIMIT: u32 = 500;
+
+/// Session metadata key holding the `WebViewVersion` of the connection
+const WEBVIEW_VERSION_KEY: &str = "webview_version";
+
+/// Session metadata key holding the device's hardware and OS context
+const DEVICE_PROPS_KEY: &str = "device";
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct PortForwardResult {
@@ -22,54 +62,460 @@ pub struct CreateSessionParams {
     pub webview_url: Option<String>,
 }
 
+/// Session with its summary data, for loading a detail view in one call
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionDetail {
+    pub session: Session,
+    pub counts: SessionCounts,
+    pub recent_metrics: Vec<StoredMetric>,
+    pub recent_requests: Vec<StoredNetworkRequest>,
+    pub markers: Vec<SessionMarker>,
+    pub annotations: Vec<Annotation>,
+}
+
+/// One CDP connection and the session recorded over it
+pub struct Connection {
+    pub id: String,
+    pub client: Arc<CdpClient>,
+    /// Session that collection on this connection records into
+    pub session_id: RwLock<Option<String>>,
+    /// Browser build reported on connect
+    pub version: RwLock<Option<WebViewVersion>>,
+    /// Collector events for `poll_events`; outlives each collector so
+    /// sequence numbers keep increasing across restarts
+    pub event_log: Arc<RwLock<EventLog>>,
+    /// Set while `connect_cdp` runs, so a second call for the same id is
+    /// rejected instead of racing it
+    connecting: AtomicBool,
+}
+
+impl Connection {
+    fn new(id: String) -> Self {
+        Self {
+            id,
+            client: Arc::new(CdpClient::new()),
+            session_id: RwLock::new(None),
+            version: RwLock::new(None),
+            event_log: Arc::new(RwLock::new(EventLog::default())),
+            connecting: AtomicBool::new(false),
+        }
+    }
+}
+
 /// Shared application state managed by Tauri
 pub struct ManagedState {
-    pub cdp_client: Arc<CdpClient>,
-    pub database: Arc<Database>,
-    pub current_session_id: Arc<RwLock<Option<String>>>,
+    /// Open CDP connections by id, so several WebViews can be profiled at once
+    pub connections: RwLock<HashMap<String, Arc<Connection>>>,
+    /// Connection used when a procedure gets no id: the last one connected
+    pub default_connection: RwLock<Option<String>>,
+    /// Swapped by `open_database`; use `database()` for the current one
+    pub database: std::sync::RwLock<Arc<Database>>,
+    pub settings: Arc<RwLock<Settings>>,
+    /// Background `devices:changed` watcher, if running
+    pub device_watch: std::sync::Mutex<Option<JoinHandle<()>>>,
+    /// `(device_id, local_port)` forwards this app created, removed on exit
+    pub forwards: std::sync::Mutex<HashSet<(String, u16)>>,
+}
+
+impl ManagedState {
+    /// The database currently open
+    pub fn database(&self) -> Arc<Database> {
+        self.database.read().unwrap().clone()
+    }
+
+    /// The connection named `id`, or the default one when None
+    pub async fn connection(&self, id: Option<&str>) -> Result<Arc<Connection>, ApiError> {
+        let id = match id {
+            Some(id) => id.to_string(),
+            None => self
+                .default_connection
+                .read()
+                .await
+                .clone()
+                .ok_or_else(|| ApiError::NotConnected("Not connected".to_string()))?,
+        };
+        self.connections
+            .read()
+            .await
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| ApiError::NotConnected(format!("Unknown connection: {}", id)))
+    }
+
+    /// Session recording on the default connection, if any
+    async fn default_session_id(&self) -> Option<String> {
+        let connection = self.connection(None).await.ok()?;
+        let session_id = connection.session_id.read().await.clone();
+        session_id
+    }
+
+    /// Detach `session_ids` from whichever connections record into them
+    async fn release_sessions(&self, session_ids: &[String]) {
+        for connection in self.connections.read().await.values() {
+            let mut current = connection.session_id.write().await;
+            if current.as_ref().is_some_and(|id| session_ids.contains(id)) {
+                *current = None;
+            }
+        }
+    }
+
+    /// Connection a new session is created on. An explicit one must exist;
+    /// without an id, the default connection if there is one. A session
+    /// that isn't being activated (an import, another device) only uses an
+    /// explicit one.
+    async fn session_connection(
+        &self,
+        connection_id: Option<&str>,
+        activate: bool,
+    ) -> Result<Option<Arc<Connection>>, ApiError> {
+        match connection_id {
+            Some(id) => Ok(Some(self.connection(Some(id)).await?)),
+            None if activate => Ok(self.connection(None).await.ok()),
+            None => Ok(None),
+        }
+    }
+
+    /// Store a new session and, when `activate`, make it `connection`'s
+    /// current one; otherwise the connection keeps recording where it was
+    async fn add_session(
+        &self,
+        session: &Session,
+        connection: Option<&Connection>,
+        activate: bool,
+    ) -> Result<(), ApiError> {
+        self.database().create_session(session)?;
+        if let Some(connection) = connection.filter(|_| activate) {
+            *connection.session_id.write().await = Some(session.id.clone());
+        }
+        Ok(())
+    }
+
+    /// Make an active session the one `connection_id` records into,
+    /// detaching it from any other connection
+    async fn activate_session(
+        &self,
+        session_id: String,
+        connection_id: Option<&str>,
+    ) -> Result<Session, ApiError> {
+        let session = self
+            .database()
+            .get_session(&session_id)?
+            .ok_or_else(|| ApiError::NotFound(format!("Session not found: {}", session_id)))?;
+        if session.status != SessionStatus::Active {
+            return Err(ApiError::InvalidInput(format!(
+                "Session {} has already ended",
+                session_id
+            )));
+        }
+
+        let connection = self.connection(connection_id).await?;
+        self.release_sessions(std::slice::from_ref(&session_id))
+            .await;
+        *connection.session_id.write().await = Some(session_id);
+
+        Ok(session)
+    }
 }
 
-/// Wrapper for metrics collector that is runtime-generic
+/// Running metrics collectors by connection id (runtime-generic)
 pub struct MetricsCollectorHolder<R: Runtime> {
-    pub collector: RwLock<Option<MetricsCollector<R>>>,
+    pub collectors: RwLock<HashMap<String, MetricsCollector<R>>>,
 }
 
 impl<R: Runtime> MetricsCollectorHolder<R> {
     pub fn new() -> Self {
         Self {
-            collector: RwLock::new(None),
+            collectors: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+/// Open the session database. A `data_dir` saved in the database in the
+/// app data directory points at the one to use instead; the returned
+/// settings report it even though they come from the database opened.
+pub fn open_app_database(app_data_dir: &Path) -> Result<(Database, Settings), StorageError> {
+    let default_db = Database::new(Database::get_db_path(app_data_dir))?;
+    let data_dir = default_db.get_settings().unwrap_or_default().data_dir;
+    let db = match data_dir.as_deref() {
+        Some(dir) => match Database::new(data_dir_db_path(Path::new(dir))) {
+            Ok(db) => db,
+            Err(e) => {
+                tracing::warn!("Failed to open the database in {}: {}", dir, e);
+                default_db
+            }
+        },
+        None => default_db,
+    };
+
+    let mut settings = db.get_settings().unwrap_or_default();
+    settings.data_dir = data_dir;
+    Ok((db, settings))
+}
+
+/// Database file a `data_dir` setting names: the file itself, or the
+/// usual file inside it when it is a directory
+fn data_dir_db_path(data_dir: &Path) -> PathBuf {
+    if data_dir.is_dir() || data_dir.extension().is_none() {
+        Database::get_db_path(data_dir)
+    } else {
+        data_dir.to_path_buf()
+    }
+}
+
+/// Save `data_dir` in the default database, where `open_app_database`
+/// reads it; `current` is used when it is that database
+fn remember_data_dir(
+    current: &Database,
+    app_data_dir: &Path,
+    data_dir: Option<String>,
+) -> Result<(), StorageError> {
+    let default_path = Database::get_db_path(app_data_dir);
+    let save = |db: &Database| {
+        let mut settings = db.get_settings().unwrap_or_default();
+        settings.data_dir = data_dir.clone();
+        db.update_settings(&settings)
+    };
+    if current.path() == Some(default_path.as_path()) {
+        save(current)
+    } else {
+        save(&Database::new(default_path)?)
+    }
+}
+
+/// Start time of the oldest session kept by a `retention_days` policy
+pub fn retention_cutoff(retention_days: u32) -> i64 {
+    chrono::Utc::now().timestamp_millis()
+        - chrono::Duration::days(retention_days as i64).num_milliseconds()
+}
+
+/// Stop collection, end the active sessions, close the CDP connections,
+/// remove the port forwards we created and checkpoint the database so
+/// nothing is lost or left behind when the app exits
+pub async fn shutdown<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(holder) = app.try_state::<MetricsCollectorHolder<R>>() {
+        for (_, collector) in holder.collectors.write().await.drain() {
+            collector.stop().await;
+        }
+    }
+
+    let Some(state) = app.try_state::<ManagedState>() else {
+        return;
+    };
+
+    let connections: Vec<Arc<Connection>> = state
+        .connections
+        .write()
+        .await
+        .drain()
+        .map(|(_, connection)| connection)
+        .collect();
+    for connection in connections {
+        let session_id = connection.session_id.write().await.take();
+        if let Some(id) = session_id {
+            let ended_at = chrono::Utc::now().timestamp_millis();
+            if let Err(e) = state.database().end_session(&id, ended_at) {
+                tracing::warn!("Failed to end session {} on exit: {}", id, e);
+            }
+        }
+
+        let _ = connection.client.disconnect().await;
+    }
+
+    // Only our own forwards; other tools may share the adb server
+    let forwards: Vec<(String, u16)> = state.forwards.lock().unwrap().drain().collect();
+    for (device_id, local_port) in forwards {
+        if let Err(e) = adb::remove_forward(app, &device_id, local_port).await {
+            tracing::warn!(
+                "Failed to remove forward tcp:{} on {}: {}",
+                local_port,
+                device_id,
+                e
+            );
+        }
+    }
+
+    if let Err(e) = state.database().checkpoint() {
+        tracing::warn!("Database checkpoint on exit failed: {}", e);
+    }
+}
+
+/// Host and port of the DevTools HTTP endpoint a WebSocket URL points at
+fn devtools_endpoint(ws_url: &str) -> Option<(String, u16)> {
+    let url = reqwest::Url::parse(ws_url).ok()?;
+    Some((url.host_str()?.to_string(), url.port_or_known_default()?))
+}
+
+/// Apply the preset `name` on `connection` and record it on the session
+async fn apply_default_preset(
+    state: &ManagedState,
+    connection: &Connection,
+    session_id: &str,
+    name: &str,
+) -> Result<(), ApiError> {
+    let preset = state.database().get_preset(name)?;
+    connection
+        .client
+        .apply_preset_config(&preset.config)
+        .await?;
+    state
+        .database()
+        .set_session_preset(session_id, &preset.name)?;
+    Ok(())
+}
+
+/// Set `key` in an existing session's metadata
+fn record_session_metadata<T: Serialize>(
+    database: &Database,
+    session_id: &str,
+    key: &str,
+    value: &T,
+) -> Result<(), ApiError> {
+    let session = database
+        .get_session(session_id)?
+        .ok_or_else(|| ApiError::NotFound(format!("Session not found: {}", session_id)))?;
+    let mut metadata = session.metadata.unwrap_or_default();
+    metadata.insert(key.to_string(), serde_json::to_value(value)?);
+    database.update_session_metadata(session_id, Some(&metadata))?;
+    Ok(())
+}
+
+/// End or abort a session, the default connection's when `session_id` is
+/// None. Collectors recording into it are stopped first so nothing lands
+/// after `ended_at`, and only the call that actually ends it stores the
+/// summary.
+async fn finish_session<R: Runtime>(
+    window: &Window<R>,
+    session_id: Option<String>,
+    status: SessionStatus,
+    abort_reason: Option<&str>,
+) -> Result<(), ApiError> {
+    let state = window.state::<ManagedState>();
+    let id = match session_id {
+        Some(id) => id,
+        None => state
+            .default_session_id()
+            .await
+            .ok_or_else(|| ApiError::NoActiveSession("No active session to end".to_string()))?,
+    };
+
+    let mut attached = Vec::new();
+    for connection in state.connections.read().await.values() {
+        if connection.session_id.read().await.as_ref() == Some(&id) {
+            attached.push(connection.id.clone());
+        }
+    }
+    let holder = window.state::<MetricsCollectorHolder<R>>();
+    for connection_id in attached {
+        let collector = holder.collectors.write().await.remove(&connection_id);
+        if let Some(collector) = collector {
+            collector.stop().await;
         }
     }
+
+    let database = state.database();
+    let ended_at = chrono::Utc::now().timestamp_millis();
+    let finished = match status {
+        SessionStatus::Aborted => database.abort_session(&id, ended_at, abort_reason)?,
+        _ => database.end_session(&id, ended_at)?,
+    };
+
+    // Clear current session if it matches
+    state.release_sessions(std::slice::from_ref(&id)).await;
+
+    if !finished {
+        return Err(ApiError::AlreadyEnded(format!(
+            "Session {} has already ended",
+            id
+        )));
+    }
+
+    let summary = database.session_aggregates(&id)?;
+    if let Err(e) = record_session_metadata(&database, &id, "summary", &summary) {
+        tracing::warn!("Failed to store the summary of session {}: {}", id, e);
+    }
+    Ok(())
 }
 
 #[taurpc::procedures(path = "api", export_to = "../src/bindings.ts")]
 pub trait Api {
     // ============ ADB Commands ============
 
-    async fn get_devices<R: Runtime>(window: Window<R>) -> Result<Vec<Device>, String>;
+    async fn get_devices<R: Runtime>(
+        window: Window<R>,
+        connected_only: Option<bool>,
+    ) -> Result<Vec<Device>, ApiError>;
+
+    async fn get_default_device<R: Runtime>(window: Window<R>) -> Result<Device, ApiError>;
 
     async fn get_webviews<R: Runtime>(
         window: Window<R>,
         device_id: String,
-    ) -> Result<Vec<WebView>, String>;
+    ) -> Result<Vec<WebView>, ApiError>;
 
     async fn start_port_forward<R: Runtime>(
         window: Window<R>,
         device_id: String,
         socket_name: String,
         local_port: u16,
-    ) -> Result<PortForwardResult, String>;
+    ) -> Result<PortForwardResult, ApiError>;
+
+    async fn start_port_forward_auto<R: Runtime>(
+        window: Window<R>,
+        device_id: String,
+        socket_name: String,
+    ) -> Result<PortForwardResult, ApiError>;
+
+    async fn list_active_forwards<R: Runtime>(
+        window: Window<R>,
+        device_id: Option<String>,
+    ) -> Result<Vec<PortForward>, ApiError>;
 
     async fn stop_port_forward<R: Runtime>(
         window: Window<R>,
         device_id: String,
         local_port: u16,
-    ) -> Result<(), String>;
+    ) -> Result<(), ApiError>;
 
     async fn stop_all_port_forwards<R: Runtime>(
         window: Window<R>,
         device_id: String,
-    ) -> Result<(), String>;
+    ) -> Result<(), ApiError>;
+
+    async fn start_device_watch<R: Runtime>(window: Window<R>) -> Result<(), ApiError>;
+
+    async fn stop_device_watch<R: Runtime>(window: Window<R>) -> Result<(), ApiError>;
+
+    async fn connect_wireless_device<R: Runtime>(
+        window: Window<R>,
+        host: String,
+        port: u16,
+    ) -> Result<String, ApiError>;
+
+    async fn pair_wireless_device<R: Runtime>(
+        window: Window<R>,
+        host: String,
+        port: u16,
+        code: String,
+    ) -> Result<(), ApiError>;
+
+    async fn disconnect_wireless_device<R: Runtime>(
+        window: Window<R>,
+        host: String,
+        port: u16,
+    ) -> Result<(), ApiError>;
+
+    async fn set_adb_path<R: Runtime>(
+        window: Window<R>,
+        path: Option<String>,
+    ) -> Result<AdbVersion, ApiError>;
+
+    async fn get_adb_version<R: Runtime>(window: Window<R>) -> Result<AdbVersion, ApiError>;
+
+    async fn set_adb_server<R: Runtime>(
+        window: Window<R>,
+        host: Option<String>,
+        port: Option<u16>,
+    ) -> Result<Vec<Device>, ApiError>;
 
     // ============ Memory Simulation Commands ============
 
@@ -78,466 +524,2801 @@ pub trait Api {
         device_id: String,
         package_name: String,
         level: TrimMemoryLevel,
-    ) -> Result<(), String>;
+    ) -> Result<(), ApiError>;
 
     async fn get_device_meminfo<R: Runtime>(
         window: Window<R>,
         device_id: String,
-    ) -> Result<MemoryInfo, String>;
+    ) -> Result<MemoryInfo, ApiError>;
+
+    async fn get_device_info<R: Runtime>(
+        window: Window<R>,
+        device_id: String,
+    ) -> Result<DeviceProps, ApiError>;
 
     // ============ CDP Commands ============
 
-    async fn get_cdp_targets(port: u16) -> Result<Vec<CdpTarget>, String>;
+    async fn get_cdp_targets<R: Runtime>(
+        window: Window<R>,
+        port: u16,
+    ) -> Result<Vec<CdpTarget>, ApiError>;
+
+    async fn get_webview_version<R: Runtime>(
+        window: Window<R>,
+        port: Option<u16>,
+        connection_id: Option<String>,
+    ) -> Result<WebViewVersion, ApiError>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn connect_cdp<R: Runtime>(
+        window: Window<R>,
+        ws_url: String,
+        create_blank_page: Option<bool>,
+        timeout_ms: Option<u64>,
+        retries: Option<u32>,
+        page_attempts: Option<u32>,
+        page_retry_delay_ms: Option<u64>,
+        connection_id: Option<String>,
+    ) -> Result<String, ApiError>;
+
+    async fn switch_target<R: Runtime>(
+        window: Window<R>,
+        ws_url: String,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
+
+    async fn connect_browser(host: String, port: u16) -> Result<Vec<CdpTarget>, ApiError>;
+
+    async fn get_enriched_targets<R: Runtime>(
+        window: Window<R>,
+        device_id: String,
+        socket_name: String,
+        port: u16,
+    ) -> Result<Vec<EnrichedTarget>, ApiError>;
+
+    async fn get_devtools_url<R: Runtime>(
+        window: Window<R>,
+        port: u16,
+        target_id: String,
+        local_port: u16,
+    ) -> Result<String, ApiError>;
+
+    async fn disconnect_cdp<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
 
-    async fn connect_cdp<R: Runtime>(window: Window<R>, ws_url: String) -> Result<(), String>;
+    async fn set_auto_reconnect<R: Runtime>(
+        window: Window<R>,
+        enabled: bool,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
 
-    async fn disconnect_cdp<R: Runtime>(window: Window<R>) -> Result<(), String>;
+    async fn get_cdp_state<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<ConnectionState, ApiError>;
 
-    async fn get_cdp_state<R: Runtime>(window: Window<R>) -> Result<ConnectionState, String>;
+    async fn check_cdp_health<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<f64, ApiError>;
 
     async fn start_metrics_collection<R: Runtime>(
         window: Window<R>,
         poll_interval_ms: Option<u64>,
-    ) -> Result<(), String>;
+        max_samples: Option<u32>,
+        emit_interval_ms: Option<u64>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
+
+    async fn update_poll_interval<R: Runtime>(
+        window: Window<R>,
+        poll_interval_ms: u64,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
+
+    async fn stop_metrics_collection<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
+
+    async fn pause_metrics_collection<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
+
+    async fn resume_metrics_collection<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
+
+    async fn get_collection_stats<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<CollectionStats, ApiError>;
+
+    async fn get_live_counters<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<LiveCounters, ApiError>;
+
+    async fn get_current_snapshot<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<MetricsSnapshot, ApiError>;
+
+    async fn get_collector_status<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<CollectorStatus, ApiError>;
 
-    async fn stop_metrics_collection<R: Runtime>(window: Window<R>) -> Result<(), String>;
+    async fn poll_events<R: Runtime>(
+        window: Window<R>,
+        since_seq: Option<u64>,
+        limit: Option<u32>,
+        connection_id: Option<String>,
+    ) -> Result<EventPage, ApiError>;
 
     async fn get_performance_metrics<R: Runtime>(
         window: Window<R>,
-    ) -> Result<PerformanceMetrics, String>;
+        include_all: bool,
+        connection_id: Option<String>,
+    ) -> Result<PerformanceMetrics, ApiError>;
+
+    async fn get_raw_performance_metrics<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<Vec<(String, f64)>, ApiError>;
+
+    async fn get_layout_metrics<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<LayoutMetrics, ApiError>;
+
+    async fn start_screencast<R: Runtime>(
+        window: Window<R>,
+        quality: Option<u8>,
+        max_width: Option<u32>,
+        every_nth_frame: Option<u32>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
+
+    async fn stop_screencast<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
+
+    async fn apply_network_throttle<R: Runtime>(
+        window: Window<R>,
+        preset: Option<String>,
+        custom: Option<NetworkThrottle>,
+        connection_id: Option<String>,
+    ) -> Result<NetworkThrottle, ApiError>;
 
     // ============ Session Commands ============
 
     async fn create_session<R: Runtime>(
         window: Window<R>,
         params: CreateSessionParams,
-    ) -> Result<Session, String>;
+        connection_id: Option<String>,
+        activate: Option<bool>,
+    ) -> Result<Session, ApiError>;
 
     async fn end_session<R: Runtime>(
         window: Window<R>,
         session_id: Option<String>,
-    ) -> Result<(), String>;
+    ) -> Result<(), ApiError>;
+
+    async fn abort_session<R: Runtime>(
+        window: Window<R>,
+        session_id: Option<String>,
+        reason: Option<String>,
+    ) -> Result<(), ApiError>;
+
+    async fn get_active_session<R: Runtime>(window: Window<R>)
+        -> Result<Option<Session>, ApiError>;
+
+    /// Deprecated alias of `set_active_session`, kept for existing callers
+    async fn resume_session<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        connection_id: Option<String>,
+    ) -> Result<Session, ApiError>;
+
+    async fn set_active_session<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        connection_id: Option<String>,
+    ) -> Result<Session, ApiError>;
 
     async fn get_session<R: Runtime>(
         window: Window<R>,
         session_id: String,
-    ) -> Result<Option<Session>, String>;
+    ) -> Result<Option<Session>, ApiError>;
+
+    async fn get_session_counts<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<SessionCounts, ApiError>;
+
+    async fn get_session_detail<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        metric_limit: Option<u32>,
+        request_limit: Option<u32>,
+    ) -> Result<SessionDetail, ApiError>;
 
     async fn list_sessions<R: Runtime>(
         window: Window<R>,
         limit: Option<u32>,
-    ) -> Result<Vec<Session>, String>;
+    ) -> Result<Vec<Session>, ApiError>;
 
     async fn delete_session<R: Runtime>(
         window: Window<R>,
         session_id: String,
-    ) -> Result<(), String>;
+    ) -> Result<(), ApiError>;
+
+    async fn delete_sessions<R: Runtime>(
+        window: Window<R>,
+        session_ids: Vec<String>,
+    ) -> Result<BatchResult, ApiError>;
+
+    async fn prune_database<R: Runtime>(
+        window: Window<R>,
+        retention_days: u32,
+    ) -> Result<PruneResult, ApiError>;
 
     async fn update_session_name<R: Runtime>(
         window: Window<R>,
         session_id: String,
         display_name: Option<String>,
-    ) -> Result<(), String>;
+    ) -> Result<(), ApiError>;
+
+    async fn update_session_from_target<R: Runtime>(
+        window: Window<R>,
+        session_id: Option<String>,
+        connection_id: Option<String>,
+    ) -> Result<Session, ApiError>;
 
     async fn update_session_tags<R: Runtime>(
         window: Window<R>,
         session_id: String,
         tags: Option<Vec<String>>,
-    ) -> Result<(), String>;
+    ) -> Result<(), ApiError>;
+
+    async fn add_tag_to_sessions<R: Runtime>(
+        window: Window<R>,
+        session_ids: Vec<String>,
+        tag: String,
+    ) -> Result<BatchResult, ApiError>;
+
+    async fn remove_tag_from_sessions<R: Runtime>(
+        window: Window<R>,
+        session_ids: Vec<String>,
+        tag: String,
+    ) -> Result<BatchResult, ApiError>;
+
+    async fn get_all_tags<R: Runtime>(window: Window<R>) -> Result<Vec<TagCount>, ApiError>;
+
+    async fn update_session_metadata<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        metadata: Option<HashMap<String, serde_json::Value>>,
+    ) -> Result<(), ApiError>;
 
     async fn search_sessions<R: Runtime>(
         window: Window<R>,
-        query: Option<String>,
-        device_id: Option<String>,
-        status: Option<String>,
-        tags: Option<Vec<String>>,
-        limit: Option<u32>,
-    ) -> Result<Vec<Session>, String>;
+        filter: SessionSearchFilter,
+    ) -> Result<Vec<Session>, ApiError>;
+
+    async fn count_sessions<R: Runtime>(
+        window: Window<R>,
+        filter: SessionSearchFilter,
+    ) -> Result<i64, ApiError>;
+
+    async fn export_session_bundle<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<String, ApiError>;
+
+    async fn export_session_bundle_to_file<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        path: String,
+    ) -> Result<(), ApiError>;
+
+    async fn export_har<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<String, ApiError>;
+
+    async fn export_session_csv<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        kind: CsvExportKind,
+    ) -> Result<String, ApiError>;
+
+    async fn export_metrics_ndjson<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        path: String,
+    ) -> Result<i64, ApiError>;
+
+    async fn compare_sessions<R: Runtime>(
+        window: Window<R>,
+        session_a: String,
+        session_b: String,
+    ) -> Result<SessionComparison, ApiError>;
+
+    async fn import_session_bundle<R: Runtime>(
+        window: Window<R>,
+        bundle: String,
+    ) -> Result<Session, ApiError>;
+
+    async fn import_session_bundle_file<R: Runtime>(
+        window: Window<R>,
+        path: String,
+    ) -> Result<Session, ApiError>;
 
     // ============ Metrics Storage Commands ============
 
+    #[allow(clippy::too_many_arguments)]
     async fn get_session_metrics<R: Runtime>(
         window: Window<R>,
         session_id: String,
-        metric_type: Option<String>,
+        metric_types: Option<Vec<MetricType>>,
         start_time: Option<i64>,
         end_time: Option<i64>,
         limit: Option<u32>,
-    ) -> Result<Vec<StoredMetric>, String>;
+        max_points: Option<u32>,
+    ) -> Result<Vec<StoredMetric>, ApiError>;
 
     async fn get_session_network_requests<R: Runtime>(
         window: Window<R>,
         session_id: String,
+        filter: Option<NetworkRequestFilter>,
         limit: Option<u32>,
-    ) -> Result<Vec<StoredNetworkRequest>, String>;
-}
+    ) -> Result<Vec<StoredNetworkRequest>, ApiError>;
 
-#[derive(Clone)]
-pub struct ApiImpl;
+    async fn get_session_network_requests_page<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        filter: Option<NetworkRequestFilter>,
+        cursor: Option<NetworkRequestCursor>,
+        limit: u32,
+    ) -> Result<NetworkRequestPage, ApiError>;
 
-#[taurpc::resolvers]
-impl Api for ApiImpl {
-    // ============ ADB Commands ============
+    async fn get_metrics_summary<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        metric_type: MetricType,
+        field: String,
+        buckets: Option<u32>,
+    ) -> Result<MetricsSummary, ApiError>;
 
-    async fn get_devices<R: Runtime>(self, window: Window<R>) -> Result<Vec<Device>, String> {
-        adb::list_devices(window.app_handle())
-            .await
-            .map_err(|e| e.to_string())
-    }
+    async fn get_smoothed_metrics<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        metric_type: MetricType,
+        field: String,
+        options: SmoothingOptions,
+    ) -> Result<SmoothedSeries, ApiError>;
 
-    async fn get_webviews<R: Runtime>(
-        self,
+    async fn start_frame_capture<R: Runtime>(
         window: Window<R>,
-        device_id: String,
-    ) -> Result<Vec<WebView>, String> {
-        adb::list_webviews(window.app_handle(), &device_id)
-            .await
-            .map_err(|e| e.to_string())
-    }
+        duration_ms: u64,
+        connection_id: Option<String>,
+    ) -> Result<FrameStats, ApiError>;
 
-    async fn start_port_forward<R: Runtime>(
-        self,
+    async fn start_js_coverage<R: Runtime>(
         window: Window<R>,
-        device_id: String,
-        socket_name: String,
-        local_port: u16,
-    ) -> Result<PortForwardResult, String> {
-        adb::forward_port(window.app_handle(), &device_id, local_port, &socket_name)
-            .await
-            .map_err(|e| e.to_string())?;
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
 
-        Ok(PortForwardResult {
-            local_port,
-            socket_name,
-        })
-    }
+    async fn stop_js_coverage<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<JsCoverage, ApiError>;
+
+    async fn capture_heap_snapshot<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<String, ApiError>;
+
+    async fn start_cpu_profile<R: Runtime>(
+        window: Window<R>,
+        sampling_interval_us: Option<u32>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
+
+    async fn stop_cpu_profile<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<String, ApiError>;
+
+    async fn take_screenshot<R: Runtime>(
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<Screenshot, ApiError>;
+
+    async fn set_screenshot_interval<R: Runtime>(
+        window: Window<R>,
+        every_n_polls: u32,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError>;
+
+    async fn get_frame_stats<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<FrameStats>, ApiError>;
+
+    async fn get_web_vitals<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<WebVitals>, ApiError>;
+
+    async fn get_cpu_metrics<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<CpuUsage>, ApiError>;
+
+    async fn get_network_summary<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        normalize: Option<bool>,
+    ) -> Result<NetworkSummary, ApiError>;
+
+    async fn get_network_by_host<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<Vec<HostNetworkSummary>, ApiError>;
+
+    async fn get_duplicate_requests<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        normalize: Option<bool>,
+    ) -> Result<Vec<DuplicateRequestGroup>, ApiError>;
+
+    async fn get_network_throughput<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        bucket_ms: Option<u32>,
+    ) -> Result<Vec<ThroughputBucket>, ApiError>;
+
+    async fn get_request_transfer_timeline<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        request_id: String,
+    ) -> Result<Vec<RequestDataChunk>, ApiError>;
+
+    async fn get_session_markers<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<Vec<SessionMarker>, ApiError>;
+
+    async fn add_annotation<R: Runtime>(
+        window: Window<R>,
+        session_id: Option<String>,
+        label: String,
+        color: Option<String>,
+        timestamp: Option<i64>,
+    ) -> Result<Annotation, ApiError>;
+
+    async fn get_annotations<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<Vec<Annotation>, ApiError>;
+
+    async fn delete_annotation<R: Runtime>(window: Window<R>, id: i64) -> Result<(), ApiError>;
+
+    async fn get_session_security_events<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<Vec<SecurityEvent>, ApiError>;
+
+    async fn set_alert_thresholds<R: Runtime>(
+        window: Window<R>,
+        thresholds: AlertThresholds,
+    ) -> Result<(), ApiError>;
+
+    async fn get_console_logs<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        level_filter: Option<Vec<ConsoleLevel>>,
+    ) -> Result<Vec<ConsoleLog>, ApiError>;
+
+    async fn get_response_body<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+        request_id: String,
+    ) -> Result<Option<ResponseBody>, ApiError>;
+
+    async fn fetch_response_body<R: Runtime>(
+        window: Window<R>,
+        request_id: String,
+        connection_id: Option<String>,
+    ) -> Result<ResponseBodyContent, ApiError>;
+
+    async fn get_alerts<R: Runtime>(
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<Vec<Alert>, ApiError>;
+
+    // ============ Preset Commands ============
+
+    async fn save_preset<R: Runtime>(
+        window: Window<R>,
+        name: String,
+        config: PresetConfig,
+    ) -> Result<TestPreset, ApiError>;
+
+    async fn list_presets<R: Runtime>(window: Window<R>) -> Result<Vec<TestPreset>, ApiError>;
+
+    async fn apply_preset<R: Runtime>(
+        window: Window<R>,
+        name: String,
+        connection_id: Option<String>,
+    ) -> Result<TestPreset, ApiError>;
+
+    // ============ Settings Commands ============
+
+    async fn get_settings<R: Runtime>(window: Window<R>) -> Result<Settings, ApiError>;
+
+    async fn get_schema_version<R: Runtime>(window: Window<R>) -> Result<u32, ApiError>;
+
+    async fn update_settings<R: Runtime>(
+        window: Window<R>,
+        settings: Settings,
+    ) -> Result<Settings, ApiError>;
+
+    // ============ Database Commands ============
+
+    async fn get_database_path<R: Runtime>(window: Window<R>) -> Result<String, ApiError>;
+
+    async fn open_database<R: Runtime>(window: Window<R>, path: String)
+        -> Result<String, ApiError>;
+
+    async fn compact_database<R: Runtime>(window: Window<R>) -> Result<CompactResult, ApiError>;
+}
+
+#[derive(Clone)]
+pub struct ApiImpl;
+
+#[taurpc::resolvers]
+impl Api for ApiImpl {
+    // ============ ADB Commands ============
+
+    async fn get_devices<R: Runtime>(
+        self,
+        window: Window<R>,
+        connected_only: Option<bool>,
+    ) -> Result<Vec<Device>, ApiError> {
+        let devices = adb::list_devices(window.app_handle()).await?;
+
+        if connected_only.unwrap_or(false) {
+            return Ok(devices
+                .into_iter()
+                .filter(|d| d.status == DeviceStatus::Device)
+                .collect());
+        }
+        Ok(devices)
+    }
+
+    async fn get_default_device<R: Runtime>(self, window: Window<R>) -> Result<Device, ApiError> {
+        adb::get_default_device(window.app_handle())
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn get_webviews<R: Runtime>(
+        self,
+        window: Window<R>,
+        device_id: String,
+    ) -> Result<Vec<WebView>, ApiError> {
+        adb::list_webviews(window.app_handle(), &device_id)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn start_port_forward<R: Runtime>(
+        self,
+        window: Window<R>,
+        device_id: String,
+        socket_name: String,
+        local_port: u16,
+    ) -> Result<PortForwardResult, ApiError> {
+        adb::forward_port(window.app_handle(), &device_id, local_port, &socket_name).await?;
+
+        let state = window.state::<ManagedState>();
+        state
+            .forwards
+            .lock()
+            .unwrap()
+            .insert((device_id, local_port));
+
+        Ok(PortForwardResult {
+            local_port,
+            socket_name,
+        })
+    }
+
+    async fn start_port_forward_auto<R: Runtime>(
+        self,
+        window: Window<R>,
+        device_id: String,
+        socket_name: String,
+    ) -> Result<PortForwardResult, ApiError> {
+        let local_port =
+            adb::forward_port_auto(window.app_handle(), &device_id, &socket_name).await?;
+
+        let state = window.state::<ManagedState>();
+        state
+            .forwards
+            .lock()
+            .unwrap()
+            .insert((device_id, local_port));
+
+        Ok(PortForwardResult {
+            local_port,
+            socket_name,
+        })
+    }
+
+    async fn list_active_forwards<R: Runtime>(
+        self,
+        window: Window<R>,
+        device_id: Option<String>,
+    ) -> Result<Vec<PortForward>, ApiError> {
+        adb::list_forwards(window.app_handle(), device_id.as_deref())
+            .await
+            .map_err(ApiError::from)
+    }
 
     async fn stop_port_forward<R: Runtime>(
         self,
         window: Window<R>,
-        device_id: String,
-        local_port: u16,
-    ) -> Result<(), String> {
-        adb::remove_forward(window.app_handle(), &device_id, local_port)
-            .await
-            .map_err(|e| e.to_string())
+        device_id: String,
+        local_port: u16,
+    ) -> Result<(), ApiError> {
+        adb::remove_forward(window.app_handle(), &device_id, local_port).await?;
+
+        let state = window.state::<ManagedState>();
+        state
+            .forwards
+            .lock()
+            .unwrap()
+            .remove(&(device_id, local_port));
+        Ok(())
+    }
+
+    async fn stop_all_port_forwards<R: Runtime>(
+        self,
+        window: Window<R>,
+        device_id: String,
+    ) -> Result<(), ApiError> {
+        adb::remove_all_forwards(window.app_handle(), &device_id).await?;
+
+        let state = window.state::<ManagedState>();
+        state
+            .forwards
+            .lock()
+            .unwrap()
+            .retain(|(device, _)| *device != device_id);
+        Ok(())
+    }
+
+    async fn start_device_watch<R: Runtime>(self, window: Window<R>) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let mut watch = state.device_watch.lock().unwrap();
+        if watch.is_some() {
+            return Ok(());
+        }
+
+        let app_handle = window.app_handle().clone();
+        let emitter = app_handle.clone();
+        *watch = Some(tauri::async_runtime::spawn(adb::watch_devices(
+            app_handle,
+            move |devices| {
+                let _ = emitter.emit("devices:changed", &devices);
+            },
+        )));
+        Ok(())
+    }
+
+    async fn stop_device_watch<R: Runtime>(self, window: Window<R>) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        if let Some(watch) = state.device_watch.lock().unwrap().take() {
+            watch.abort();
+        }
+        Ok(())
+    }
+
+    async fn connect_wireless_device<R: Runtime>(
+        self,
+        window: Window<R>,
+        host: String,
+        port: u16,
+    ) -> Result<String, ApiError> {
+        adb::connect_wireless(window.app_handle(), &host, port)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn pair_wireless_device<R: Runtime>(
+        self,
+        window: Window<R>,
+        host: String,
+        port: u16,
+        code: String,
+    ) -> Result<(), ApiError> {
+        adb::pair_wireless(window.app_handle(), &host, port, &code)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn disconnect_wireless_device<R: Runtime>(
+        self,
+        window: Window<R>,
+        host: String,
+        port: u16,
+    ) -> Result<(), ApiError> {
+        adb::disconnect_wireless(window.app_handle(), &host, port)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn set_adb_path<R: Runtime>(
+        self,
+        window: Window<R>,
+        path: Option<String>,
+    ) -> Result<AdbVersion, ApiError> {
+        let state = window.state::<ManagedState>();
+        let adb_config = window.state::<AdbConfig>();
+        let path = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+
+        // Make sure the binary actually runs before keeping it
+        let previous = adb_config.path();
+        adb_config.set_path(path.clone());
+        let version = match adb::adb_version(window.app_handle()).await {
+            Ok(version) => version,
+            Err(e) => {
+                adb_config.set_path(previous);
+                return Err(e.into());
+            }
+        };
+
+        let mut settings = state.settings.read().await.clone();
+        settings.adb_path = path;
+        state.database().update_settings(&settings)?;
+        *state.settings.write().await = settings;
+
+        Ok(version)
+    }
+
+    async fn get_adb_version<R: Runtime>(self, window: Window<R>) -> Result<AdbVersion, ApiError> {
+        adb::adb_version(window.app_handle())
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn set_adb_server<R: Runtime>(
+        self,
+        window: Window<R>,
+        host: Option<String>,
+        port: Option<u16>,
+    ) -> Result<Vec<Device>, ApiError> {
+        let state = window.state::<ManagedState>();
+        let adb_config = window.state::<AdbConfig>();
+        let server = host
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty())
+            .map(|host| AdbServer {
+                host,
+                port: port.unwrap_or(adb::DEFAULT_ADB_SERVER_PORT),
+            });
+
+        // Make sure the server answers before keeping it
+        let previous = adb_config.server();
+        adb_config.set_server(server.clone());
+        let devices = match adb::list_devices(window.app_handle()).await {
+            Ok(devices) => devices,
+            Err(e) => {
+                adb_config.set_server(previous);
+                return Err(e.into());
+            }
+        };
+
+        let mut settings = state.settings.read().await.clone();
+        settings.adb_server = server;
+        state.database().update_settings(&settings)?;
+        *state.settings.write().await = settings;
+
+        Ok(devices)
+    }
+
+    // ============ Memory Simulation Commands ============
+
+    async fn send_trim_memory<R: Runtime>(
+        self,
+        window: Window<R>,
+        device_id: String,
+        package_name: String,
+        level: TrimMemoryLevel,
+    ) -> Result<(), ApiError> {
+        adb::send_trim_memory(window.app_handle(), &device_id, &package_name, level)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn get_device_meminfo<R: Runtime>(
+        self,
+        window: Window<R>,
+        device_id: String,
+    ) -> Result<MemoryInfo, ApiError> {
+        adb::get_meminfo(window.app_handle(), &device_id)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn get_device_info<R: Runtime>(
+        self,
+        window: Window<R>,
+        device_id: String,
+    ) -> Result<DeviceProps, ApiError> {
+        adb::get_device_props(window.app_handle(), &device_id)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    // ============ CDP Commands ============
+
+    async fn get_cdp_targets<R: Runtime>(
+        self,
+        window: Window<R>,
+        port: u16,
+    ) -> Result<Vec<CdpTarget>, ApiError> {
+        let host = adb::forward_host(window.app_handle());
+        CdpClient::get_targets_on_host(&host, port)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn get_webview_version<R: Runtime>(
+        self,
+        window: Window<R>,
+        port: Option<u16>,
+        connection_id: Option<String>,
+    ) -> Result<WebViewVersion, ApiError> {
+        let state = window.state::<ManagedState>();
+        let from_cdp = match state.connection(connection_id.as_deref()).await {
+            Ok(connection) => connection
+                .client
+                .get_browser_version()
+                .await
+                .map_err(ApiError::from),
+            Err(e) => Err(e),
+        };
+
+        // Fall back to the forwarded port's /json/version
+        match (from_cdp, port) {
+            (Ok(version), _) => Ok(version),
+            (Err(_), Some(port)) => {
+                let host = adb::forward_host(window.app_handle());
+                Ok(CdpClient::get_version_info(&host, port).await?.into())
+            }
+            (Err(e), None) => Err(e),
+        }
+    }
+
+    async fn get_enriched_targets<R: Runtime>(
+        self,
+        window: Window<R>,
+        device_id: String,
+        socket_name: String,
+        port: u16,
+    ) -> Result<Vec<EnrichedTarget>, ApiError> {
+        let webviews = adb::list_webviews(window.app_handle(), &device_id).await?;
+        let host = adb::forward_host(window.app_handle());
+        let targets = CdpClient::get_targets_on_host(&host, port).await?;
+
+        Ok(adb::match_webview_to_target(
+            &webviews,
+            &socket_name,
+            targets,
+        ))
+    }
+
+    async fn get_devtools_url<R: Runtime>(
+        self,
+        window: Window<R>,
+        port: u16,
+        target_id: String,
+        local_port: u16,
+    ) -> Result<String, ApiError> {
+        let host = adb::forward_host(window.app_handle());
+        let targets = CdpClient::get_targets_on_host(&host, port).await?;
+        let target = targets
+            .iter()
+            .find(|t| t.id == target_id)
+            .ok_or_else(|| ApiError::NotFound(format!("Target not found: {}", target_id)))?;
+
+        Ok(target.devtools_url_for_port(&host, local_port))
+    }
+
+    async fn connect_cdp<R: Runtime>(
+        self,
+        window: Window<R>,
+        ws_url: String,
+        create_blank_page: Option<bool>,
+        timeout_ms: Option<u64>,
+        retries: Option<u32>,
+        page_attempts: Option<u32>,
+        page_retry_delay_ms: Option<u64>,
+        connection_id: Option<String>,
+    ) -> Result<String, ApiError> {
+        let state = window.state::<ManagedState>();
+        let defaults = ConnectOptions::default();
+        let options = ConnectOptions {
+            timeout: timeout_ms.map_or(defaults.timeout, Duration::from_millis),
+            retries: retries.unwrap_or(defaults.retries),
+            page_attempts: page_attempts.unwrap_or(defaults.page_attempts),
+            page_retry_delay: page_retry_delay_ms
+                .map_or(defaults.page_retry_delay, Duration::from_millis),
+            create_blank_page: create_blank_page.unwrap_or(defaults.create_blank_page),
+        };
+
+        // Reconnect the named connection; without an id, open a new one
+        // rather than replacing the default connection's socket
+        let id = connection_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+        // Register before connecting so concurrent calls for one id share
+        // a single client
+        let (connection, created) = {
+            let mut connections = state.connections.write().await;
+            match connections.get(&id) {
+                Some(existing) => (existing.clone(), false),
+                None => {
+                    let connection = Arc::new(Connection::new(id.clone()));
+                    connections.insert(id.clone(), connection.clone());
+                    (connection, true)
+                }
+            }
+        };
+        if connection.connecting.swap(true, Ordering::SeqCst) {
+            return Err(ApiError::InvalidInput(format!(
+                "Connection {} is already connecting",
+                id
+            )));
+        }
+        let result = connection.client.connect(&ws_url, &options).await;
+        connection.connecting.store(false, Ordering::SeqCst);
+        if let Err(e) = result {
+            // Don't keep a connection that never opened
+            if created {
+                state.connections.write().await.remove(&id);
+            }
+            return Err(e.into());
+        }
+
+        let version = match connection.client.get_browser_version().await {
+            Ok(version) => Some(version),
+            Err(_) => match devtools_endpoint(&ws_url) {
+                Some((host, port)) => CdpClient::get_version_info(&host, port)
+                    .await
+                    .ok()
+                    .map(WebViewVersion::from),
+                None => None,
+            },
+        };
+        // A reconnect keeps the connection's session; note the build there too
+        if let (Some(version), Some(session_id)) =
+            (&version, connection.session_id.read().await.clone())
+        {
+            if let Err(e) = record_session_metadata(
+                &state.database(),
+                &session_id,
+                WEBVIEW_VERSION_KEY,
+                version,
+            ) {
+                tracing::warn!("Failed to record WebView version: {}", e);
+            }
+        }
+        *connection.version.write().await = version;
+
+        *state.default_connection.write().await = Some(id.clone());
+        Ok(id)
+    }
+
+    async fn switch_target<R: Runtime>(
+        self,
+        window: Window<R>,
+        ws_url: String,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+
+        // The collector and session stay attached; only the page changes
+        connection.client.switch_target(&ws_url).await?;
+
+        if let Some(session_id) = connection.session_id.read().await.clone() {
+            match connection.client.get_page_location().await {
+                Ok(location) => {
+                    let title = (!location.title.is_empty()).then_some(location.title);
+                    if let Err(e) = state.database().update_session_target(
+                        &session_id,
+                        Some(&location.url),
+                        title.as_deref(),
+                    ) {
+                        tracing::warn!("Failed to record switched target: {}", e);
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to read switched target location: {}", e),
+            }
+        }
+        Ok(())
+    }
+
+    async fn connect_browser(self, host: String, port: u16) -> Result<Vec<CdpTarget>, ApiError> {
+        // Direct CDP mode: talk to a browser's debugging port without ADB.
+        // Verify the browser endpoint, then list its pages for the user to
+        // pick and pass to `connect_cdp`.
+        let info = CdpClient::get_version_info(&host, port).await?;
+        if info.web_socket_debugger_url.is_none() {
+            return Err(ApiError::ConnectionFailed(format!(
+                "{}:{} did not report a browser WebSocket endpoint",
+                host, port
+            )));
+        }
+
+        let targets = CdpClient::get_targets_on_host(&host, port).await?;
+
+        Ok(targets
+            .into_iter()
+            .filter(|t| t.target_type == "page")
+            .collect())
+    }
+
+    async fn disconnect_cdp<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+
+        // Stop metrics collection first
+        if let Some(c) = holder.collectors.write().await.remove(&connection.id) {
+            c.stop().await;
+        }
+
+        state.connections.write().await.remove(&connection.id);
+        {
+            let mut default = state.default_connection.write().await;
+            if default.as_ref() == Some(&connection.id) {
+                *default = state.connections.read().await.keys().next().cloned();
+            }
+        }
+
+        connection.client.disconnect().await.map_err(ApiError::from)
+    }
+
+    async fn set_auto_reconnect<R: Runtime>(
+        self,
+        window: Window<R>,
+        enabled: bool,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        connection.client.set_auto_reconnect(enabled);
+        Ok(())
+    }
+
+    async fn get_cdp_state<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<ConnectionState, ApiError> {
+        let state = window.state::<ManagedState>();
+        match state.connection(connection_id.as_deref()).await {
+            Ok(connection) => Ok(connection.client.get_state().await),
+            Err(_) => Ok(ConnectionState::Disconnected),
+        }
+    }
+
+    async fn check_cdp_health<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<f64, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        connection.client.ping().await.map_err(ApiError::from)
+    }
+
+    async fn start_metrics_collection<R: Runtime>(
+        self,
+        window: Window<R>,
+        poll_interval_ms: Option<u64>,
+        max_samples: Option<u32>,
+        emit_interval_ms: Option<u64>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        let settings = state.settings.read().await.clone();
+        let interval = poll_interval_ms.unwrap_or(settings.poll_interval_ms);
+        let max_samples = max_samples.or(settings.max_samples);
+
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let session_id = connection.session_id.read().await.clone().ok_or_else(|| {
+            ApiError::NoActiveSession("No active session. Create a session first.".to_string())
+        })?;
+
+        let session = state.database().get_session(&session_id).ok().flatten();
+
+        // Sessions without a preset of their own get the default one
+        if let Some(ref name) = settings.default_preset {
+            if session.as_ref().is_some_and(|s| s.preset_name.is_none()) {
+                if let Err(e) = apply_default_preset(&state, &connection, &session_id, name).await {
+                    tracing::warn!("Failed to apply default preset {}: {}", name, e);
+                }
+            }
+        }
+
+        // Process sampling needs the app's PID; skip it if it can't be resolved
+        let process_target = match session {
+            Some(Session {
+                device_id,
+                package_name: Some(package_name),
+                ..
+            }) => adb::get_pid_for_package(window.app_handle(), &device_id, &package_name)
+                .await
+                .ok()
+                .map(|pid| (device_id, pid)),
+            _ => None,
+        };
+
+        let body_capture = settings
+            .body_capture
+            .as_ref()
+            .map(|rule| {
+                regex::Regex::new(&rule.url_pattern)
+                    .map(|pattern| (pattern, rule.max_bytes))
+                    .map_err(|e| {
+                        ApiError::InvalidInput(format!("Invalid body capture pattern: {}", e))
+                    })
+            })
+            .transpose()?;
+
+        let mut collector = MetricsCollector::new(
+            connection.client.clone(),
+            state.database(),
+            session_id.clone(),
+            connection.id.clone(),
+            Some(window.app_handle().clone()),
+        )
+        .with_stall_timeout(Duration::from_millis(settings.stall_timeout_ms))
+        .with_snapshot_interval(Duration::from_millis(settings.snapshot_interval_ms))
+        .with_network_batch_window(Duration::from_millis(settings.network_batch_ms))
+        .with_alert_thresholds(settings.alert_thresholds.clone())
+        .with_event_log(connection.event_log.clone());
+        if let Some((device_id, pid)) = process_target {
+            collector = collector.with_process_sampling(device_id, pid);
+        }
+        if let Some((pattern, max_bytes)) = body_capture {
+            collector = collector.with_body_capture(pattern, max_bytes);
+        }
+        if let Ok(app_data_dir) = window.app_handle().path().app_data_dir() {
+            collector = collector
+                .with_screenshot_dir(Database::get_screenshot_dir(&app_data_dir, &session_id));
+        }
+
+        // Replacing a collector on the same connection stops the old one
+        // first, so the two never record the same events
+        let previous = holder.collectors.write().await.remove(&connection.id);
+        if let Some(previous) = previous {
+            previous.stop().await;
+        }
+
+        collector
+            .start(
+                interval,
+                max_samples,
+                emit_interval_ms.unwrap_or(settings.emit_interval_ms),
+            )
+            .await?;
+
+        // A concurrent start on the same connection may have got in between
+        let raced = holder
+            .collectors
+            .write()
+            .await
+            .insert(connection.id.clone(), collector);
+        if let Some(raced) = raced {
+            raced.stop().await;
+        }
+
+        Ok(())
+    }
+
+    async fn update_poll_interval<R: Runtime>(
+        self,
+        window: Window<R>,
+        poll_interval_ms: u64,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        if poll_interval_ms == 0 {
+            return Err(ApiError::InvalidInput(
+                "Poll interval must be greater than zero".to_string(),
+            ));
+        }
+
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        let collectors = holder.collectors.read().await;
+        let c = collectors.get(&connection.id).ok_or_else(|| {
+            ApiError::NotCollecting("Metrics collection is not running".to_string())
+        })?;
+        c.set_interval(poll_interval_ms).await;
+        Ok(())
+    }
+
+    async fn stop_metrics_collection<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        if let Some(c) = holder.collectors.write().await.remove(&connection.id) {
+            c.stop().await;
+        }
+        Ok(())
+    }
+
+    async fn pause_metrics_collection<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        let collectors = holder.collectors.read().await;
+        let c = collectors.get(&connection.id).ok_or_else(|| {
+            ApiError::NotCollecting("Metrics collection is not running".to_string())
+        })?;
+        c.pause().await;
+        Ok(())
+    }
+
+    async fn resume_metrics_collection<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        let collectors = holder.collectors.read().await;
+        let c = collectors.get(&connection.id).ok_or_else(|| {
+            ApiError::NotCollecting("Metrics collection is not running".to_string())
+        })?;
+        c.resume().await;
+        Ok(())
+    }
+
+    async fn get_collection_stats<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<CollectionStats, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        let collectors = holder.collectors.read().await;
+        let c = collectors.get(&connection.id).ok_or_else(|| {
+            ApiError::NotCollecting("Metrics collection is not running".to_string())
+        })?;
+        Ok(c.stats())
+    }
+
+    async fn get_live_counters<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<LiveCounters, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        let collectors = holder.collectors.read().await;
+        let c = collectors.get(&connection.id).ok_or_else(|| {
+            ApiError::NotCollecting("Metrics collection is not running".to_string())
+        })?;
+        Ok(c.live_counters())
+    }
+
+    async fn get_current_snapshot<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<MetricsSnapshot, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        let collectors = holder.collectors.read().await;
+        let c = collectors.get(&connection.id).ok_or_else(|| {
+            ApiError::NotCollecting("Metrics collection is not running".to_string())
+        })?;
+        Ok(c.snapshot().await)
+    }
+
+    async fn get_collector_status<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<CollectorStatus, ApiError> {
+        let state = window.state::<ManagedState>();
+        let Ok(connection) = state.connection(connection_id.as_deref()).await else {
+            return Ok(CollectorStatus::default());
+        };
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        let collectors = holder.collectors.read().await;
+        match collectors.get(&connection.id) {
+            Some(c) => Ok(c.status().await),
+            None => Ok(CollectorStatus::default()),
+        }
+    }
+
+    async fn poll_events<R: Runtime>(
+        self,
+        window: Window<R>,
+        since_seq: Option<u64>,
+        limit: Option<u32>,
+        connection_id: Option<String>,
+    ) -> Result<EventPage, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let page = connection.event_log.read().await.since(
+            since_seq.unwrap_or(0),
+            limit.unwrap_or(DEFAULT_POLL_EVENTS_LIMIT) as usize,
+        );
+        Ok(page)
+    }
+
+    async fn get_performance_metrics<R: Runtime>(
+        self,
+        window: Window<R>,
+        include_all: bool,
+        connection_id: Option<String>,
+    ) -> Result<PerformanceMetrics, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        connection
+            .client
+            .get_performance_metrics(include_all)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn get_raw_performance_metrics<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<Vec<(String, f64)>, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        connection
+            .client
+            .get_all_performance_metrics()
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn get_layout_metrics<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<LayoutMetrics, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        connection
+            .client
+            .get_layout_metrics()
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn start_screencast<R: Runtime>(
+        self,
+        window: Window<R>,
+        quality: Option<u8>,
+        max_width: Option<u32>,
+        every_nth_frame: Option<u32>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let mut cdp_rx = connection.client.subscribe();
+
+        connection
+            .client
+            .start_screencast(
+                quality.unwrap_or(DEFAULT_SCREENCAST_QUALITY),
+                max_width.or(Some(DEFAULT_SCREENCAST_MAX_WIDTH)),
+                every_nth_frame.unwrap_or(1),
+            )
+            .await?;
+
+        // Forward frames to the UI until the screencast or connection ends.
+        // A lagging receiver just skips the frames it missed.
+        let app_handle = window.app_handle().clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                match cdp_rx.recv().await {
+                    Ok(CdpEvent::ScreencastFrame(frame)) => {
+                        let _ = app_handle.emit("cdp:screencast_frame", &frame);
+                    }
+                    Ok(CdpEvent::ScreencastStopped) | Ok(CdpEvent::Disconnected) => break,
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn stop_screencast<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        connection
+            .client
+            .stop_screencast()
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn apply_network_throttle<R: Runtime>(
+        self,
+        window: Window<R>,
+        preset: Option<String>,
+        custom: Option<NetworkThrottle>,
+        connection_id: Option<String>,
+    ) -> Result<NetworkThrottle, ApiError> {
+        let state = window.state::<ManagedState>();
+        let throttle = match (preset, custom) {
+            (Some(name), _) => NetworkThrottle::preset(&name).ok_or_else(|| {
+                ApiError::InvalidInput(format!(
+                    "Unknown network preset: {} (expected one of: {})",
+                    name,
+                    NetworkThrottle::PRESETS.join(", ")
+                ))
+            })?,
+            (None, Some(custom)) => custom,
+            (None, None) => {
+                return Err(ApiError::InvalidInput(
+                    "Specify a network preset or custom conditions".to_string(),
+                ))
+            }
+        };
+
+        let connection = state.connection(connection_id.as_deref()).await?;
+        connection.client.set_network_conditions(&throttle).await?;
+
+        // Mark the switch so observed durations can be compared before/after
+        if let Some(ref session_id) = *connection.session_id.read().await {
+            let marker = SessionMarker::new(session_id, MarkerKind::NetworkThrottleChanged);
+            state.database().store_marker(&marker)?;
+        }
+
+        Ok(throttle)
+    }
+
+    // ============ Session Commands ============
+
+    async fn create_session<R: Runtime>(
+        self,
+        window: Window<R>,
+        params: CreateSessionParams,
+        connection_id: Option<String>,
+        activate: Option<bool>,
+    ) -> Result<Session, ApiError> {
+        let state = window.state::<ManagedState>();
+        let activate = activate.unwrap_or(true);
+        let mut session = Session::new(
+            params.device_id,
+            params.device_name,
+            params.package_name,
+            params.target_title,
+            params.webview_url,
+        );
+
+        let connection = state
+            .session_connection(connection_id.as_deref(), activate)
+            .await?;
+
+        if let Some(connection) = &connection {
+            // Record which WebView build produced the session
+            if let Some(version) = connection.version.read().await.as_ref() {
+                session.metadata.get_or_insert_with(HashMap::new).insert(
+                    WEBVIEW_VERSION_KEY.to_string(),
+                    serde_json::to_value(version)?,
+                );
+            }
+
+            // The listed target may be a splash page the app already
+            // redirected away from; prefer what is loaded now
+            if let Ok(location) = connection.client.get_page_location().await {
+                session.webview_url = Some(location.url);
+                if !location.title.is_empty() {
+                    session.target_title = Some(location.title);
+                }
+            }
+        }
+
+        state
+            .add_session(&session, connection.as_deref(), activate)
+            .await?;
+
+        // Record the device context so runs compare like with like. That
+        // takes several adb round trips, so it lands in the metadata shortly
+        // after the session is returned; a silent device only costs the props.
+        let app_handle = window.app_handle().clone();
+        let database = state.database();
+        let (session_id, device_id) = (session.id.clone(), session.device_id.clone());
+        tauri::async_runtime::spawn(async move {
+            let recorded = match adb::get_device_props(&app_handle, &device_id).await {
+                Ok(props) => {
+                    record_session_metadata(&database, &session_id, DEVICE_PROPS_KEY, &props)
+                }
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = recorded {
+                tracing::warn!("Failed to record device properties: {}", e);
+            }
+        });
+
+        Ok(session)
+    }
+
+    async fn end_session<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        finish_session(&window, session_id, SessionStatus::Completed, None).await
+    }
+
+    async fn abort_session<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: Option<String>,
+        reason: Option<String>,
+    ) -> Result<(), ApiError> {
+        finish_session(
+            &window,
+            session_id,
+            SessionStatus::Aborted,
+            reason.as_deref(),
+        )
+        .await
+    }
+
+    async fn get_active_session<R: Runtime>(
+        self,
+        window: Window<R>,
+    ) -> Result<Option<Session>, ApiError> {
+        let state = window.state::<ManagedState>();
+        let mut attached = HashSet::new();
+        for connection in state.connections.read().await.values() {
+            if let Some(id) = connection.session_id.read().await.clone() {
+                attached.insert(id);
+            }
+        }
+
+        // Newest active session no connection records into
+        let sessions = state.database().get_active_sessions()?;
+        Ok(sessions.into_iter().find(|s| !attached.contains(&s.id)))
+    }
+
+    async fn resume_session<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        connection_id: Option<String>,
+    ) -> Result<Session, ApiError> {
+        self.set_active_session(window, session_id, connection_id)
+            .await
+    }
+
+    async fn set_active_session<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        connection_id: Option<String>,
+    ) -> Result<Session, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .activate_session(session_id, connection_id.as_deref())
+            .await
+    }
+
+    async fn get_session<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<Option<Session>, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .get_session(&session_id)
+            .map_err(ApiError::from)
+    }
+
+    async fn get_session_counts<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<SessionCounts, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .get_session_counts(&session_id)
+            .map_err(ApiError::from)
+    }
+
+    async fn get_session_detail<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        metric_limit: Option<u32>,
+        request_limit: Option<u32>,
+    ) -> Result<SessionDetail, ApiError> {
+        let state = window.state::<ManagedState>();
+        let db = state.database();
+
+        let session = db
+            .get_session(&session_id)?
+            .ok_or_else(|| ApiError::NotFound(format!("Session not found: {}", session_id)))?;
+        let counts = db.get_session_counts(&session_id)?;
+        let recent_metrics =
+            db.get_recent_metrics(&session_id, metric_limit.unwrap_or(DEFAULT_DETAIL_LIMIT))?;
+        let recent_requests = db.get_recent_network_requests(
+            &session_id,
+            request_limit.unwrap_or(DEFAULT_DETAIL_LIMIT),
+        )?;
+        let markers = db.get_markers(&session_id)?;
+        let annotations = db.get_annotations(&session_id)?;
+
+        Ok(SessionDetail {
+            session,
+            counts,
+            recent_metrics,
+            recent_requests,
+            markers,
+            annotations,
+        })
+    }
+
+    async fn list_sessions<R: Runtime>(
+        self,
+        window: Window<R>,
+        limit: Option<u32>,
+    ) -> Result<Vec<Session>, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .list_sessions(limit)
+            .map_err(ApiError::from)
+    }
+
+    async fn delete_session<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+
+        // Clear current session if it matches the deleted one
+        state
+            .release_sessions(std::slice::from_ref(&session_id))
+            .await;
+
+        state
+            .database()
+            .delete_session(&session_id)
+            .map_err(ApiError::from)
+    }
+
+    async fn delete_sessions<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_ids: Vec<String>,
+    ) -> Result<BatchResult, ApiError> {
+        let state = window.state::<ManagedState>();
+
+        // Clear current session if it is among the deleted ones
+        state.release_sessions(&session_ids).await;
+
+        state
+            .database()
+            .delete_sessions(&session_ids)
+            .map_err(ApiError::from)
+    }
+
+    async fn prune_database<R: Runtime>(
+        self,
+        window: Window<R>,
+        retention_days: u32,
+    ) -> Result<PruneResult, ApiError> {
+        let state = window.state::<ManagedState>();
+        let result = state
+            .database()
+            .prune_old_sessions(retention_cutoff(retention_days))?;
+        if result.sessions_deleted > 0 {
+            state.database().vacuum()?;
+        }
+
+        Ok(result)
+    }
+
+    async fn update_session_name<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        display_name: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .update_session_name(&session_id, display_name.as_deref())
+            .map_err(ApiError::from)
+    }
+
+    async fn update_session_from_target<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: Option<String>,
+        connection_id: Option<String>,
+    ) -> Result<Session, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let session_id = match session_id {
+            Some(id) => id,
+            None => connection.session_id.read().await.clone().ok_or_else(|| {
+                ApiError::NoActiveSession("No active session. Create a session first.".to_string())
+            })?,
+        };
+
+        let location = connection.client.get_page_location().await?;
+        let title = (!location.title.is_empty()).then_some(location.title);
+        state.database().update_session_target(
+            &session_id,
+            Some(&location.url),
+            title.as_deref(),
+        )?;
+
+        state
+            .database()
+            .get_session(&session_id)?
+            .ok_or_else(|| ApiError::NotFound(format!("Session not found: {}", session_id)))
+    }
+
+    async fn update_session_tags<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        tags: Option<Vec<String>>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .update_session_tags(&session_id, tags.as_deref())
+            .map_err(ApiError::from)
+    }
+
+    async fn add_tag_to_sessions<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_ids: Vec<String>,
+        tag: String,
+    ) -> Result<BatchResult, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .add_tag_to_sessions(&session_ids, &tag)
+            .map_err(ApiError::from)
+    }
+
+    async fn remove_tag_from_sessions<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_ids: Vec<String>,
+        tag: String,
+    ) -> Result<BatchResult, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .remove_tag_from_sessions(&session_ids, &tag)
+            .map_err(ApiError::from)
+    }
+
+    async fn get_all_tags<R: Runtime>(self, window: Window<R>) -> Result<Vec<TagCount>, ApiError> {
+        let state = window.state::<ManagedState>();
+        state.database().list_all_tags().map_err(ApiError::from)
+    }
+
+    async fn update_session_metadata<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        metadata: Option<HashMap<String, serde_json::Value>>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .update_session_metadata(&session_id, metadata.as_ref())
+            .map_err(ApiError::from)
+    }
+
+    async fn search_sessions<R: Runtime>(
+        self,
+        window: Window<R>,
+        filter: SessionSearchFilter,
+    ) -> Result<Vec<Session>, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .search_sessions(&filter)
+            .map_err(ApiError::from)
+    }
+
+    async fn count_sessions<R: Runtime>(
+        self,
+        window: Window<R>,
+        filter: SessionSearchFilter,
+    ) -> Result<i64, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .count_sessions(&filter)
+            .map_err(ApiError::from)
+    }
+
+    async fn export_session_bundle<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<String, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .export_session_bundle(&session_id)
+            .map_err(ApiError::from)
+    }
+
+    async fn export_session_bundle_to_file<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        path: String,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let file = std::fs::File::create(&path)?;
+        state
+            .database()
+            .write_session_bundle(&session_id, std::io::BufWriter::new(file))
+            .map(|_| ())
+            .map_err(ApiError::from)
+    }
+
+    async fn export_har<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<String, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .export_har(&session_id)
+            .map_err(ApiError::from)
+    }
+
+    async fn export_session_csv<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        kind: CsvExportKind,
+    ) -> Result<String, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .export_session_csv(&session_id, kind)
+            .map_err(ApiError::from)
+    }
+
+    async fn export_metrics_ndjson<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        path: String,
+    ) -> Result<i64, ApiError> {
+        let state = window.state::<ManagedState>();
+        // Don't leave an empty file behind for a session that doesn't exist
+        if state.database().get_session(&session_id)?.is_none() {
+            return Err(ApiError::NotFound(format!(
+                "Session not found: {}",
+                session_id
+            )));
+        }
+        let file = std::fs::File::create(&path)?;
+        state
+            .database()
+            .write_metrics_ndjson(&session_id, std::io::BufWriter::new(file))
+            .map_err(ApiError::from)
+    }
+
+    async fn compare_sessions<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_a: String,
+        session_b: String,
+    ) -> Result<SessionComparison, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .compare_sessions(&session_a, &session_b)
+            .map_err(ApiError::from)
+    }
+
+    async fn import_session_bundle<R: Runtime>(
+        self,
+        window: Window<R>,
+        bundle: String,
+    ) -> Result<Session, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .import_session_bundle(&bundle)
+            .map_err(ApiError::from)
+    }
+
+    async fn import_session_bundle_file<R: Runtime>(
+        self,
+        window: Window<R>,
+        path: String,
+    ) -> Result<Session, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .import_session_bundle_file(std::path::Path::new(&path))
+            .map_err(ApiError::from)
+    }
+
+    // ============ Metrics Storage Commands ============
+
+    async fn get_session_metrics<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        metric_types: Option<Vec<MetricType>>,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: Option<u32>,
+        max_points: Option<u32>,
+    ) -> Result<Vec<StoredMetric>, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .get_metrics_downsampled(
+                &session_id,
+                metric_types.as_deref(),
+                start_time,
+                end_time,
+                limit,
+                max_points,
+            )
+            .map_err(ApiError::from)
+    }
+
+    async fn get_session_network_requests<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        filter: Option<NetworkRequestFilter>,
+        limit: Option<u32>,
+    ) -> Result<Vec<StoredNetworkRequest>, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .get_network_requests(&session_id, &filter.unwrap_or_default(), limit)
+            .map_err(ApiError::from)
+    }
+
+    async fn get_session_network_requests_page<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        filter: Option<NetworkRequestFilter>,
+        cursor: Option<NetworkRequestCursor>,
+        limit: u32,
+    ) -> Result<NetworkRequestPage, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .get_network_requests_page(
+                &session_id,
+                &filter.unwrap_or_default(),
+                cursor.as_ref(),
+                limit,
+            )
+            .map_err(ApiError::from)
+    }
+
+    async fn get_metrics_summary<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        metric_type: MetricType,
+        field: String,
+        buckets: Option<u32>,
+    ) -> Result<MetricsSummary, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .aggregate_metrics(&session_id, metric_type, &field, buckets)
+            .map_err(ApiError::from)
     }
 
-    async fn stop_all_port_forwards<R: Runtime>(
+    async fn get_smoothed_metrics<R: Runtime>(
         self,
         window: Window<R>,
-        device_id: String,
-    ) -> Result<(), String> {
-        adb::remove_all_forwards(window.app_handle(), &device_id)
-            .await
-            .map_err(|e| e.to_string())
+        session_id: String,
+        metric_type: MetricType,
+        field: String,
+        options: SmoothingOptions,
+    ) -> Result<SmoothedSeries, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .smoothed_metrics(&session_id, metric_type, &field, &options, None, None)
+            .map_err(ApiError::from)
     }
 
-    // ============ Memory Simulation Commands ============
-
-    async fn send_trim_memory<R: Runtime>(
+    async fn start_frame_capture<R: Runtime>(
         self,
         window: Window<R>,
-        device_id: String,
-        package_name: String,
-        level: TrimMemoryLevel,
-    ) -> Result<(), String> {
-        adb::send_trim_memory(window.app_handle(), &device_id, &package_name, level)
-            .await
-            .map_err(|e| e.to_string())
+        duration_ms: u64,
+        connection_id: Option<String>,
+    ) -> Result<FrameStats, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let stats = connection
+            .client
+            .capture_frames(Duration::from_millis(duration_ms))
+            .await?;
+
+        // Keep the result with the active session, if there is one
+        let session_id = connection.session_id.read().await.clone();
+        if let Some(session_id) = session_id {
+            let metric = StoredMetric {
+                id: None,
+                session_id,
+                timestamp: stats.timestamp,
+                metric_type: MetricType::Frames,
+                data: serde_json::to_string(&stats)?,
+            };
+            state.database().store_metric(&metric)?;
+        }
+
+        Ok(stats)
     }
 
-    async fn get_device_meminfo<R: Runtime>(
+    async fn start_js_coverage<R: Runtime>(
         self,
         window: Window<R>,
-        device_id: String,
-    ) -> Result<MemoryInfo, String> {
-        adb::get_meminfo(window.app_handle(), &device_id)
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        connection
+            .client
+            .start_js_coverage()
             .await
-            .map_err(|e| e.to_string())
+            .map_err(ApiError::from)
     }
 
-    // ============ CDP Commands ============
+    async fn stop_js_coverage<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<JsCoverage, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let coverage = connection.client.stop_js_coverage().await?;
+
+        // Keep the totals with the active session; per-script detail is
+        // only returned
+        let session_id = connection.session_id.read().await.clone();
+        if let Some(session_id) = session_id {
+            let totals = JsCoverage {
+                scripts: Vec::new(),
+                ..coverage.clone()
+            };
+            let metric = StoredMetric {
+                id: None,
+                session_id,
+                timestamp: coverage.timestamp,
+                metric_type: MetricType::Coverage,
+                data: serde_json::to_string(&totals)?,
+            };
+            state.database().store_metric(&metric)?;
+        }
 
-    async fn get_cdp_targets(self, port: u16) -> Result<Vec<CdpTarget>, String> {
-        CdpClient::get_targets(port)
-            .await
-            .map_err(|e| e.to_string())
+        Ok(coverage)
     }
 
-    async fn connect_cdp<R: Runtime>(
+    async fn capture_heap_snapshot<R: Runtime>(
         self,
         window: Window<R>,
-        ws_url: String,
-    ) -> Result<(), String> {
+        connection_id: Option<String>,
+    ) -> Result<String, ApiError> {
         let state = window.state::<ManagedState>();
-        state
-            .cdp_client
-            .connect(&ws_url)
+        let dir = window
+            .app_handle()
+            .path()
+            .app_data_dir()?
+            .join("heap_snapshots");
+        std::fs::create_dir_all(&dir)?;
+
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let prefix = connection
+            .session_id
+            .read()
             .await
-            .map_err(|e| e.to_string())
+            .clone()
+            .unwrap_or_else(|| "heap".to_string());
+        let path = dir.join(format!(
+            "{}-{}.heapsnapshot",
+            prefix,
+            chrono::Utc::now().timestamp_millis()
+        ));
+
+        let emitter = window.app_handle().clone();
+        connection
+            .client
+            .take_heap_snapshot(&path, |progress| {
+                let _ = emitter.emit("cdp:heap_snapshot_progress", &progress);
+            })
+            .await?;
+
+        Ok(path.to_string_lossy().into_owned())
     }
 
-    async fn disconnect_cdp<R: Runtime>(self, window: Window<R>) -> Result<(), String> {
-        let state = window.state::<ManagedState>();
-        let holder = window.state::<MetricsCollectorHolder<R>>();
-
-        // Stop metrics collection first
-        {
-            let mut collector = holder.collector.write().await;
-            if let Some(c) = collector.as_ref() {
-                c.stop().await;
-            }
-            *collector = None;
+    async fn start_cpu_profile<R: Runtime>(
+        self,
+        window: Window<R>,
+        sampling_interval_us: Option<u32>,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        if sampling_interval_us == Some(0) {
+            return Err(ApiError::InvalidInput(
+                "Sampling interval must be positive".into(),
+            ));
         }
-
-        state
-            .cdp_client
-            .disconnect()
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        connection
+            .client
+            .start_cpu_profile(sampling_interval_us)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(ApiError::from)
     }
 
-    async fn get_cdp_state<R: Runtime>(self, window: Window<R>) -> Result<ConnectionState, String> {
+    async fn stop_cpu_profile<R: Runtime>(
+        self,
+        window: Window<R>,
+        connection_id: Option<String>,
+    ) -> Result<String, ApiError> {
         let state = window.state::<ManagedState>();
-        Ok(state.cdp_client.get_state().await)
+        let dir = window
+            .app_handle()
+            .path()
+            .app_data_dir()?
+            .join("cpu_profiles");
+        std::fs::create_dir_all(&dir)?;
+
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let profile = connection.client.stop_cpu_profile().await?;
+
+        let prefix = connection
+            .session_id
+            .read()
+            .await
+            .clone()
+            .unwrap_or_else(|| "cpu".to_string());
+        let path = dir.join(format!(
+            "{}-{}.cpuprofile",
+            prefix,
+            chrono::Utc::now().timestamp_millis()
+        ));
+        std::fs::write(&path, profile)?;
+
+        Ok(path.to_string_lossy().into_owned())
     }
 
-    async fn start_metrics_collection<R: Runtime>(
+    async fn take_screenshot<R: Runtime>(
         self,
         window: Window<R>,
-        poll_interval_ms: Option<u64>,
-    ) -> Result<(), String> {
+        connection_id: Option<String>,
+    ) -> Result<Screenshot, ApiError> {
         let state = window.state::<ManagedState>();
-        let holder = window.state::<MetricsCollectorHolder<R>>();
-        let interval = poll_interval_ms.unwrap_or(1000);
-
-        // Get current session ID
-        let session_id = {
-            let current = state.current_session_id.read().await;
-            current
-                .clone()
-                .ok_or("No active session. Create a session first.")?
-        };
-
-        let collector = MetricsCollector::new(
-            state.cdp_client.clone(),
-            state.database.clone(),
-            session_id,
-            Some(window.app_handle().clone()),
+        let app_data_dir = window.app_handle().path().app_data_dir()?;
+        let connection = state.connection(connection_id.as_deref()).await?;
+        let session_id = connection.session_id.read().await.clone();
+        let dir = Database::get_screenshot_dir(
+            &app_data_dir,
+            session_id.as_deref().unwrap_or("no_session"),
         );
-        collector.start(interval).await.map_err(|e| e.to_string())?;
-
-        let mut collector_lock = holder.collector.write().await;
-        *collector_lock = Some(collector);
 
-        Ok(())
+        save_screenshot(&connection.client, &dir)
+            .await
+            .map_err(ApiError::from)
     }
 
-    async fn stop_metrics_collection<R: Runtime>(self, window: Window<R>) -> Result<(), String> {
+    async fn set_screenshot_interval<R: Runtime>(
+        self,
+        window: Window<R>,
+        every_n_polls: u32,
+        connection_id: Option<String>,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
         let holder = window.state::<MetricsCollectorHolder<R>>();
-        let mut collector = holder.collector.write().await;
-        if let Some(c) = collector.as_ref() {
-            c.stop().await;
-        }
-        *collector = None;
+        let collectors = holder.collectors.read().await;
+        let c = collectors.get(&connection.id).ok_or_else(|| {
+            ApiError::NotCollecting("Metrics collection is not running".to_string())
+        })?;
+        c.set_screenshot_interval(every_n_polls);
         Ok(())
     }
 
-    async fn get_performance_metrics<R: Runtime>(
+    async fn get_frame_stats<R: Runtime>(
         self,
         window: Window<R>,
-    ) -> Result<PerformanceMetrics, String> {
+        session_id: String,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<FrameStats>, ApiError> {
         let state = window.state::<ManagedState>();
-        state
-            .cdp_client
-            .get_performance_metrics()
-            .await
-            .map_err(|e| e.to_string())
+        let metrics = state.database().get_metrics(
+            &session_id,
+            Some(&[MetricType::Frames]),
+            start_time,
+            end_time,
+            limit,
+        )?;
+
+        metrics
+            .iter()
+            .map(|m| serde_json::from_str(&m.data).map_err(ApiError::from))
+            .collect()
     }
 
-    // ============ Session Commands ============
+    async fn get_web_vitals<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<WebVitals>, ApiError> {
+        let state = window.state::<ManagedState>();
+        let metrics = state.database().get_metrics(
+            &session_id,
+            Some(&[MetricType::WebVitals]),
+            start_time,
+            end_time,
+            limit,
+        )?;
+
+        metrics
+            .iter()
+            .map(|m| serde_json::from_str(&m.data).map_err(ApiError::from))
+            .collect()
+    }
 
-    async fn create_session<R: Runtime>(
+    async fn get_cpu_metrics<R: Runtime>(
         self,
         window: Window<R>,
-        params: CreateSessionParams,
-    ) -> Result<Session, String> {
+        session_id: String,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: Option<u32>,
+    ) -> Result<Vec<CpuUsage>, ApiError> {
         let state = window.state::<ManagedState>();
-        let session = Session::new(
-            params.device_id,
-            params.device_name,
-            params.package_name,
-            params.target_title,
-            params.webview_url,
-        );
+        let metrics = state.database().get_metrics(
+            &session_id,
+            Some(&[MetricType::Cpu]),
+            start_time,
+            end_time,
+            limit,
+        )?;
+
+        metrics
+            .iter()
+            .map(|m| serde_json::from_str(&m.data).map_err(ApiError::from))
+            .collect()
+    }
 
+    async fn get_network_summary<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        normalize: Option<bool>,
+    ) -> Result<NetworkSummary, ApiError> {
+        let state = window.state::<ManagedState>();
+        let normalizer = normalize.unwrap_or(false).then(UrlNormalizer::default);
         state
-            .database
-            .create_session(&session)
-            .map_err(|e| e.to_string())?;
+            .database()
+            .get_network_summary(&session_id, normalizer.as_ref())
+            .map_err(ApiError::from)
+    }
 
-        // Set as current session
-        {
-            let mut current = state.current_session_id.write().await;
-            *current = Some(session.id.clone());
-        }
+    async fn get_network_by_host<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<Vec<HostNetworkSummary>, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .network_by_host(&session_id)
+            .map_err(ApiError::from)
+    }
 
-        Ok(session)
+    async fn get_duplicate_requests<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        normalize: Option<bool>,
+    ) -> Result<Vec<DuplicateRequestGroup>, ApiError> {
+        let state = window.state::<ManagedState>();
+        let normalizer = normalize.unwrap_or(false).then(UrlNormalizer::default);
+        state
+            .database()
+            .find_duplicate_requests(&session_id, normalizer.as_ref())
+            .map_err(ApiError::from)
     }
 
-    async fn end_session<R: Runtime>(
+    async fn get_network_throughput<R: Runtime>(
         self,
         window: Window<R>,
-        session_id: Option<String>,
-    ) -> Result<(), String> {
+        session_id: String,
+        bucket_ms: Option<u32>,
+    ) -> Result<Vec<ThroughputBucket>, ApiError> {
+        let bucket_ms = bucket_ms.unwrap_or(1000);
+        if bucket_ms == 0 {
+            return Err(ApiError::InvalidInput(
+                "Bucket size must be at least 1 ms".to_string(),
+            ));
+        }
         let state = window.state::<ManagedState>();
-        let id = if let Some(id) = session_id {
-            id
-        } else {
-            let current = state.current_session_id.read().await;
-            current.clone().ok_or("No active session")?
-        };
+        state
+            .database()
+            .network_throughput(&session_id, bucket_ms as i64)
+            .map_err(ApiError::from)
+    }
 
-        let ended_at = chrono::Utc::now().timestamp_millis();
+    async fn get_request_transfer_timeline<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        request_id: String,
+    ) -> Result<Vec<RequestDataChunk>, ApiError> {
+        let state = window.state::<ManagedState>();
         state
-            .database
-            .end_session(&id, ended_at)
-            .map_err(|e| e.to_string())?;
+            .database()
+            .get_request_data_chunks(&session_id, &request_id)
+            .map_err(ApiError::from)
+    }
 
-        // Clear current session if it matches
-        {
-            let mut current = state.current_session_id.write().await;
-            if current.as_ref() == Some(&id) {
-                *current = None;
-            }
+    async fn get_session_markers<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+    ) -> Result<Vec<SessionMarker>, ApiError> {
+        let state = window.state::<ManagedState>();
+        state
+            .database()
+            .get_markers(&session_id)
+            .map_err(ApiError::from)
+    }
+
+    async fn add_annotation<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: Option<String>,
+        label: String,
+        color: Option<String>,
+        timestamp: Option<i64>,
+    ) -> Result<Annotation, ApiError> {
+        let state = window.state::<ManagedState>();
+        let label = label.trim().to_string();
+        if label.is_empty() {
+            return Err(ApiError::InvalidInput(
+                "Annotation label must not be empty".to_string(),
+            ));
         }
+        let session_id = match session_id {
+            Some(id) => id,
+            None => state.default_session_id().await.ok_or_else(|| {
+                ApiError::NoActiveSession("No active session. Create a session first.".to_string())
+            })?,
+        };
 
-        Ok(())
+        let mut annotation = Annotation {
+            id: None,
+            session_id,
+            timestamp: timestamp.unwrap_or_else(|| chrono::Utc::now().timestamp_millis()),
+            label,
+            color,
+        };
+        annotation.id = Some(state.database().store_annotation(&annotation)?);
+        Ok(annotation)
     }
 
-    async fn get_session<R: Runtime>(
+    async fn get_annotations<R: Runtime>(
         self,
         window: Window<R>,
         session_id: String,
-    ) -> Result<Option<Session>, String> {
+    ) -> Result<Vec<Annotation>, ApiError> {
         let state = window.state::<ManagedState>();
         state
-            .database
-            .get_session(&session_id)
-            .map_err(|e| e.to_string())
+            .database()
+            .get_annotations(&session_id)
+            .map_err(ApiError::from)
     }
 
-    async fn list_sessions<R: Runtime>(
+    async fn delete_annotation<R: Runtime>(
         self,
         window: Window<R>,
-        limit: Option<u32>,
-    ) -> Result<Vec<Session>, String> {
+        id: i64,
+    ) -> Result<(), ApiError> {
         let state = window.state::<ManagedState>();
         state
-            .database
-            .list_sessions(limit)
-            .map_err(|e| e.to_string())
+            .database()
+            .delete_annotation(id)
+            .map_err(ApiError::from)
     }
 
-    async fn delete_session<R: Runtime>(
+    async fn get_session_security_events<R: Runtime>(
         self,
         window: Window<R>,
         session_id: String,
-    ) -> Result<(), String> {
+    ) -> Result<Vec<SecurityEvent>, ApiError> {
         let state = window.state::<ManagedState>();
+        state
+            .database()
+            .get_security_events(&session_id)
+            .map_err(ApiError::from)
+    }
 
-        // Clear current session if it matches the deleted one
-        {
-            let mut current = state.current_session_id.write().await;
-            if current.as_ref() == Some(&session_id) {
-                *current = None;
-            }
+    async fn set_alert_thresholds<R: Runtime>(
+        self,
+        window: Window<R>,
+        thresholds: AlertThresholds,
+    ) -> Result<(), ApiError> {
+        let state = window.state::<ManagedState>();
+
+        // Persist so the next collection starts with the same limits
+        let mut settings = state.settings.read().await.clone();
+        settings.alert_thresholds = thresholds.clone();
+        state.database().update_settings(&settings)?;
+        *state.settings.write().await = settings;
+
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        for c in holder.collectors.read().await.values() {
+            c.set_alert_thresholds(thresholds.clone()).await;
         }
+        Ok(())
+    }
 
+    async fn get_console_logs<R: Runtime>(
+        self,
+        window: Window<R>,
+        session_id: String,
+        level_filter: Option<Vec<ConsoleLevel>>,
+    ) -> Result<Vec<ConsoleLog>, ApiError> {
+        let state = window.state::<ManagedState>();
         state
-            .database
-            .delete_session(&session_id)
-            .map_err(|e| e.to_string())
+            .database()
+            .get_console_logs(&session_id, level_filter.as_deref())
+            .map_err(ApiError::from)
     }
 
-    async fn update_session_name<R: Runtime>(
+    async fn get_response_body<R: Runtime>(
         self,
         window: Window<R>,
         session_id: String,
-        display_name: Option<String>,
-    ) -> Result<(), String> {
+        request_id: String,
+    ) -> Result<Option<ResponseBody>, ApiError> {
         let state = window.state::<ManagedState>();
         state
-            .database
-            .update_session_name(&session_id, display_name.as_deref())
-            .map_err(|e| e.to_string())
+            .database()
+            .get_response_body(&session_id, &request_id)
+            .map_err(ApiError::from)
     }
 
-    async fn update_session_tags<R: Runtime>(
+    async fn fetch_response_body<R: Runtime>(
+        self,
+        window: Window<R>,
+        request_id: String,
+        connection_id: Option<String>,
+    ) -> Result<ResponseBodyContent, ApiError> {
+        let state = window.state::<ManagedState>();
+        let connection = state.connection(connection_id.as_deref()).await?;
+        connection
+            .client
+            .get_response_body(&request_id)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    async fn get_alerts<R: Runtime>(
         self,
         window: Window<R>,
         session_id: String,
-        tags: Option<Vec<String>>,
-    ) -> Result<(), String> {
+    ) -> Result<Vec<Alert>, ApiError> {
         let state = window.state::<ManagedState>();
         state
-            .database
-            .update_session_tags(&session_id, tags.as_deref())
-            .map_err(|e| e.to_string())
+            .database()
+            .get_alerts(&session_id)
+            .map_err(ApiError::from)
     }
 
-    async fn search_sessions<R: Runtime>(
+    // ============ Preset Commands ============
+
+    async fn save_preset<R: Runtime>(
         self,
         window: Window<R>,
-        query: Option<String>,
-        device_id: Option<String>,
-        status: Option<String>,
-        tags: Option<Vec<String>>,
-        limit: Option<u32>,
-    ) -> Result<Vec<Session>, String> {
+        name: String,
+        config: PresetConfig,
+    ) -> Result<TestPreset, ApiError> {
         let state = window.state::<ManagedState>();
-        state
-            .database
-            .search_sessions(
-                query.as_deref(),
-                device_id.as_deref(),
-                status.as_deref(),
-                tags.as_deref(),
-                limit,
-            )
-            .map_err(|e| e.to_string())
+        let preset = TestPreset::new(name, config);
+        state.database().save_preset(&preset)?;
+        Ok(preset)
     }
 
-    // ============ Metrics Storage Commands ============
+    async fn list_presets<R: Runtime>(
+        self,
+        window: Window<R>,
+    ) -> Result<Vec<TestPreset>, ApiError> {
+        let state = window.state::<ManagedState>();
+        state.database().list_presets().map_err(ApiError::from)
+    }
 
-    async fn get_session_metrics<R: Runtime>(
+    async fn apply_preset<R: Runtime>(
         self,
         window: Window<R>,
-        session_id: String,
-        metric_type: Option<String>,
-        start_time: Option<i64>,
-        end_time: Option<i64>,
-        limit: Option<u32>,
-    ) -> Result<Vec<StoredMetric>, String> {
+        name: String,
+        connection_id: Option<String>,
+    ) -> Result<TestPreset, ApiError> {
+        let state = window.state::<ManagedState>();
+        let preset = state.database().get_preset(&name)?;
+
+        let connection = state.connection(connection_id.as_deref()).await?;
+        connection
+            .client
+            .apply_preset_config(&preset.config)
+            .await?;
+
+        // Record provenance on the active session
+        let current = connection.session_id.read().await;
+        if let Some(ref session_id) = *current {
+            state
+                .database()
+                .set_session_preset(session_id, &preset.name)?;
+        }
+
+        Ok(preset)
+    }
+
+    // ============ Settings Commands ============
+
+    async fn get_settings<R: Runtime>(self, window: Window<R>) -> Result<Settings, ApiError> {
         let state = window.state::<ManagedState>();
-        let mt = metric_type.map(|s| MetricType::from_str(&s));
+        let settings = state.settings.read().await.clone();
+        Ok(settings)
+    }
+
+    async fn get_schema_version<R: Runtime>(self, window: Window<R>) -> Result<u32, ApiError> {
+        let state = window.state::<ManagedState>();
+        state.database().schema_version().map_err(ApiError::from)
+    }
+
+    async fn update_settings<R: Runtime>(
+        self,
+        window: Window<R>,
+        settings: Settings,
+    ) -> Result<Settings, ApiError> {
+        // Zero would spin the poll loop or write off every request at once
+        if settings.poll_interval_ms == 0 {
+            return Err(ApiError::InvalidInput(
+                "Poll interval must be greater than zero".to_string(),
+            ));
+        }
+        if settings.stall_timeout_ms == 0 {
+            return Err(ApiError::InvalidInput(
+                "Stall timeout must be greater than zero".to_string(),
+            ));
+        }
+
+        let state = window.state::<ManagedState>();
+        let adb_config = window.state::<AdbConfig>();
+        state.database().update_settings(&settings)?;
+        if let Ok(app_data_dir) = window.app_handle().path().app_data_dir() {
+            remember_data_dir(&state.database(), &app_data_dir, settings.data_dir.clone())?;
+        }
         state
-            .database
-            .get_metrics(&session_id, mt, start_time, end_time, limit)
-            .map_err(|e| e.to_string())
+            .database()
+            .set_metric_compression(settings.compress_metrics);
+        adb_config.set_path(settings.adb_path.clone());
+        adb_config.set_server(settings.adb_server.clone());
+        *state.settings.write().await = settings.clone();
+        Ok(settings)
     }
 
-    async fn get_session_network_requests<R: Runtime>(
+    // ============ Database Commands ============
+
+    async fn get_database_path<R: Runtime>(self, window: Window<R>) -> Result<String, ApiError> {
+        let state = window.state::<ManagedState>();
+        let database = state.database();
+        let path = database
+            .path()
+            .ok_or_else(|| ApiError::Other("The database is not backed by a file".to_string()))?;
+        Ok(path.display().to_string())
+    }
+
+    async fn open_database<R: Runtime>(
         self,
         window: Window<R>,
-        session_id: String,
-        limit: Option<u32>,
-    ) -> Result<Vec<StoredNetworkRequest>, String> {
+        path: String,
+    ) -> Result<String, ApiError> {
+        let state = window.state::<ManagedState>();
+        let adb_config = window.state::<AdbConfig>();
+        let holder = window.state::<MetricsCollectorHolder<R>>();
+        if path.trim().is_empty() {
+            return Err(ApiError::InvalidInput("Database path is empty".into()));
+        }
+
+        // Open first so a bad path leaves the current database in use
+        let database = Arc::new(Database::new(PathBuf::from(&path))?);
+
+        // Reopen this database on the next launch; the default one needs
+        // no data_dir
+        let app_data_dir = window.app_handle().path().app_data_dir()?;
+        let data_dir = (database.path() != Some(Database::get_db_path(&app_data_dir).as_path()))
+            .then(|| path.clone());
+        remember_data_dir(&database, &app_data_dir, data_dir.clone())?;
+
+        // Nothing may keep writing into the old file: stop collection and
+        // end the sessions recording there. The lock is held until the swap
+        // so no collector starts on the old database meanwhile.
+        let mut collectors = holder.collectors.write().await;
+        for (_, collector) in collectors.drain() {
+            collector.stop().await;
+        }
+        let old = state.database();
+        let ended_at = chrono::Utc::now().timestamp_millis();
+        for connection in state.connections.read().await.values() {
+            if let Some(id) = connection.session_id.write().await.take() {
+                if let Err(e) = old.end_session(&id, ended_at) {
+                    tracing::warn!("Failed to end session {} before switching: {}", id, e);
+                }
+            }
+        }
+        if let Err(e) = old.checkpoint() {
+            tracing::warn!("Failed to checkpoint the previous database: {}", e);
+        }
+
+        // Settings live in the database, so the new one's take effect
+        let mut settings = database.get_settings().unwrap_or_default();
+        settings.data_dir = data_dir;
+        database.set_metric_compression(settings.compress_metrics);
+        adb_config.set_path(settings.adb_path.clone());
+        adb_config.set_server(settings.adb_server.clone());
+        *state.settings.write().await = settings;
+
+        // The old connection closes once the last handle to it is dropped
+        *state.database.write().unwrap() = database;
+        drop(collectors);
+        tracing::info!("Opened database {}", path);
+        Ok(path)
+    }
+
+    async fn compact_database<R: Runtime>(
+        self,
+        window: Window<R>,
+    ) -> Result<CompactResult, ApiError> {
         let state = window.state::<ManagedState>();
+        state.database().compact_metrics().map_err(ApiError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_connection(session_id: &str) -> (ManagedState, Arc<Connection>) {
+        let connection = Arc::new(Connection::new("conn".into()));
+        *connection.session_id.try_write().unwrap() = Some(session_id.to_string());
+        let state = ManagedState {
+            connections: RwLock::new(HashMap::from([(connection.id.clone(), connection.clone())])),
+            default_connection: RwLock::new(Some(connection.id.clone())),
+            database: std::sync::RwLock::new(Arc::new(Database::in_memory().unwrap())),
+            settings: Arc::new(RwLock::new(Settings::default())),
+            device_watch: std::sync::Mutex::new(None),
+            forwards: std::sync::Mutex::new(HashSet::new()),
+        };
+        (state, connection)
+    }
+
+    #[tokio::test]
+    async fn create_session_without_activation_keeps_current_session() {
+        let (state, connection) = state_with_connection("recording");
+        let session = Session::new("device-1".into(), None, None, None, None);
+
+        // Without an id, an inactive session doesn't touch the default connection
+        assert!(state
+            .session_connection(None, false)
+            .await
+            .unwrap()
+            .is_none());
+
+        // An explicit connection only describes the session
+        let explicit = state.session_connection(Some("conn"), false).await.unwrap();
+        state
+            .add_session(&session, explicit.as_deref(), false)
+            .await
+            .unwrap();
+        assert_eq!(
+            connection.session_id.read().await.as_deref(),
+            Some("recording")
+        );
+        assert!(state.database().get_session(&session.id).unwrap().is_some());
+
+        let activated = Session::new("device-1".into(), None, None, None, None);
+        let default = state.session_connection(None, true).await.unwrap();
         state
-            .database
-            .get_network_requests(&session_id, limit)
-            .map_err(|e| e.to_string())
+            .add_session(&activated, default.as_deref(), true)
+            .await
+            .unwrap();
+        assert_eq!(
+            connection.session_id.read().await.as_deref(),
+            Some(activated.id.as_str())
+        );
     }
 }
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::cdp::PerformanceMetrics;
+
+/// Limits checked by the collector; unset limits are never triggered
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct AlertThresholds {
+    pub max_js_heap_bytes: Option<f64>,
+    pub max_dom_nodes: Option<f64>,
+    pub max_request_duration_ms: Option<f64>,
+}
+
+/// A threshold that was exceeded during a session
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Alert {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub session_id: String,
+    pub timestamp: i64,
+    pub kind: AlertKind,
+    /// The offending value
+    pub value: f64,
+    pub threshold: f64,
+    /// Request that ran too long, for `request_duration` alerts
+    pub request_id: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    JsHeap,
+    DomNodes,
+    RequestDuration,
+}
+
+impl AlertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertKind::JsHeap => "js_heap",
+            AlertKind::DomNodes => "dom_nodes",
+            AlertKind::RequestDuration => "request_duration",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "js_heap" => AlertKind::JsHeap,
+            "dom_nodes" => AlertKind::DomNodes,
+            _ => AlertKind::RequestDuration,
+        }
+    }
+}
+
+impl AlertThresholds {
+    /// Performance limits exceeded by a sample, as (kind, value, threshold)
+    pub fn exceeded_by(&self, metrics: &PerformanceMetrics) -> Vec<(AlertKind, f64, f64)> {
+        [
+            (
+                AlertKind::JsHeap,
+                metrics.js_heap_used_size,
+                self.max_js_heap_bytes,
+            ),
+            (AlertKind::DomNodes, metrics.dom_nodes, self.max_dom_nodes),
+        ]
+        .into_iter()
+        .filter_map(|(kind, value, threshold)| match (value, threshold) {
+            (Some(value), Some(threshold)) if value > threshold => Some((kind, value, threshold)),
+            _ => None,
+        })
+        .collect()
+    }
+
+    /// Alert for a finished request that took longer than allowed
+    pub fn check_request(
+        &self,
+        session_id: &str,
+        request_id: &str,
+        url: &str,
+        duration_ms: f64,
+    ) -> Option<Alert> {
+        let threshold = self.max_request_duration_ms?;
+        (duration_ms > threshold).then(|| Alert {
+            request_id: Some(request_id.to_string()),
+            url: Some(url.to_string()),
+            ..Alert::new(
+                session_id,
+                AlertKind::RequestDuration,
+                duration_ms,
+                threshold,
+            )
+        })
+    }
+}
+
+impl Alert {
+    pub fn new(session_id: &str, kind: AlertKind, value: f64, threshold: f64) -> Self {
+        Self {
+            id: None,
+            session_id: session_id.to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            kind,
+            value,
+            threshold,
+            request_id: None,
+            url: None,
+        }
+    }
+}
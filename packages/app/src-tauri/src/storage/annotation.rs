@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// User-placed label on a session timeline ("tapped checkout")
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Annotation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub session_id: String,
+    /// Milliseconds since epoch, like metric timestamps
+    pub timestamp: i64,
+    pub label: String,
+    /// CSS color for the marker line, e.g. "#f59e0b"
+    pub color: Option<String>,
+}
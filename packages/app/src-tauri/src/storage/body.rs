@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Response body captured during collection
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ResponseBody {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub session_id: String,
+    pub request_id: String,
+    pub url: String,
+    pub timestamp: i64,
+    /// Decoded size in bytes
+    pub size_bytes: i64,
+    /// `body` holds base64 of binary content rather than text
+    pub base64_encoded: bool,
+    pub body: String,
+}
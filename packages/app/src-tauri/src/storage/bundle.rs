@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{self, Write};
+
+use super::annotation::Annotation;
+use super::database::StorageError;
+use super::metrics::{StoredMetric, StoredNetworkRequest};
+use super::session::Session;
+
+/// Current session bundle format version
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Portable session data carried inside a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionBundleContent {
+    pub session: Session,
+    pub metrics: Vec<StoredMetric>,
+    pub network_requests: Vec<StoredNetworkRequest>,
+    /// Missing from bundles written before annotations were exported
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+/// Session bundle envelope.
+/// `content` is kept as the exact serialized string so the checksum can be
+/// verified without depending on re-serialization being byte-identical.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionBundle {
+    pub format_version: u32,
+    pub sha256: String,
+    pub content: String,
+}
+
+impl SessionBundle {
+    /// Verify version and checksum, then decode the content
+    pub fn open(&self) -> Result<SessionBundleContent, StorageError> {
+        if self.format_version != BUNDLE_FORMAT_VERSION {
+            return Err(StorageError::UnsupportedBundleVersion(self.format_version));
+        }
+        if checksum(&self.content) != self.sha256.to_lowercase() {
+            return Err(StorageError::ChecksumMismatch);
+        }
+        Ok(serde_json::from_str(&self.content)?)
+    }
+}
+
+/// Streams content into a `SessionBundle` envelope without holding the whole
+/// serialized session in memory. Bytes written are hashed as-is and escaped
+/// into the `content` string; `finish` closes the string and appends the
+/// checksum.
+pub struct BundleWriter<W: Write> {
+    out: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> BundleWriter<W> {
+    pub fn new(mut out: W) -> io::Result<Self> {
+        write!(
+            out,
+            r#"{{"format_version":{},"content":""#,
+            BUNDLE_FORMAT_VERSION
+        )?;
+        Ok(Self {
+            out,
+            hasher: Sha256::new(),
+        })
+    }
+
+    /// Close the envelope and hand back the underlying writer
+    pub fn finish(self) -> io::Result<W> {
+        let Self { mut out, hasher } = self;
+        write!(out, r#"","sha256":"{}"}}"#, hex::encode(hasher.finalize()))?;
+        out.flush()?;
+        Ok(out)
+    }
+}
+
+impl<W: Write> Write for BundleWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.hasher.update(buf);
+        // Escaping byte-wise is UTF-8 safe: multi-byte sequences never
+        // contain bytes below 0x80
+        let mut start = 0;
+        for (i, &byte) in buf.iter().enumerate() {
+            match byte {
+                b'"' | b'\\' => {
+                    self.out.write_all(&buf[start..i])?;
+                    self.out.write_all(&[b'\\', byte])?;
+                }
+                0x00..=0x1f => {
+                    self.out.write_all(&buf[start..i])?;
+                    write!(self.out, "\\u{:04x}", byte)?;
+                }
+                _ => continue,
+            }
+            start = i + 1;
+        }
+        self.out.write_all(&buf[start..])?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
+
+fn checksum(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
@@ -0,0 +1,70 @@
+use std::io::{Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use rusqlite::functions::{Context, FunctionFlags};
+use rusqlite::types::ValueRef;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// How a metrics row's `data` column is stored, recorded in its `encoding`
+/// column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricEncoding {
+    /// Plain JSON text
+    Json = 0,
+    /// Gzip-compressed JSON blob
+    Gzip = 1,
+}
+
+/// Outcome of `compact_metrics`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct CompactResult {
+    /// Rows rewritten in compressed form
+    pub rows_compressed: i64,
+    /// Size of those rows' data before compression
+    pub bytes_before: i64,
+    /// Size of the same rows after compression
+    pub bytes_after: i64,
+}
+
+/// Gzip `json`, or None when that wouldn't make it smaller (tiny rows)
+pub fn compress(json: &str) -> Option<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(json.as_bytes()).ok()?;
+    let compressed = encoder.finish().ok()?;
+    (compressed.len() < json.len()).then_some(compressed)
+}
+
+fn decompress(bytes: &[u8]) -> std::io::Result<String> {
+    let mut json = String::new();
+    GzDecoder::new(bytes).read_to_string(&mut json)?;
+    Ok(json)
+}
+
+/// Register the SQL function `metric_json(data, encoding)`, which turns a
+/// metrics row back into JSON text so `json_extract` works on compressed
+/// rows too
+pub fn register_functions(conn: &Connection) -> rusqlite::Result<()> {
+    conn.create_scalar_function(
+        "metric_json",
+        2,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        metric_json,
+    )
+}
+
+fn metric_json(ctx: &Context) -> rusqlite::Result<String> {
+    let encoding: i64 = ctx.get(1)?;
+    match ctx.get_raw(0) {
+        ValueRef::Blob(bytes) if encoding == MetricEncoding::Gzip as i64 => {
+            decompress(bytes).map_err(|e| rusqlite::Error::UserFunctionError(Box::new(e)))
+        }
+        ValueRef::Text(text) | ValueRef::Blob(text) => {
+            Ok(String::from_utf8_lossy(text).into_owned())
+        }
+        _ => Ok(String::new()),
+    }
+}
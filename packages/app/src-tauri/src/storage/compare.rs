@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Headline aggregates for one session
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SessionAggregates {
+    /// Session length in ms (last sample for sessions that never ended)
+    pub duration_ms: i64,
+    pub mean_js_heap: Option<f64>,
+    pub peak_dom_nodes: Option<f64>,
+    pub total_bytes: f64,
+    pub request_count: i64,
+    pub mean_request_duration_ms: Option<f64>,
+}
+
+impl SessionAggregates {
+    /// Requests per minute, so sessions of different lengths compare fairly
+    pub fn requests_per_minute(&self) -> Option<f64> {
+        per_minute(self.request_count as f64, self.duration_ms)
+    }
+
+    /// Bytes per minute, so sessions of different lengths compare fairly
+    pub fn bytes_per_minute(&self) -> Option<f64> {
+        per_minute(self.total_bytes, self.duration_ms)
+    }
+}
+
+fn per_minute(value: f64, duration_ms: i64) -> Option<f64> {
+    (duration_ms > 0).then(|| value / (duration_ms as f64 / 60_000.0))
+}
+
+/// Change of one aggregate from session A (baseline) to session B
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MetricDelta {
+    pub a: Option<f64>,
+    pub b: Option<f64>,
+    /// `b - a`
+    pub absolute: Option<f64>,
+    /// `(b - a) / a * 100`; None when the baseline is missing or zero
+    pub percent: Option<f64>,
+}
+
+impl MetricDelta {
+    pub fn new(a: Option<f64>, b: Option<f64>) -> Self {
+        let absolute = a.zip(b).map(|(a, b)| b - a);
+        let percent = a
+            .zip(absolute)
+            .filter(|(a, _)| *a != 0.0)
+            .map(|(a, delta)| delta / a * 100.0);
+        Self {
+            a,
+            b,
+            absolute,
+            percent,
+        }
+    }
+}
+
+/// Side-by-side comparison of two sessions; positive deltas mean B is higher
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionComparison {
+    pub session_a: String,
+    pub session_b: String,
+    pub duration_ms: MetricDelta,
+    pub mean_js_heap: MetricDelta,
+    pub peak_dom_nodes: MetricDelta,
+    pub total_bytes: MetricDelta,
+    pub request_count: MetricDelta,
+    pub mean_request_duration_ms: MetricDelta,
+    pub requests_per_minute: MetricDelta,
+    pub bytes_per_minute: MetricDelta,
+}
+
+impl SessionComparison {
+    pub fn new(
+        session_a: &str,
+        a: &SessionAggregates,
+        session_b: &str,
+        b: &SessionAggregates,
+    ) -> Self {
+        Self {
+            session_a: session_a.to_string(),
+            session_b: session_b.to_string(),
+            duration_ms: MetricDelta::new(Some(a.duration_ms as f64), Some(b.duration_ms as f64)),
+            mean_js_heap: MetricDelta::new(a.mean_js_heap, b.mean_js_heap),
+            peak_dom_nodes: MetricDelta::new(a.peak_dom_nodes, b.peak_dom_nodes),
+            total_bytes: MetricDelta::new(Some(a.total_bytes), Some(b.total_bytes)),
+            request_count: MetricDelta::new(
+                Some(a.request_count as f64),
+                Some(b.request_count as f64),
+            ),
+            mean_request_duration_ms: MetricDelta::new(
+                a.mean_request_duration_ms,
+                b.mean_request_duration_ms,
+            ),
+            requests_per_minute: MetricDelta::new(a.requests_per_minute(), b.requests_per_minute()),
+            bytes_per_minute: MetricDelta::new(a.bytes_per_minute(), b.bytes_per_minute()),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::cdp::ConsoleLevel;
+
+/// Console message or browser log entry from the page
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ConsoleLog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub session_id: String,
+    pub timestamp: i64,
+    pub level: ConsoleLevel,
+    pub text: String,
+    /// Script or document that logged the message
+    pub url: Option<String>,
+    /// 0-based line in `url`
+    pub line: Option<i64>,
+}
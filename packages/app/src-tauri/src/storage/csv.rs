@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::database::StorageError;
+use super::metrics::{StoredMetric, StoredNetworkRequest};
+use crate::cdp::PerformanceMetrics;
+
+/// Which session data `export_session_csv` writes
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum CsvExportKind {
+    /// Performance samples, one column per `PerformanceMetrics` field
+    Metrics,
+    /// One row per network request
+    Network,
+}
+
+const NETWORK_COLUMNS: [&str; 19] = [
+    "id",
+    "session_id",
+    "url",
+    "method",
+    "status_code",
+    "request_time",
+    "response_time",
+    "duration_ms",
+    "size_bytes",
+    "decoded_size_bytes",
+    "headers",
+    "initiator_type",
+    "initiator_url",
+    "initiator_line",
+    "initiator_function",
+    "priority",
+    "mime_type",
+    "resource_type",
+    "failed",
+];
+
+/// Performance samples as CSV. The header comes from the fields of
+/// `PerformanceMetrics` (timestamp first), so it is the same for every
+/// session even if a field was never reported.
+pub fn metrics_to_csv(metrics: &[StoredMetric]) -> Result<String, StorageError> {
+    let template = serde_json::to_value(PerformanceMetrics::default())?;
+    let mut columns = vec!["timestamp".to_string()];
+    if let Some(fields) = template.as_object() {
+        columns.extend(fields.keys().filter(|k| *k != "timestamp").cloned());
+    }
+
+    let mut out = String::new();
+    write_row(&mut out, columns.iter().map(String::as_str));
+
+    for metric in metrics {
+        let value: serde_json::Value = serde_json::from_str(&metric.data)?;
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| match &value[column.as_str()] {
+                _ if column == "timestamp" => metric.timestamp.to_string(),
+                serde_json::Value::Null => String::new(),
+                other => other.to_string(),
+            })
+            .collect();
+        write_row(&mut out, cells.iter().map(String::as_str));
+    }
+
+    Ok(out)
+}
+
+/// Network requests as CSV, with headers serialized as a JSON object
+pub fn network_to_csv(requests: &[StoredNetworkRequest]) -> Result<String, StorageError> {
+    let mut out = String::new();
+    write_row(&mut out, NETWORK_COLUMNS);
+
+    for request in requests {
+        let headers = request
+            .headers
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let cells = [
+            request.id.clone(),
+            request.session_id.clone(),
+            request.url.clone(),
+            opt(&request.method),
+            opt(&request.status_code),
+            request.request_time.to_string(),
+            opt(&request.response_time),
+            opt(&request.duration_ms),
+            opt(&request.size_bytes),
+            opt(&request.decoded_size_bytes),
+            headers.unwrap_or_default(),
+            opt(&request.initiator_type),
+            opt(&request.initiator_url),
+            opt(&request.initiator_line),
+            opt(&request.initiator_function),
+            opt(&request.priority),
+            opt(&request.mime_type),
+            opt(&request.resource_type),
+            request.failed.to_string(),
+        ];
+        write_row(&mut out, cells.iter().map(String::as_str));
+    }
+
+    Ok(out)
+}
+
+fn opt<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(T::to_string).unwrap_or_default()
+}
+
+/// Append one CRLF-terminated record, escaping fields per RFC 4180
+fn write_row<'a>(out: &mut String, fields: impl IntoIterator<Item = &'a str>) {
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if field.contains([',', '"', '\r', '\n']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(field);
+        }
+    }
+    out.push_str("\r\n");
+}
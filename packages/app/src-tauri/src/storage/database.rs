@@ -1,10 +1,39 @@
-use rusqlite::{params, Connection};
+use rusqlite::types::Value;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use thiserror::Error;
 
-use super::metrics::{MetricType, StoredMetric, StoredNetworkRequest};
-use super::session::{Session, SessionStatus};
+use super::alert::{Alert, AlertKind};
+use super::annotation::Annotation;
+use super::body::ResponseBody;
+use super::bundle::{BundleWriter, SessionBundle};
+use super::codec::{self, CompactResult, MetricEncoding};
+use super::compare::{SessionAggregates, SessionComparison};
+use super::console::ConsoleLog;
+use super::csv::{metrics_to_csv, network_to_csv, CsvExportKind};
+use super::downsample::downsample_metrics;
+use super::har::Har;
+use super::marker::{MarkerKind, SessionMarker};
+use super::metrics::{
+    DuplicateRequestGroup, HostNetworkSummary, MetricBucketStats, MetricType, MetricsSummary,
+    NetworkRequestCursor, NetworkRequestFilter, NetworkRequestPage, NetworkSummary,
+    RequestDataChunk, ResourceTypeBytes, StatusClass, StoredMetric, StoredNetworkRequest,
+    ThroughputBucket,
+};
+use super::migrations::MIGRATIONS;
+use super::normalize::UrlNormalizer;
+use super::preset::TestPreset;
+use super::security::SecurityEvent;
+use super::session::{
+    BatchResult, PruneResult, Session, SessionCounts, SessionSearchFilter, SessionStatus, TagCount,
+};
+use super::settings::Settings;
+use super::smoothing::{smooth, SmoothedSeries, SmoothingOptions};
+use crate::cdp::ConsoleLevel;
 
 #[derive(Error, Debug)]
 pub enum StorageError {
@@ -16,10 +45,32 @@ pub enum StorageError {
     Io(#[from] std::io::Error),
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+    #[error("Unsupported bundle format version: {0}")]
+    UnsupportedBundleVersion(u32),
+    #[error("Bundle checksum mismatch: the file is truncated or was modified")]
+    ChecksumMismatch,
+    #[error("Preset not found: {0}")]
+    PresetNotFound(String),
+    #[error("Annotation not found: {0}")]
+    AnnotationNotFound(i64),
+    #[error("Response body is {size} bytes, over the {max} byte cap")]
+    BodyTooLarge { size: i64, max: i64 },
 }
 
+/// Rows read per query while streaming a bundle, so the connection lock is
+/// released between pages and the collector can keep writing
+const BUNDLE_PAGE_SIZE: i64 = 2000;
+
+/// Most windows `network_throughput` returns; wider windows are used when
+/// the session is too long for the requested size
+const MAX_THROUGHPUT_BUCKETS: i64 = 10_000;
+
 pub struct Database {
     conn: Mutex<Connection>,
+    /// File backing the connection; None for in-memory databases
+    path: Option<PathBuf>,
+    /// Gzip new metric rows (see `set_metric_compression`)
+    compress_metrics: AtomicBool,
 }
 
 impl Database {
@@ -33,6 +84,8 @@ impl Database {
         let conn = Connection::open(&db_path)?;
         let db = Self {
             conn: Mutex::new(conn),
+            path: Some(db_path),
+            compress_metrics: AtomicBool::new(false),
         };
         db.initialize()?;
         Ok(db)
@@ -44,17 +97,37 @@ impl Database {
         let conn = Connection::open_in_memory()?;
         let db = Self {
             conn: Mutex::new(conn),
+            path: None,
+            compress_metrics: AtomicBool::new(false),
         };
         db.initialize()?;
         Ok(db)
     }
 
+    /// File this database was opened from
+    pub fn path(&self) -> Option<&std::path::Path> {
+        self.path.as_deref()
+    }
+
+    /// Gzip the data of metrics stored from now on. Reads handle both
+    /// forms, so this can change at any time.
+    pub fn set_metric_compression(&self, enabled: bool) {
+        self.compress_metrics.store(enabled, Ordering::Relaxed);
+    }
+
     /// Initialize database schema
     fn initialize(&self) -> Result<(), StorageError> {
+        codec::register_functions(&self.conn.lock().unwrap())?;
+        self.create_tables()?;
+        self.migrate()
+    }
+
+    /// Create any missing tables with their current columns
+    fn create_tables(&self) -> Result<(), StorageError> {
         let conn = self.conn.lock().unwrap();
 
         // Enable foreign keys
-        conn.execute("PRAGMA foreign_keys = ON", [])?;
+        Self::ensure_foreign_keys(&conn)?;
 
         // Create sessions table
         conn.execute(
@@ -70,15 +143,12 @@ impl Database {
                 status TEXT NOT NULL DEFAULT 'active',
                 display_name TEXT,
                 tags TEXT,
-                metadata TEXT
+                metadata TEXT,
+                preset_name TEXT
             )",
             [],
         )?;
 
-        // Migration: Add display_name and tags columns if they don't exist
-        let _ = conn.execute("ALTER TABLE sessions ADD COLUMN display_name TEXT", []);
-        let _ = conn.execute("ALTER TABLE sessions ADD COLUMN tags TEXT", []);
-
         // Create metrics table (time-series data)
         conn.execute(
             "CREATE TABLE IF NOT EXISTS metrics (
@@ -86,7 +156,8 @@ impl Database {
                 session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
                 timestamp INTEGER NOT NULL,
                 metric_type TEXT NOT NULL,
-                data TEXT NOT NULL
+                data TEXT NOT NULL,
+                encoding INTEGER NOT NULL DEFAULT 0
             )",
             [],
         )?;
@@ -110,7 +181,19 @@ impl Database {
                 response_time INTEGER,
                 duration_ms REAL,
                 size_bytes REAL,
-                headers TEXT
+                headers TEXT,
+                initiator_type TEXT,
+                initiator_url TEXT,
+                priority TEXT,
+                mime_type TEXT,
+                resource_type TEXT,
+                failed INTEGER NOT NULL DEFAULT 0,
+                initiator_line INTEGER,
+                initiator_function TEXT,
+                decoded_size_bytes REAL,
+                timing TEXT,
+                error_text TEXT,
+                wall_time INTEGER
             )",
             [],
         )?;
@@ -122,6 +205,239 @@ impl Database {
             [],
         )?;
 
+        // Create request_data_chunks table (per-request transfer progress)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS request_data_chunks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+                request_id TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                data_length INTEGER NOT NULL,
+                encoded_data_length INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_data_chunks_request
+             ON request_data_chunks(request_id, timestamp)",
+            [],
+        )?;
+
+        // Create session_markers table (collection state transitions)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS session_markers (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+                timestamp INTEGER NOT NULL,
+                kind TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_markers_session_time
+             ON session_markers(session_id, timestamp)",
+            [],
+        )?;
+
+        // Create security_events table (page security state changes)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS security_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+                timestamp INTEGER NOT NULL,
+                state TEXT NOT NULL,
+                summary TEXT
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_security_session_time
+             ON security_events(session_id, timestamp)",
+            [],
+        )?;
+
+        // Create alerts table (thresholds exceeded during collection)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS alerts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+                timestamp INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                value REAL NOT NULL,
+                threshold REAL NOT NULL,
+                request_id TEXT,
+                url TEXT
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_alerts_session_time
+             ON alerts(session_id, timestamp)",
+            [],
+        )?;
+
+        // Create console_logs table (page console output, capped per session)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS console_logs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+                timestamp INTEGER NOT NULL,
+                level TEXT NOT NULL,
+                text TEXT NOT NULL,
+                url TEXT,
+                line INTEGER
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_console_session_time
+             ON console_logs(session_id, timestamp)",
+            [],
+        )?;
+
+        // Create annotations table (user-placed timeline labels)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS annotations (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+                timestamp INTEGER NOT NULL,
+                label TEXT NOT NULL,
+                color TEXT
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_annotations_session_time
+             ON annotations(session_id, timestamp)",
+            [],
+        )?;
+
+        // Create response_bodies table (opt-in body capture)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS response_bodies (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+                request_id TEXT NOT NULL,
+                url TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                size_bytes INTEGER NOT NULL,
+                base64_encoded INTEGER NOT NULL,
+                body TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_response_bodies_session_request
+             ON response_bodies(session_id, request_id)",
+            [],
+        )?;
+
+        // Create settings table (one JSON value per key)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create presets table (named emulation settings)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS presets (
+                name TEXT PRIMARY KEY,
+                config TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Apply pending migrations in order, each in its own transaction, and
+    /// record them in `schema_version`. Safe to call repeatedly.
+    pub fn migrate(&self) -> Result<(), StorageError> {
+        let mut conn = self.conn.lock().unwrap();
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS schema_version (
+                version INTEGER PRIMARY KEY,
+                description TEXT NOT NULL,
+                applied_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        let current = Self::current_schema_version(&conn)?;
+
+        for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+            let tx = conn.transaction()?;
+            for (table, column, definition) in migration.columns {
+                let exists: bool = tx.query_row(
+                    "SELECT COUNT(*) > 0 FROM pragma_table_info(?1) WHERE name = ?2",
+                    params![table, column],
+                    |row| row.get(0),
+                )?;
+                if !exists {
+                    tx.execute(
+                        &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+                        [],
+                    )?;
+                }
+            }
+            tx.execute(
+                "INSERT INTO schema_version (version, description, applied_at)
+                 VALUES (?1, ?2, ?3)",
+                params![
+                    migration.version,
+                    migration.description,
+                    chrono::Utc::now().timestamp_millis()
+                ],
+            )?;
+            tx.commit()?;
+            tracing::info!(
+                "Applied schema migration {}: {}",
+                migration.version,
+                migration.description
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Highest applied migration version (0 for a database never migrated)
+    pub fn schema_version(&self) -> Result<u32, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        Self::current_schema_version(&conn)
+    }
+
+    fn current_schema_version(conn: &Connection) -> Result<u32, StorageError> {
+        Ok(conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+            [],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// `PRAGMA foreign_keys` is per connection and off by default in SQLite;
+    /// every cascading delete depends on it, so check rather than assume
+    fn ensure_foreign_keys(conn: &Connection) -> Result<(), StorageError> {
+        let enabled: bool = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0))?;
+        if !enabled {
+            conn.execute_batch("PRAGMA foreign_keys = ON")?;
+        }
+        Ok(())
+    }
+
+    /// Flush the write-ahead log into the main database file
+    pub fn checkpoint(&self) -> Result<(), StorageError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
         Ok(())
     }
 
@@ -130,6 +446,10 @@ impl Database {
     /// Create a new session
     pub fn create_session(&self, session: &Session) -> Result<(), StorageError> {
         let conn = self.conn.lock().unwrap();
+        Self::insert_session(&conn, session)
+    }
+
+    fn insert_session(conn: &Connection, session: &Session) -> Result<(), StorageError> {
         let metadata_json = session
             .metadata
             .as_ref()
@@ -143,8 +463,9 @@ impl Database {
 
         conn.execute(
             "INSERT INTO sessions (id, device_id, device_name, webview_url, package_name,
-                                   target_title, started_at, ended_at, status, display_name, tags, metadata)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                                   target_title, started_at, ended_at, status, display_name, tags, metadata,
+                                   preset_name)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
             params![
                 session.id,
                 session.device_id,
@@ -158,25 +479,64 @@ impl Database {
                 session.display_name,
                 tags_json,
                 metadata_json,
+                session.preset_name,
             ],
         )?;
 
         Ok(())
     }
 
-    /// End a session
-    pub fn end_session(&self, session_id: &str, ended_at: i64) -> Result<(), StorageError> {
+    /// End a session.
+    /// Only active sessions transition; ending an already completed/aborted
+    /// session is a no-op that preserves the original `ended_at`.
+    /// Returns whether the session was actually ended by this call.
+    pub fn end_session(&self, session_id: &str, ended_at: i64) -> Result<bool, StorageError> {
+        self.finish_session(session_id, ended_at, SessionStatus::Completed, None)
+    }
+
+    /// Mark a session as aborted (device disconnect, user cancellation).
+    /// Same transition rules as `end_session`; the reason, if any, is kept
+    /// in metadata under `abort_reason`.
+    pub fn abort_session(
+        &self,
+        session_id: &str,
+        ended_at: i64,
+        reason: Option<&str>,
+    ) -> Result<bool, StorageError> {
+        self.finish_session(session_id, ended_at, SessionStatus::Aborted, reason)
+    }
+
+    fn finish_session(
+        &self,
+        session_id: &str,
+        ended_at: i64,
+        status: SessionStatus,
+        abort_reason: Option<&str>,
+    ) -> Result<bool, StorageError> {
         let conn = self.conn.lock().unwrap();
         let rows = conn.execute(
-            "UPDATE sessions SET ended_at = ?1, status = 'completed' WHERE id = ?2",
-            params![ended_at, session_id],
+            "UPDATE sessions SET ended_at = ?1, status = ?2,
+                    metadata = CASE WHEN ?4 IS NULL THEN metadata
+                               ELSE json_set(COALESCE(metadata, '{}'), '$.abort_reason', ?4) END
+             WHERE id = ?3 AND status = 'active'",
+            params![ended_at, status.as_str(), session_id, abort_reason],
         )?;
 
-        if rows == 0 {
+        if rows > 0 {
+            return Ok(true);
+        }
+
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM sessions WHERE id = ?1)",
+            params![session_id],
+            |row| row.get(0),
+        )?;
+
+        if !exists {
             return Err(StorageError::SessionNotFound(session_id.to_string()));
         }
 
-        Ok(())
+        Ok(false)
     }
 
     /// Get a session by ID
@@ -184,7 +544,8 @@ impl Database {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
             "SELECT id, device_id, device_name, webview_url, package_name,
-                    target_title, started_at, ended_at, status, display_name, tags, metadata
+                    target_title, started_at, ended_at, status, display_name, tags, metadata,
+                    preset_name
              FROM sessions WHERE id = ?1",
         )?;
 
@@ -203,16 +564,29 @@ impl Database {
         let limit_clause = limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
         let query = format!(
             "SELECT id, device_id, device_name, webview_url, package_name,
-                    target_title, started_at, ended_at, status, display_name, tags, metadata
+                    target_title, started_at, ended_at, status, display_name, tags, metadata,
+                    preset_name
              FROM sessions ORDER BY started_at DESC{}",
             limit_clause
         );
 
         let mut stmt = conn.prepare(&query)?;
-        let rows = stmt.query_map([], |row| Ok(Self::row_to_session(row).unwrap()))?;
+        let sessions = Self::collect_sessions(stmt.query([])?)?;
+        Ok(sessions)
+    }
 
-        let sessions: Result<Vec<_>, _> = rows.collect();
-        Ok(sessions?)
+    /// Sessions still marked active, newest first. Outside a run these were
+    /// left behind by a crash or a kill before `end_session`.
+    pub fn get_active_sessions(&self) -> Result<Vec<Session>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, device_id, device_name, webview_url, package_name,
+                    target_title, started_at, ended_at, status, display_name, tags, metadata,
+                    preset_name
+             FROM sessions WHERE status = 'active' ORDER BY started_at DESC",
+        )?;
+        let sessions = Self::collect_sessions(stmt.query([])?)?;
+        Ok(sessions)
     }
 
     /// Delete a session and all related data
@@ -227,6 +601,25 @@ impl Database {
         Ok(())
     }
 
+    /// Delete several sessions in one transaction
+    pub fn delete_sessions(&self, session_ids: &[String]) -> Result<BatchResult, StorageError> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let mut result = BatchResult::default();
+
+        for session_id in session_ids {
+            let rows = tx.execute("DELETE FROM sessions WHERE id = ?1", params![session_id])?;
+            if rows == 0 {
+                result.failed.push(session_id.clone());
+            } else {
+                result.succeeded.push(session_id.clone());
+            }
+        }
+        tx.commit()?;
+
+        Ok(result)
+    }
+
     /// Update session display name
     pub fn update_session_name(
         &self,
@@ -246,6 +639,47 @@ impl Database {
         Ok(())
     }
 
+    /// Set the URL and title of the page a session records
+    pub fn update_session_target(
+        &self,
+        session_id: &str,
+        webview_url: Option<&str>,
+        target_title: Option<&str>,
+    ) -> Result<(), StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let rows = conn.execute(
+            "UPDATE sessions SET webview_url = ?1, target_title = ?2 WHERE id = ?3",
+            params![webview_url, target_title, session_id],
+        )?;
+
+        if rows == 0 {
+            return Err(StorageError::SessionNotFound(session_id.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Replace session metadata (None clears it)
+    pub fn update_session_metadata(
+        &self,
+        session_id: &str,
+        metadata: Option<&HashMap<String, serde_json::Value>>,
+    ) -> Result<(), StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let metadata_json = metadata.map(serde_json::to_string).transpose()?;
+
+        let rows = conn.execute(
+            "UPDATE sessions SET metadata = ?1 WHERE id = ?2",
+            params![metadata_json, session_id],
+        )?;
+
+        if rows == 0 {
+            return Err(StorageError::SessionNotFound(session_id.to_string()));
+        }
+
+        Ok(())
+    }
+
     /// Update session tags
     pub fn update_session_tags(
         &self,
@@ -267,16 +701,128 @@ impl Database {
         Ok(())
     }
 
-    /// Search sessions with filters
-    pub fn search_sessions(
+    /// Every tag in use with the number of sessions carrying it, most used
+    /// first (ties alphabetical). Tags are JSON arrays, so they are
+    /// flattened here rather than in SQL.
+    pub fn list_all_tags(&self) -> Result<Vec<TagCount>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT tags FROM sessions WHERE tags IS NOT NULL")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+
+        let mut counts: HashMap<String, i64> = HashMap::new();
+        for tags_json in rows {
+            let mut tags: Vec<String> = serde_json::from_str(&tags_json?).unwrap_or_default();
+            // A session listing a tag twice still counts once
+            tags.sort();
+            tags.dedup();
+            for tag in tags {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+
+        let mut tags: Vec<TagCount> = counts
+            .into_iter()
+            .map(|(tag, count)| TagCount { tag, count })
+            .collect();
+        tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        Ok(tags)
+    }
+
+    /// Add a tag to several sessions, keeping their existing tags
+    pub fn add_tag_to_sessions(
         &self,
-        query: Option<&str>,
-        device_id: Option<&str>,
-        status: Option<&str>,
-        tags: Option<&[String]>,
-        limit: Option<u32>,
-    ) -> Result<Vec<Session>, StorageError> {
+        session_ids: &[String],
+        tag: &str,
+    ) -> Result<BatchResult, StorageError> {
+        self.modify_session_tags(session_ids, |tags| {
+            if !tags.iter().any(|t| t == tag) {
+                tags.push(tag.to_string());
+            }
+        })
+    }
+
+    /// Remove a tag from several sessions, keeping their other tags
+    pub fn remove_tag_from_sessions(
+        &self,
+        session_ids: &[String],
+        tag: &str,
+    ) -> Result<BatchResult, StorageError> {
+        self.modify_session_tags(session_ids, |tags| tags.retain(|t| t != tag))
+    }
+
+    /// Read-modify-write the tags of each session inside one transaction.
+    /// An emptied tag list is stored as NULL, matching an untagged session.
+    fn modify_session_tags(
+        &self,
+        session_ids: &[String],
+        modify: impl Fn(&mut Vec<String>),
+    ) -> Result<BatchResult, StorageError> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let mut result = BatchResult::default();
+
+        for session_id in session_ids {
+            let existing: Option<Option<String>> = tx
+                .query_row(
+                    "SELECT tags FROM sessions WHERE id = ?1",
+                    params![session_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let Some(tags_json) = existing else {
+                result.failed.push(session_id.clone());
+                continue;
+            };
+
+            let mut tags: Vec<String> = tags_json
+                .map(|s| serde_json::from_str(&s))
+                .transpose()?
+                .unwrap_or_default();
+            modify(&mut tags);
+            let tags_json = if tags.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_string(&tags)?)
+            };
+
+            tx.execute(
+                "UPDATE sessions SET tags = ?1 WHERE id = ?2",
+                params![tags_json, session_id],
+            )?;
+            result.succeeded.push(session_id.clone());
+        }
+        tx.commit()?;
+
+        Ok(result)
+    }
+
+    /// Record the test preset applied to a session
+    pub fn set_session_preset(
+        &self,
+        session_id: &str,
+        preset_name: &str,
+    ) -> Result<(), StorageError> {
         let conn = self.conn.lock().unwrap();
+        let rows = conn.execute(
+            "UPDATE sessions SET preset_name = ?1 WHERE id = ?2",
+            params![preset_name, session_id],
+        )?;
+
+        if rows == 0 {
+            return Err(StorageError::SessionNotFound(session_id.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Build the WHERE clause and bound parameters for a session search
+    fn session_search_clause(
+        filter: &SessionSearchFilter,
+    ) -> (String, Vec<Box<dyn rusqlite::ToSql>>) {
+        let query = filter.query.as_deref();
+        let device_id = filter.device_id.as_deref();
+        let status = filter.status.as_deref();
+        let tags = filter.tags.as_deref();
 
         let mut conditions = Vec::new();
         let mut param_idx = 1;
@@ -296,6 +842,25 @@ impl Database {
             conditions.push(format!("status = ?{}", param_idx));
             param_idx += 1;
         }
+        if filter.started_after.is_some() {
+            conditions.push(format!("started_at >= ?{}", param_idx));
+            param_idx += 1;
+        }
+        if filter.started_before.is_some() {
+            conditions.push(format!("started_at <= ?{}", param_idx));
+            param_idx += 1;
+        }
+        if filter.min_duration_ms.is_some() || filter.max_duration_ms.is_some() {
+            conditions.push("ended_at IS NOT NULL".to_string());
+        }
+        if filter.min_duration_ms.is_some() {
+            conditions.push(format!("ended_at - started_at >= ?{}", param_idx));
+            param_idx += 1;
+        }
+        if filter.max_duration_ms.is_some() {
+            conditions.push(format!("ended_at - started_at <= ?{}", param_idx));
+            param_idx += 1;
+        }
         if let Some(tag_list) = tags {
             // Check if any of the tags match (JSON array contains)
             let tag_conditions: Vec<String> = tag_list
@@ -314,17 +879,6 @@ impl Database {
             format!(" WHERE {}", conditions.join(" AND "))
         };
 
-        let limit_clause = limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
-        let sql = format!(
-            "SELECT id, device_id, device_name, webview_url, package_name,
-                    target_title, started_at, ended_at, status, display_name, tags, metadata
-             FROM sessions{}
-             ORDER BY started_at DESC{}",
-            where_clause, limit_clause
-        );
-
-        let mut stmt = conn.prepare(&sql)?;
-
         // Build dynamic params
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
         if let Some(q) = query {
@@ -336,21 +890,99 @@ impl Database {
         if let Some(s) = status {
             params_vec.push(Box::new(s.to_string()));
         }
+        if let Some(after) = filter.started_after {
+            params_vec.push(Box::new(after));
+        }
+        if let Some(before) = filter.started_before {
+            params_vec.push(Box::new(before));
+        }
+        if let Some(min) = filter.min_duration_ms {
+            params_vec.push(Box::new(min));
+        }
+        if let Some(max) = filter.max_duration_ms {
+            params_vec.push(Box::new(max));
+        }
         if let Some(tag_list) = tags {
             for tag in tag_list {
                 params_vec.push(Box::new(format!("%\"{}\"", tag)));
             }
         }
 
-        let params_refs: Vec<&dyn rusqlite::ToSql> =
-            params_vec.iter().map(|p| p.as_ref()).collect();
+        (where_clause, params_vec)
+    }
 
-        let rows = stmt.query_map(params_refs.as_slice(), |row| {
-            Ok(Self::row_to_session(row).unwrap())
-        })?;
+    /// Search sessions with filters
+    pub fn search_sessions(
+        &self,
+        filter: &SessionSearchFilter,
+    ) -> Result<Vec<Session>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let (where_clause, params_vec) = Self::session_search_clause(filter);
+
+        let limit_clause = filter
+            .limit
+            .map(|l| format!(" LIMIT {}", l))
+            .unwrap_or_default();
+        let sql = format!(
+            "SELECT id, device_id, device_name, webview_url, package_name,
+                    target_title, started_at, ended_at, status, display_name, tags, metadata,
+                    preset_name
+             FROM sessions{}
+             ORDER BY started_at DESC{}",
+            where_clause, limit_clause
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+
+        let sessions = Self::collect_sessions(stmt.query(params_refs.as_slice())?)?;
+        Ok(sessions)
+    }
+
+    /// Count sessions matching a search filter, ignoring its limit
+    pub fn count_sessions(&self, filter: &SessionSearchFilter) -> Result<i64, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let (where_clause, params_vec) = Self::session_search_clause(filter);
+        let sql = format!("SELECT COUNT(*) FROM sessions{}", where_clause);
+
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
 
-        let sessions: Result<Vec<_>, _> = rows.collect();
-        Ok(sessions?)
+        let count = conn.query_row(&sql, params_refs.as_slice(), |row| row.get(0))?;
+        Ok(count)
+    }
+
+    /// Count stored rows belonging to a session
+    pub fn get_session_counts(&self, session_id: &str) -> Result<SessionCounts, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let counts = conn.query_row(
+            "SELECT
+                (SELECT COUNT(*) FROM metrics WHERE session_id = ?1),
+                (SELECT COUNT(*) FROM network_requests WHERE session_id = ?1),
+                (SELECT COUNT(*) FROM session_markers WHERE session_id = ?1)",
+            params![session_id],
+            |row| {
+                Ok(SessionCounts {
+                    metric_count: row.get(0)?,
+                    network_request_count: row.get(1)?,
+                    marker_count: row.get(2)?,
+                })
+            },
+        )?;
+
+        Ok(counts)
+    }
+
+    /// Read every row of a session query. Not `query_map`: decoding tags and
+    /// metadata can fail with a JSON error, not just a SQLite one.
+    fn collect_sessions(mut rows: rusqlite::Rows) -> Result<Vec<Session>, StorageError> {
+        let mut sessions = Vec::new();
+        while let Some(row) = rows.next()? {
+            sessions.push(Self::row_to_session(row)?);
+        }
+        Ok(sessions)
     }
 
     fn row_to_session(row: &rusqlite::Row) -> Result<Session, StorageError> {
@@ -371,6 +1003,7 @@ impl Database {
             status: SessionStatus::from_str(&status_str),
             display_name,
             tags: tags_json.map(|s| serde_json::from_str(&s)).transpose()?,
+            preset_name: row.get(12)?,
             metadata: metadata_json
                 .map(|s| serde_json::from_str(&s))
                 .transpose()?,
@@ -382,37 +1015,144 @@ impl Database {
     /// Store a performance metric
     pub fn store_metric(&self, metric: &StoredMetric) -> Result<i64, StorageError> {
         let conn = self.conn.lock().unwrap();
+        Self::insert_metric(&conn, metric, self.compress_metrics.load(Ordering::Relaxed))
+    }
+
+    fn insert_metric(
+        conn: &Connection,
+        metric: &StoredMetric,
+        compress: bool,
+    ) -> Result<i64, StorageError> {
+        let compressed = compress.then(|| codec::compress(&metric.data)).flatten();
+        let (data, encoding) = match compressed {
+            Some(bytes) => (Value::Blob(bytes), MetricEncoding::Gzip),
+            None => (Value::Text(metric.data.clone()), MetricEncoding::Json),
+        };
         conn.execute(
-            "INSERT INTO metrics (session_id, timestamp, metric_type, data)
-             VALUES (?1, ?2, ?3, ?4)",
+            "INSERT INTO metrics (session_id, timestamp, metric_type, data, encoding)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
             params![
                 metric.session_id,
                 metric.timestamp,
                 metric.metric_type.as_str(),
-                metric.data,
+                data,
+                encoding as i64,
             ],
         )?;
 
         Ok(conn.last_insert_rowid())
     }
 
+    /// Gzip the data of every uncompressed metrics row where that saves
+    /// space, then `VACUUM` to hand the freed pages back to the filesystem.
+    /// Works page by page so collection can keep writing meanwhile.
+    pub fn compact_metrics(&self) -> Result<CompactResult, StorageError> {
+        let mut result = CompactResult::default();
+        let mut after_id = 0;
+        loop {
+            let mut conn = self.conn.lock().unwrap();
+            let page: Vec<(i64, String)> = {
+                let mut stmt = conn.prepare(
+                    "SELECT id, data FROM metrics
+                     WHERE encoding = ?1 AND id > ?2 ORDER BY id ASC LIMIT ?3",
+                )?;
+                let rows = stmt.query_map(
+                    params![MetricEncoding::Json as i64, after_id, BUNDLE_PAGE_SIZE],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )?;
+                rows.collect::<Result<_, _>>()?
+            };
+            let Some((last_id, _)) = page.last() else {
+                break;
+            };
+            after_id = *last_id;
+
+            let tx = conn.transaction()?;
+            for (id, json) in &page {
+                let Some(compressed) = codec::compress(json) else {
+                    continue;
+                };
+                tx.execute(
+                    "UPDATE metrics SET data = ?1, encoding = ?2 WHERE id = ?3",
+                    params![compressed, MetricEncoding::Gzip as i64, id],
+                )?;
+                result.rows_compressed += 1;
+                result.bytes_before += json.len() as i64;
+                result.bytes_after += compressed.len() as i64;
+            }
+            tx.commit()?;
+        }
+
+        self.vacuum()?;
+        Ok(result)
+    }
+
+    /// Keep only the most recent `keep_last_n` metric samples for a session.
+    /// Deletes by id range so the trim is a single indexed delete.
+    pub fn prune_metrics(&self, session_id: &str, keep_last_n: u32) -> Result<usize, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let threshold: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM metrics WHERE session_id = ?1
+                 ORDER BY id DESC LIMIT 1 OFFSET ?2",
+                params![session_id, keep_last_n],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let Some(threshold) = threshold else {
+            return Ok(0);
+        };
+
+        let deleted = conn.execute(
+            "DELETE FROM metrics WHERE session_id = ?1 AND id <= ?2",
+            params![session_id, threshold],
+        )?;
+
+        Ok(deleted)
+    }
+
+    /// `get_metrics`, with each metric type's series reduced to at most
+    /// `max_points` rows by LTTB on its primary field, for charting
+    pub fn get_metrics_downsampled(
+        &self,
+        session_id: &str,
+        metric_types: Option<&[MetricType]>,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: Option<u32>,
+        max_points: Option<u32>,
+    ) -> Result<Vec<StoredMetric>, StorageError> {
+        let metrics = self.get_metrics(session_id, metric_types, start_time, end_time, limit)?;
+        Ok(match max_points {
+            Some(max_points) => downsample_metrics(metrics, max_points as usize),
+            None => metrics,
+        })
+    }
+
     /// Get metrics for a session
     pub fn get_metrics(
         &self,
         session_id: &str,
-        metric_type: Option<MetricType>,
+        metric_types: Option<&[MetricType]>,
         start_time: Option<i64>,
         end_time: Option<i64>,
         limit: Option<u32>,
     ) -> Result<Vec<StoredMetric>, StorageError> {
         let conn = self.conn.lock().unwrap();
 
+        // An empty type list means no type filter
+        let metric_types = metric_types.filter(|types| !types.is_empty());
+
         let mut conditions = vec!["session_id = ?1".to_string()];
         let mut param_idx = 2;
 
-        if metric_type.is_some() {
-            conditions.push(format!("metric_type = ?{}", param_idx));
-            param_idx += 1;
+        if let Some(types) = metric_types {
+            let placeholders: Vec<String> = (0..types.len())
+                .map(|i| format!("?{}", param_idx + i))
+                .collect();
+            conditions.push(format!("metric_type IN ({})", placeholders.join(", ")));
+            param_idx += types.len();
         }
         if start_time.is_some() {
             conditions.push(format!("timestamp >= ?{}", param_idx));
@@ -424,8 +1164,8 @@ impl Database {
 
         let limit_clause = limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
         let query = format!(
-            "SELECT id, session_id, timestamp, metric_type, data
-             FROM metrics WHERE {} ORDER BY timestamp ASC{}",
+            "SELECT id, session_id, timestamp, metric_type, metric_json(data, encoding) AS data
+             FROM metrics WHERE {} ORDER BY timestamp ASC, id ASC{}",
             conditions.join(" AND "),
             limit_clause
         );
@@ -434,8 +1174,10 @@ impl Database {
 
         // Build dynamic params
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(session_id.to_string())];
-        if let Some(ref mt) = metric_type {
-            params_vec.push(Box::new(mt.as_str().to_string()));
+        if let Some(types) = metric_types {
+            for mt in types {
+                params_vec.push(Box::new(mt.as_str().to_string()));
+            }
         }
         if let Some(st) = start_time {
             params_vec.push(Box::new(st));
@@ -447,21 +1189,173 @@ impl Database {
         let params_refs: Vec<&dyn rusqlite::ToSql> =
             params_vec.iter().map(|p| p.as_ref()).collect();
 
-        let rows = stmt.query_map(params_refs.as_slice(), |row| {
-            let type_str: String = row.get(3)?;
-            Ok(StoredMetric {
-                id: Some(row.get(0)?),
-                session_id: row.get(1)?,
-                timestamp: row.get(2)?,
-                metric_type: MetricType::from_str(&type_str),
-                data: row.get(4)?,
-            })
-        })?;
+        let rows = stmt.query_map(params_refs.as_slice(), Self::row_to_metric)?;
+
+        let metrics: Result<Vec<_>, _> = rows.collect();
+        Ok(metrics?)
+    }
+
+    /// Summarize a numeric field of a session's metrics (e.g.
+    /// `js_heap_used_size`) as min/max/mean and nearest-rank percentiles.
+    /// With `buckets` > 1 the session's time range is split into that many
+    /// equal windows. Values are pulled out of the JSON with `json_extract`
+    /// and ranked in SQL, so rows are never deserialized in Rust.
+    pub fn aggregate_metrics(
+        &self,
+        session_id: &str,
+        metric_type: MetricType,
+        field: &str,
+        buckets: Option<u32>,
+    ) -> Result<MetricsSummary, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let type_str = metric_type.as_str();
+        let path = format!("$.{}", field);
+
+        let (first, last): (Option<i64>, Option<i64>) = conn.query_row(
+            "SELECT MIN(timestamp), MAX(timestamp) FROM metrics
+             WHERE session_id = ?1 AND metric_type = ?2",
+            params![session_id, type_str],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        let mut summary = MetricsSummary {
+            session_id: session_id.to_string(),
+            metric_type,
+            field: field.to_string(),
+            buckets: Vec::new(),
+        };
+        let (Some(first), Some(last)) = (first, last) else {
+            return Ok(summary);
+        };
+
+        let bucket_count = buckets.unwrap_or(1).max(1) as i64;
+        let span = last - first + 1;
+
+        // Nearest-rank percentile: the value at rank ceil(p * n / 100)
+        let mut stmt = conn.prepare(
+            "WITH vals AS (
+                SELECT MIN(?5 - 1, (timestamp - ?3) * ?5 / ?4) AS bucket,
+                       CAST(json_extract(metric_json(data, encoding), ?6) AS REAL) AS v
+                FROM metrics
+                WHERE session_id = ?1 AND metric_type = ?2
+                  AND json_extract(metric_json(data, encoding), ?6) IS NOT NULL
+             ),
+             ranked AS (
+                SELECT bucket, v,
+                       ROW_NUMBER() OVER (PARTITION BY bucket ORDER BY v) AS rn,
+                       COUNT(*) OVER (PARTITION BY bucket) AS n
+                FROM vals
+             )
+             SELECT bucket, COUNT(*), MIN(v), MAX(v), AVG(v),
+                    MIN(CASE WHEN rn = (50 * n + 99) / 100 THEN v END),
+                    MIN(CASE WHEN rn = (90 * n + 99) / 100 THEN v END),
+                    MIN(CASE WHEN rn = (95 * n + 99) / 100 THEN v END),
+                    MIN(CASE WHEN rn = (99 * n + 99) / 100 THEN v END)
+             FROM ranked
+             GROUP BY bucket
+             ORDER BY bucket",
+        )?;
+
+        let rows = stmt.query_map(
+            params![session_id, type_str, first, span, bucket_count, path],
+            |row| {
+                let bucket: i64 = row.get(0)?;
+                Ok(MetricBucketStats {
+                    start_time: first + bucket * span / bucket_count,
+                    end_time: first + (bucket + 1) * span / bucket_count - 1,
+                    count: row.get(1)?,
+                    min: row.get(2)?,
+                    max: row.get(3)?,
+                    mean: row.get(4)?,
+                    p50: row.get(5)?,
+                    p90: row.get(6)?,
+                    p95: row.get(7)?,
+                    p99: row.get(8)?,
+                })
+            },
+        )?;
+
+        summary.buckets = rows.collect::<Result<Vec<_>, _>>()?;
+        Ok(summary)
+    }
+
+    /// Smooth a numeric field of a session's metrics (e.g. the GC sawtooth
+    /// in `js_heap_used_size`). Values are extracted in SQL; the windowing
+    /// runs in Rust over the fetched series.
+    pub fn smoothed_metrics(
+        &self,
+        session_id: &str,
+        metric_type: MetricType,
+        field: &str,
+        options: &SmoothingOptions,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+    ) -> Result<SmoothedSeries, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT timestamp, CAST(json_extract(metric_json(data, encoding), ?3) AS REAL)
+             FROM metrics
+             WHERE session_id = ?1 AND metric_type = ?2
+               AND json_extract(metric_json(data, encoding), ?3) IS NOT NULL
+               AND (?4 IS NULL OR timestamp >= ?4)
+               AND (?5 IS NULL OR timestamp <= ?5)
+             ORDER BY timestamp ASC, id ASC",
+        )?;
+        let samples = stmt
+            .query_map(
+                params![
+                    session_id,
+                    metric_type.as_str(),
+                    format!("$.{}", field),
+                    start_time,
+                    end_time
+                ],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?
+            .collect::<Result<Vec<(i64, f64)>, _>>()?;
+
+        let (points, outliers_dropped) = smooth(&samples, options);
+        Ok(SmoothedSeries {
+            session_id: session_id.to_string(),
+            metric_type,
+            field: field.to_string(),
+            points,
+            outliers_dropped,
+        })
+    }
+
+    /// Get the most recent metrics for a session, in ascending time order
+    pub fn get_recent_metrics(
+        &self,
+        session_id: &str,
+        limit: u32,
+    ) -> Result<Vec<StoredMetric>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, timestamp, metric_type, data FROM (
+                SELECT id, session_id, timestamp, metric_type, metric_json(data, encoding) AS data
+                FROM metrics WHERE session_id = ?1
+                ORDER BY timestamp DESC, id DESC LIMIT ?2
+             ) ORDER BY timestamp ASC, id ASC",
+        )?;
+
+        let rows = stmt.query_map(params![session_id, limit], Self::row_to_metric)?;
 
         let metrics: Result<Vec<_>, _> = rows.collect();
         Ok(metrics?)
     }
 
+    fn row_to_metric(row: &rusqlite::Row) -> rusqlite::Result<StoredMetric> {
+        let type_str: String = row.get(3)?;
+        Ok(StoredMetric {
+            id: Some(row.get(0)?),
+            session_id: row.get(1)?,
+            timestamp: row.get(2)?,
+            metric_type: MetricType::from_str(&type_str),
+            data: row.get(4)?,
+        })
+    }
+
     // ==================== Network Request Operations ====================
 
     /// Store a network request
@@ -470,17 +1364,32 @@ impl Database {
         request: &StoredNetworkRequest,
     ) -> Result<(), StorageError> {
         let conn = self.conn.lock().unwrap();
+        Self::insert_network_request(&conn, request)
+    }
+
+    fn insert_network_request(
+        conn: &Connection,
+        request: &StoredNetworkRequest,
+    ) -> Result<(), StorageError> {
         let headers_json = request
             .headers
             .as_ref()
             .map(serde_json::to_string)
             .transpose()?;
+        let timing_json = request
+            .timing
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
 
         conn.execute(
             "INSERT OR REPLACE INTO network_requests
              (id, session_id, url, method, status_code, request_time, response_time,
-              duration_ms, size_bytes, headers)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+              duration_ms, size_bytes, headers, initiator_type, initiator_url, priority,
+              mime_type, resource_type, failed, initiator_line, initiator_function,
+              decoded_size_bytes, timing, error_text, wall_time)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                     ?17, ?18, ?19, ?20, ?21, ?22)",
             params![
                 request.id,
                 request.session_id,
@@ -492,52 +1401,2722 @@ impl Database {
                 request.duration_ms,
                 request.size_bytes,
                 headers_json,
+                request.initiator_type,
+                request.initiator_url,
+                request.priority,
+                request.mime_type,
+                request.resource_type,
+                request.failed,
+                request.initiator_line,
+                request.initiator_function,
+                request.decoded_size_bytes,
+                timing_json,
+                request.error_text,
+                request.wall_time,
             ],
         )?;
 
         Ok(())
     }
 
+    /// Build the WHERE clause and params for a network request filter
+    fn network_filter_clause(
+        session_id: &str,
+        filter: &NetworkRequestFilter,
+    ) -> (String, Vec<Box<dyn rusqlite::ToSql>>) {
+        let mut conditions = vec!["session_id = ?1".to_string()];
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(session_id.to_string())];
+
+        // An empty type list means no filter, as with metric types
+        if let Some(types) = filter.resource_types.as_ref().filter(|t| !t.is_empty()) {
+            let placeholders: Vec<String> = (0..types.len())
+                .map(|i| format!("?{}", params_vec.len() + 1 + i))
+                .collect();
+            conditions.push(format!("resource_type IN ({})", placeholders.join(", ")));
+            for resource_type in types {
+                params_vec.push(Box::new(resource_type.to_lowercase()));
+            }
+        }
+        if let Some(substring) = &filter.url_contains {
+            conditions.push(format!("instr(url, ?{}) > 0", params_vec.len() + 1));
+            params_vec.push(Box::new(substring.clone()));
+        }
+        if let Some(class) = filter.status_class {
+            conditions.push(
+                match class {
+                    StatusClass::Success => "failed = 0 AND status_code BETWEEN 200 AND 299",
+                    StatusClass::Redirect => "failed = 0 AND status_code BETWEEN 300 AND 399",
+                    StatusClass::ClientError => "failed = 0 AND status_code BETWEEN 400 AND 499",
+                    StatusClass::ServerError => "failed = 0 AND status_code BETWEEN 500 AND 599",
+                    StatusClass::Failed => "failed = 1",
+                }
+                .to_string(),
+            );
+        }
+
+        (format!(" WHERE {}", conditions.join(" AND ")), params_vec)
+    }
+
     /// Get network requests for a session
     pub fn get_network_requests(
         &self,
         session_id: &str,
+        filter: &NetworkRequestFilter,
         limit: Option<u32>,
     ) -> Result<Vec<StoredNetworkRequest>, StorageError> {
         let conn = self.conn.lock().unwrap();
+        let (where_clause, params_vec) = Self::network_filter_clause(session_id, filter);
         let limit_clause = limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
         let query = format!(
             "SELECT id, session_id, url, method, status_code, request_time,
-                    response_time, duration_ms, size_bytes, headers
-             FROM network_requests
-             WHERE session_id = ?1
-             ORDER BY request_time ASC{}",
-            limit_clause
+                    response_time, duration_ms, size_bytes, headers,
+                    initiator_type, initiator_url, priority, mime_type, resource_type,
+                    failed, initiator_line, initiator_function, decoded_size_bytes, timing,
+                    error_text, wall_time
+             FROM network_requests{}
+             ORDER BY request_time ASC, id ASC{}",
+            where_clause, limit_clause
         );
 
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+
         let mut stmt = conn.prepare(&query)?;
-        let rows = stmt.query_map(params![session_id], |row| {
-            let headers_json: Option<String> = row.get(9)?;
-            Ok(StoredNetworkRequest {
-                id: row.get(0)?,
+        let rows = stmt.query_map(params_refs.as_slice(), Self::row_to_network_request)?;
+
+        let requests: Result<Vec<_>, _> = rows.collect();
+        Ok(requests?)
+    }
+
+    /// Get one page of network requests after `cursor` (from the start when
+    /// None), using keyset pagination on `(request_time, id)`
+    pub fn get_network_requests_page(
+        &self,
+        session_id: &str,
+        filter: &NetworkRequestFilter,
+        cursor: Option<&NetworkRequestCursor>,
+        limit: u32,
+    ) -> Result<NetworkRequestPage, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let (mut where_clause, mut params_vec) = Self::network_filter_clause(session_id, filter);
+        if let Some(cursor) = cursor {
+            let idx = params_vec.len() + 1;
+            where_clause.push_str(&format!(
+                " AND (request_time > ?{} OR (request_time = ?{} AND id > ?{}))",
+                idx,
+                idx,
+                idx + 1
+            ));
+            params_vec.push(Box::new(cursor.request_time));
+            params_vec.push(Box::new(cursor.id.clone()));
+        }
+
+        // Fetch one extra row to learn whether another page follows
+        let query = format!(
+            "SELECT id, session_id, url, method, status_code, request_time,
+                    response_time, duration_ms, size_bytes, headers,
+                    initiator_type, initiator_url, priority, mime_type, resource_type,
+                    failed, initiator_line, initiator_function, decoded_size_bytes, timing,
+                    error_text, wall_time
+             FROM network_requests{}
+             ORDER BY request_time ASC, id ASC
+             LIMIT {}",
+            where_clause,
+            limit as u64 + 1
+        );
+
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map(params_refs.as_slice(), Self::row_to_network_request)?;
+        let mut requests = rows.collect::<Result<Vec<_>, _>>()?;
+
+        let has_more = requests.len() > limit as usize;
+        requests.truncate(limit as usize);
+        let next_cursor = requests
+            .last()
+            .filter(|_| has_more)
+            .map(|last| NetworkRequestCursor {
+                request_time: last.request_time,
+                id: last.id.clone(),
+            });
+
+        Ok(NetworkRequestPage {
+            requests,
+            next_cursor,
+        })
+    }
+
+    /// Get the most recent network requests for a session, in ascending time order
+    pub fn get_recent_network_requests(
+        &self,
+        session_id: &str,
+        limit: u32,
+    ) -> Result<Vec<StoredNetworkRequest>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT * FROM (
+                SELECT id, session_id, url, method, status_code, request_time,
+                       response_time, duration_ms, size_bytes, headers,
+                       initiator_type, initiator_url, priority, mime_type, resource_type,
+                       failed, initiator_line, initiator_function, decoded_size_bytes, timing,
+                       error_text, wall_time
+                FROM network_requests
+                WHERE session_id = ?1
+                ORDER BY request_time DESC, id DESC LIMIT ?2
+             ) ORDER BY request_time ASC, id ASC",
+        )?;
+
+        let rows = stmt.query_map(params![session_id, limit], Self::row_to_network_request)?;
+
+        let requests: Result<Vec<_>, _> = rows.collect();
+        Ok(requests?)
+    }
+
+    fn row_to_network_request(row: &rusqlite::Row) -> rusqlite::Result<StoredNetworkRequest> {
+        let headers_json: Option<String> = row.get(9)?;
+        let timing_json: Option<String> = row.get(19)?;
+        Ok(StoredNetworkRequest {
+            id: row.get(0)?,
+            session_id: row.get(1)?,
+            url: row.get(2)?,
+            method: row.get(3)?,
+            status_code: row.get(4)?,
+            request_time: row.get(5)?,
+            response_time: row.get(6)?,
+            duration_ms: row.get(7)?,
+            size_bytes: row.get(8)?,
+            decoded_size_bytes: row.get(18)?,
+            headers: headers_json.map(|s| serde_json::from_str(&s).unwrap_or_default()),
+            initiator_type: row.get(10)?,
+            initiator_url: row.get(11)?,
+            priority: row.get(12)?,
+            mime_type: row.get(13)?,
+            resource_type: row.get(14)?,
+            failed: row.get(15)?,
+            initiator_line: row.get(16)?,
+            initiator_function: row.get(17)?,
+            timing: timing_json.and_then(|s| serde_json::from_str(&s).ok()),
+            error_text: row.get(20)?,
+            wall_time: row.get(21)?,
+        })
+    }
+
+    /// Get aggregate network statistics for a session.
+    /// With a normalizer, `by_endpoint` groups URLs by normalized endpoint.
+    pub fn get_network_summary(
+        &self,
+        session_id: &str,
+        normalizer: Option<&UrlNormalizer>,
+    ) -> Result<NetworkSummary, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let (total_requests, completed_requests, total_bytes, total_decoded_bytes, avg_duration_ms) =
+            conn.query_row(
+                "SELECT COUNT(*), COUNT(response_time), COALESCE(SUM(size_bytes), 0),
+                        COALESCE(SUM(decoded_size_bytes), 0), AVG(duration_ms)
+                 FROM network_requests
+                 WHERE session_id = ?1",
+                params![session_id],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                    ))
+                },
+            )?;
+
+        let mut stmt = conn.prepare(
+            "SELECT COALESCE(priority, 'unknown'), COUNT(*)
+             FROM network_requests
+             WHERE session_id = ?1
+             GROUP BY 1",
+        )?;
+        let by_priority = stmt
+            .query_map(params![session_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT url, COUNT(*)
+             FROM network_requests
+             WHERE session_id = ?1
+             GROUP BY url",
+        )?;
+        let url_counts = stmt
+            .query_map(params![session_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut stmt = conn.prepare(
+            "SELECT COALESCE(resource_type, 'unknown'), COUNT(*),
+                    COALESCE(SUM(size_bytes), 0), COALESCE(SUM(decoded_size_bytes), 0)
+             FROM network_requests
+             WHERE session_id = ?1
+             GROUP BY 1",
+        )?;
+        let by_resource_type = stmt
+            .query_map(params![session_id], |row| {
+                Ok((
+                    row.get(0)?,
+                    ResourceTypeBytes {
+                        requests: row.get(1)?,
+                        wire_bytes: row.get(2)?,
+                        decoded_bytes: row.get(3)?,
+                    },
+                ))
+            })?
+            .collect::<Result<_, _>>()?;
+
+        let mut by_endpoint = HashMap::new();
+        for (url, count) in url_counts {
+            let key = match normalizer {
+                Some(n) => n.normalize(&url),
+                None => url,
+            };
+            *by_endpoint.entry(key).or_insert(0) += count;
+        }
+
+        Ok(NetworkSummary {
+            session_id: session_id.to_string(),
+            total_requests,
+            completed_requests,
+            total_bytes,
+            total_decoded_bytes,
+            avg_duration_ms,
+            by_priority,
+            by_endpoint,
+            by_resource_type,
+        })
+    }
+
+    /// Groups of requests sharing method and URL, most wasted bytes first.
+    /// With a normalizer, URLs are compared by normalized endpoint, so
+    /// cache-busting query strings and variable path segments count as the same.
+    pub fn find_duplicate_requests(
+        &self,
+        session_id: &str,
+        normalizer: Option<&UrlNormalizer>,
+    ) -> Result<Vec<DuplicateRequestGroup>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, method, url, size_bytes
+             FROM network_requests
+             WHERE session_id = ?1
+             ORDER BY request_time ASC, id ASC",
+        )?;
+        let rows = stmt
+            .query_map(params![session_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<f64>>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut groups: HashMap<(Option<String>, String), DuplicateRequestGroup> = HashMap::new();
+        for (id, method, url, size_bytes) in rows {
+            let url = match normalizer {
+                Some(n) => n.normalize(&url),
+                None => url,
+            };
+            let size_bytes = size_bytes.unwrap_or(0.0);
+            let group = groups
+                .entry((method.clone(), url.clone()))
+                .or_insert_with(|| DuplicateRequestGroup {
+                    method,
+                    url,
+                    count: 0,
+                    total_bytes: 0.0,
+                    wasted_bytes: 0.0,
+                    request_ids: Vec::new(),
+                });
+            if group.count > 0 {
+                group.wasted_bytes += size_bytes;
+            }
+            group.count += 1;
+            group.total_bytes += size_bytes;
+            group.request_ids.push(id);
+        }
+
+        let mut duplicates: Vec<DuplicateRequestGroup> =
+            groups.into_values().filter(|g| g.count > 1).collect();
+        duplicates.sort_by(|a, b| {
+            b.wasted_bytes
+                .total_cmp(&a.wasted_bytes)
+                .then_with(|| b.count.cmp(&a.count))
+                .then_with(|| a.url.cmp(&b.url))
+        });
+        Ok(duplicates)
+    }
+
+    /// Count requests and sum their wire bytes per `bucket_ms` window of
+    /// `request_time`. Every window between the first and last request is
+    /// returned, with zero counts for the quiet ones, so a chart's x-axis
+    /// has no gaps. Windows are widened as needed to stay within
+    /// `MAX_THROUGHPUT_BUCKETS`.
+    pub fn network_throughput(
+        &self,
+        session_id: &str,
+        bucket_ms: i64,
+    ) -> Result<Vec<ThroughputBucket>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let (first, last): (Option<i64>, Option<i64>) = conn.query_row(
+            "SELECT MIN(request_time), MAX(request_time)
+             FROM network_requests WHERE session_id = ?1",
+            params![session_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        let span = last.zip(first).map_or(0, |(last, first)| last - first);
+        let bucket_ms = bucket_ms.max(span / (MAX_THROUGHPUT_BUCKETS - 1) + 1);
+
+        let mut stmt = conn.prepare(
+            "WITH RECURSIVE counts AS (
+                SELECT request_time / ?2 AS bucket, COUNT(*) AS n,
+                       COALESCE(SUM(size_bytes), 0.0) AS bytes
+                FROM network_requests
+                WHERE session_id = ?1
+                GROUP BY bucket
+             ),
+             buckets(bucket) AS (
+                SELECT MIN(bucket) FROM counts
+                UNION ALL
+                SELECT bucket + 1 FROM buckets
+                WHERE bucket < (SELECT MAX(bucket) FROM counts)
+             )
+             SELECT b.bucket * ?2, COALESCE(c.n, 0), COALESCE(c.bytes, 0.0)
+             FROM buckets b
+             LEFT JOIN counts c ON c.bucket = b.bucket
+             WHERE b.bucket IS NOT NULL
+             ORDER BY b.bucket",
+        )?;
+        let buckets = stmt
+            .query_map(params![session_id, bucket_ms], |row| {
+                Ok(ThroughputBucket {
+                    start_time: row.get(0)?,
+                    request_count: row.get(1)?,
+                    total_bytes: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(buckets)
+    }
+
+    /// Request count, wire bytes and mean duration per URL host, largest
+    /// byte total first
+    pub fn network_by_host(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<HostNetworkSummary>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT url, size_bytes, duration_ms
+             FROM network_requests
+             WHERE session_id = ?1",
+        )?;
+        let rows = stmt
+            .query_map(params![session_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<f64>>(1)?,
+                    row.get::<_, Option<f64>>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // host -> (summary, sum of known durations, requests with a duration)
+        let mut hosts: HashMap<String, (HostNetworkSummary, f64, i64)> = HashMap::new();
+        for (url, size_bytes, duration_ms) in rows {
+            let host = reqwest::Url::parse(&url)
+                .ok()
+                .and_then(|u| u.host_str().map(str::to_string))
+                .unwrap_or_else(|| "(inline)".to_string());
+            let (summary, duration_sum, timed) = hosts.entry(host.clone()).or_insert_with(|| {
+                let summary = HostNetworkSummary {
+                    host,
+                    requests: 0,
+                    total_bytes: 0.0,
+                    avg_duration_ms: None,
+                };
+                (summary, 0.0, 0)
+            });
+            summary.requests += 1;
+            summary.total_bytes += size_bytes.unwrap_or(0.0);
+            if let Some(duration_ms) = duration_ms {
+                *duration_sum += duration_ms;
+                *timed += 1;
+            }
+        }
+
+        let mut summaries: Vec<HostNetworkSummary> = hosts
+            .into_values()
+            .map(|(mut summary, duration_sum, timed)| {
+                summary.avg_duration_ms = (timed > 0).then(|| duration_sum / timed as f64);
+                summary
+            })
+            .collect();
+        summaries.sort_by(|a, b| {
+            b.total_bytes
+                .total_cmp(&a.total_bytes)
+                .then_with(|| a.host.cmp(&b.host))
+        });
+        Ok(summaries)
+    }
+
+    /// Store a cumulative transfer sample for a request
+    pub fn store_request_data_chunk(&self, chunk: &RequestDataChunk) -> Result<(), StorageError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO request_data_chunks
+             (session_id, request_id, timestamp, data_length, encoded_data_length)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                chunk.session_id,
+                chunk.request_id,
+                chunk.timestamp,
+                chunk.data_length,
+                chunk.encoded_data_length,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get the transfer timeline (cumulative bytes over time) for a request.
+    /// CDP request ids repeat across sessions, so both are needed.
+    pub fn get_request_data_chunks(
+        &self,
+        session_id: &str,
+        request_id: &str,
+    ) -> Result<Vec<RequestDataChunk>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, request_id, timestamp, data_length, encoded_data_length
+             FROM request_data_chunks
+             WHERE session_id = ?1 AND request_id = ?2
+             ORDER BY timestamp ASC, id ASC",
+        )?;
+
+        let rows = stmt.query_map(params![session_id, request_id], |row| {
+            Ok(RequestDataChunk {
+                id: Some(row.get(0)?),
                 session_id: row.get(1)?,
-                url: row.get(2)?,
-                method: row.get(3)?,
-                status_code: row.get(4)?,
-                request_time: row.get(5)?,
-                response_time: row.get(6)?,
-                duration_ms: row.get(7)?,
-                size_bytes: row.get(8)?,
-                headers: headers_json.map(|s| serde_json::from_str(&s).unwrap_or_default()),
+                request_id: row.get(2)?,
+                timestamp: row.get(3)?,
+                data_length: row.get(4)?,
+                encoded_data_length: row.get(5)?,
             })
         })?;
 
-        let requests: Result<Vec<_>, _> = rows.collect();
-        Ok(requests?)
+        let chunks: Result<Vec<_>, _> = rows.collect();
+        Ok(chunks?)
+    }
+
+    // ==================== Marker Operations ====================
+
+    /// Store a session marker
+    pub fn store_marker(&self, marker: &SessionMarker) -> Result<i64, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO session_markers (session_id, timestamp, kind)
+             VALUES (?1, ?2, ?3)",
+            params![marker.session_id, marker.timestamp, marker.kind.as_str()],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Get markers for a session in timeline order
+    pub fn get_markers(&self, session_id: &str) -> Result<Vec<SessionMarker>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, timestamp, kind
+             FROM session_markers
+             WHERE session_id = ?1
+             ORDER BY timestamp ASC, id ASC",
+        )?;
+
+        let rows = stmt.query_map(params![session_id], |row| {
+            let kind_str: String = row.get(3)?;
+            Ok(SessionMarker {
+                id: Some(row.get(0)?),
+                session_id: row.get(1)?,
+                timestamp: row.get(2)?,
+                kind: MarkerKind::from_str(&kind_str),
+            })
+        })?;
+
+        let markers: Result<Vec<_>, _> = rows.collect();
+        Ok(markers?)
+    }
+
+    // ==================== Annotation Operations ====================
+
+    /// Store an annotation
+    pub fn store_annotation(&self, annotation: &Annotation) -> Result<i64, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        Self::insert_annotation(&conn, annotation)?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    fn insert_annotation(conn: &Connection, annotation: &Annotation) -> Result<(), StorageError> {
+        conn.execute(
+            "INSERT INTO annotations (session_id, timestamp, label, color)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                annotation.session_id,
+                annotation.timestamp,
+                annotation.label,
+                annotation.color
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Get annotations for a session in timeline order
+    pub fn get_annotations(&self, session_id: &str) -> Result<Vec<Annotation>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, timestamp, label, color
+             FROM annotations
+             WHERE session_id = ?1
+             ORDER BY timestamp ASC, id ASC",
+        )?;
+
+        let rows = stmt.query_map(params![session_id], |row| {
+            Ok(Annotation {
+                id: Some(row.get(0)?),
+                session_id: row.get(1)?,
+                timestamp: row.get(2)?,
+                label: row.get(3)?,
+                color: row.get(4)?,
+            })
+        })?;
+
+        let annotations: Result<Vec<_>, _> = rows.collect();
+        Ok(annotations?)
+    }
+
+    /// Delete an annotation by id
+    pub fn delete_annotation(&self, id: i64) -> Result<(), StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let rows = conn.execute("DELETE FROM annotations WHERE id = ?1", params![id])?;
+
+        if rows == 0 {
+            return Err(StorageError::AnnotationNotFound(id));
+        }
+
+        Ok(())
+    }
+
+    // ==================== Security Operations ====================
+
+    /// Store a page security state change
+    pub fn store_security_event(&self, event: &SecurityEvent) -> Result<i64, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO security_events (session_id, timestamp, state, summary)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                event.session_id,
+                event.timestamp,
+                event.state,
+                event.summary
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Get security state changes for a session, oldest first
+    pub fn get_security_events(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<SecurityEvent>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, timestamp, state, summary
+             FROM security_events
+             WHERE session_id = ?1
+             ORDER BY timestamp ASC, id ASC",
+        )?;
+
+        let rows = stmt.query_map(params![session_id], |row| {
+            Ok(SecurityEvent {
+                id: Some(row.get(0)?),
+                session_id: row.get(1)?,
+                timestamp: row.get(2)?,
+                state: row.get(3)?,
+                summary: row.get(4)?,
+            })
+        })?;
+
+        let events: Result<Vec<_>, _> = rows.collect();
+        Ok(events?)
+    }
+
+    // ==================== Alert Operations ====================
+
+    /// Store a triggered alert
+    pub fn store_alert(&self, alert: &Alert) -> Result<i64, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO alerts (session_id, timestamp, kind, value, threshold, request_id, url)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                alert.session_id,
+                alert.timestamp,
+                alert.kind.as_str(),
+                alert.value,
+                alert.threshold,
+                alert.request_id,
+                alert.url
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Get alerts for a session, oldest first
+    pub fn get_alerts(&self, session_id: &str) -> Result<Vec<Alert>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, timestamp, kind, value, threshold, request_id, url
+             FROM alerts
+             WHERE session_id = ?1
+             ORDER BY timestamp ASC, id ASC",
+        )?;
+
+        let rows = stmt.query_map(params![session_id], |row| {
+            let kind: String = row.get(3)?;
+            Ok(Alert {
+                id: Some(row.get(0)?),
+                session_id: row.get(1)?,
+                timestamp: row.get(2)?,
+                kind: AlertKind::from_str(&kind),
+                value: row.get(4)?,
+                threshold: row.get(5)?,
+                request_id: row.get(6)?,
+                url: row.get(7)?,
+            })
+        })?;
+
+        let alerts: Result<Vec<_>, _> = rows.collect();
+        Ok(alerts?)
+    }
+
+    // ==================== Console Operations ====================
+
+    /// Store a console message
+    pub fn store_console_log(&self, log: &ConsoleLog) -> Result<i64, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO console_logs (session_id, timestamp, level, text, url, line)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                log.session_id,
+                log.timestamp,
+                log.level.as_str(),
+                log.text,
+                log.url,
+                log.line
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Keep only the most recent `keep_last_n` console messages for a session
+    pub fn prune_console_logs(
+        &self,
+        session_id: &str,
+        keep_last_n: u32,
+    ) -> Result<usize, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let threshold: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM console_logs WHERE session_id = ?1
+                 ORDER BY id DESC LIMIT 1 OFFSET ?2",
+                params![session_id, keep_last_n],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let Some(threshold) = threshold else {
+            return Ok(0);
+        };
+
+        let deleted = conn.execute(
+            "DELETE FROM console_logs WHERE session_id = ?1 AND id <= ?2",
+            params![session_id, threshold],
+        )?;
+
+        Ok(deleted)
+    }
+
+    /// Get console messages for a session, oldest first. An empty or missing
+    /// level list returns every level.
+    pub fn get_console_logs(
+        &self,
+        session_id: &str,
+        levels: Option<&[ConsoleLevel]>,
+    ) -> Result<Vec<ConsoleLog>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let levels = levels.filter(|levels| !levels.is_empty());
+
+        let mut query = "SELECT id, session_id, timestamp, level, text, url, line
+             FROM console_logs
+             WHERE session_id = ?1"
+            .to_string();
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(session_id.to_string())];
+        if let Some(levels) = levels {
+            let placeholders: Vec<String> =
+                (0..levels.len()).map(|i| format!("?{}", i + 2)).collect();
+            query.push_str(&format!(" AND level IN ({})", placeholders.join(", ")));
+            for level in levels {
+                params_vec.push(Box::new(level.as_str()));
+            }
+        }
+        query.push_str(" ORDER BY timestamp ASC, id ASC");
+
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|p| p.as_ref()).collect();
+
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt.query_map(params_refs.as_slice(), |row| {
+            let level: String = row.get(3)?;
+            Ok(ConsoleLog {
+                id: Some(row.get(0)?),
+                session_id: row.get(1)?,
+                timestamp: row.get(2)?,
+                level: ConsoleLevel::from_str(&level),
+                text: row.get(4)?,
+                url: row.get(5)?,
+                line: row.get(6)?,
+            })
+        })?;
+
+        let logs: Result<Vec<_>, _> = rows.collect();
+        Ok(logs?)
+    }
+
+    // ==================== Response Body Operations ====================
+
+    /// Store a captured response body, refusing bodies over `max_bytes`
+    pub fn store_response_body(
+        &self,
+        body: &ResponseBody,
+        max_bytes: i64,
+    ) -> Result<i64, StorageError> {
+        if body.size_bytes > max_bytes {
+            return Err(StorageError::BodyTooLarge {
+                size: body.size_bytes,
+                max: max_bytes,
+            });
+        }
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO response_bodies
+             (session_id, request_id, url, timestamp, size_bytes, base64_encoded, body)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                body.session_id,
+                body.request_id,
+                body.url,
+                body.timestamp,
+                body.size_bytes,
+                body.base64_encoded,
+                body.body
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Get the captured body of one request, if it was captured
+    pub fn get_response_body(
+        &self,
+        session_id: &str,
+        request_id: &str,
+    ) -> Result<Option<ResponseBody>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let body = conn
+            .query_row(
+                "SELECT id, session_id, request_id, url, timestamp, size_bytes, base64_encoded, body
+                 FROM response_bodies
+                 WHERE session_id = ?1 AND request_id = ?2
+                 ORDER BY id DESC LIMIT 1",
+                params![session_id, request_id],
+                |row| {
+                    Ok(ResponseBody {
+                        id: Some(row.get(0)?),
+                        session_id: row.get(1)?,
+                        request_id: row.get(2)?,
+                        url: row.get(3)?,
+                        timestamp: row.get(4)?,
+                        size_bytes: row.get(5)?,
+                        base64_encoded: row.get(6)?,
+                        body: row.get(7)?,
+                    })
+                },
+            )
+            .optional()?;
+
+        Ok(body)
+    }
+
+    // ==================== Settings Operations ====================
+
+    /// Load settings, falling back to defaults for missing or unreadable keys
+    pub fn get_settings(&self) -> Result<Settings, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT key, value FROM settings")?;
+        let stored = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut merged = serde_json::to_value(Settings::default())?;
+        for (key, value_json) in stored {
+            let Ok(value) = serde_json::from_str(&value_json) else {
+                continue;
+            };
+            let mut candidate = merged.clone();
+            candidate[key.as_str()] = value;
+            // Keep a key only if it still deserializes into the current schema
+            if serde_json::from_value::<Settings>(candidate.clone()).is_ok() {
+                merged = candidate;
+            }
+        }
+
+        Ok(serde_json::from_value(merged)?)
+    }
+
+    /// Persist every settings field, leaving unknown keys untouched
+    pub fn update_settings(&self, settings: &Settings) -> Result<(), StorageError> {
+        let mut conn = self.conn.lock().unwrap();
+        let serde_json::Value::Object(fields) = serde_json::to_value(settings)? else {
+            return Ok(());
+        };
+
+        let tx = conn.transaction()?;
+        for (key, value) in fields {
+            tx.execute(
+                "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+                params![key, value.to_string()],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    // ==================== Preset Operations ====================
+
+    /// Save a preset, replacing any existing preset with the same name
+    pub fn save_preset(&self, preset: &TestPreset) -> Result<(), StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let config_json = serde_json::to_string(&preset.config)?;
+        conn.execute(
+            "INSERT OR REPLACE INTO presets (name, config, created_at) VALUES (?1, ?2, ?3)",
+            params![preset.name, config_json, preset.created_at],
+        )?;
+
+        Ok(())
+    }
+
+    /// List all presets by name
+    pub fn list_presets(&self) -> Result<Vec<TestPreset>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt =
+            conn.prepare("SELECT name, config, created_at FROM presets ORDER BY name")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get(2)?,
+            ))
+        })?;
+
+        let mut presets = Vec::new();
+        for row in rows {
+            let (name, config_json, created_at) = row?;
+            presets.push(TestPreset {
+                name,
+                config: serde_json::from_str(&config_json)?,
+                created_at,
+            });
+        }
+        Ok(presets)
+    }
+
+    /// Get a preset by name
+    pub fn get_preset(&self, name: &str) -> Result<TestPreset, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let (config_json, created_at): (String, i64) = conn
+            .query_row(
+                "SELECT config, created_at FROM presets WHERE name = ?1",
+                params![name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?
+            .ok_or_else(|| StorageError::PresetNotFound(name.to_string()))?;
+
+        Ok(TestPreset {
+            name: name.to_string(),
+            config: serde_json::from_str(&config_json)?,
+            created_at,
+        })
+    }
+
+    // ==================== Retention Operations ====================
+
+    /// Delete finished sessions that ended before `older_than_ms` (ms since
+    /// epoch), with all their data. Active sessions are never pruned.
+    pub fn prune_old_sessions(&self, older_than_ms: i64) -> Result<PruneResult, StorageError> {
+        const STALE_SESSIONS: &str = "SELECT id FROM sessions
+             WHERE status != 'active' AND COALESCE(ended_at, started_at) < ?1";
+        const CHILD_TABLES: [&str; 9] = [
+            "metrics",
+            "network_requests",
+            "request_data_chunks",
+            "session_markers",
+            "security_events",
+            "alerts",
+            "console_logs",
+            "response_bodies",
+            "annotations",
+        ];
+
+        let mut conn = self.conn.lock().unwrap();
+        Self::ensure_foreign_keys(&conn)?;
+        let tx = conn.transaction()?;
+
+        let mut result = PruneResult::default();
+        for table in CHILD_TABLES {
+            let count: i64 = tx.query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM {} WHERE session_id IN ({})",
+                    table, STALE_SESSIONS
+                ),
+                params![older_than_ms],
+                |row| row.get(0),
+            )?;
+            match table {
+                "metrics" => result.metrics_deleted = count,
+                "network_requests" => result.network_requests_deleted = count,
+                _ => {}
+            }
+            result.total_rows_deleted += count;
+        }
+
+        result.sessions_deleted = tx.execute(
+            &format!("DELETE FROM sessions WHERE id IN ({})", STALE_SESSIONS),
+            params![older_than_ms],
+        )? as i64;
+        result.total_rows_deleted += result.sessions_deleted;
+        tx.commit()?;
+
+        Ok(result)
+    }
+
+    /// Rebuild the database file to return space freed by deletes to the OS
+    pub fn vacuum(&self) -> Result<(), StorageError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute_batch("VACUUM")?;
+        Ok(())
+    }
+
+    // ==================== Bundle Operations ====================
+
+    /// Export a session with all its metrics, network requests and
+    /// annotations as a checksummed JSON bundle
+    pub fn export_session_bundle(&self, session_id: &str) -> Result<String, StorageError> {
+        let bytes = self.write_session_bundle(session_id, Vec::new())?;
+        // serde_json only writes UTF-8, so nothing is actually replaced
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Stream a session bundle to `out`, reading rows page by page so large
+    /// sessions are never fully loaded into memory
+    pub fn write_session_bundle<W: Write>(
+        &self,
+        session_id: &str,
+        out: W,
+    ) -> Result<W, StorageError> {
+        let session = self
+            .get_session(session_id)?
+            .ok_or_else(|| StorageError::SessionNotFound(session_id.to_string()))?;
+
+        let mut writer = BundleWriter::new(out)?;
+        writer.write_all(br#"{"session":"#)?;
+        serde_json::to_writer(&mut writer, &session)?;
+
+        writer.write_all(br#","metrics":["#)?;
+        let mut after_id = 0;
+        let mut first = true;
+        loop {
+            let page = self.metrics_page(session_id, after_id)?;
+            let Some(last) = page.last() else { break };
+            after_id = last.id.unwrap_or_default();
+            for metric in &page {
+                if !std::mem::take(&mut first) {
+                    writer.write_all(b",")?;
+                }
+                serde_json::to_writer(&mut writer, metric)?;
+            }
+        }
+
+        writer.write_all(br#"],"network_requests":["#)?;
+        let mut after_rowid = 0;
+        let mut first = true;
+        loop {
+            let page = self.network_requests_page(session_id, after_rowid)?;
+            let Some((last_rowid, _)) = page.last() else {
+                break;
+            };
+            after_rowid = *last_rowid;
+            for (_, request) in &page {
+                if !std::mem::take(&mut first) {
+                    writer.write_all(b",")?;
+                }
+                serde_json::to_writer(&mut writer, request)?;
+            }
+        }
+
+        // A handful per session; no need to page
+        writer.write_all(br#"],"annotations":"#)?;
+        serde_json::to_writer(&mut writer, &self.get_annotations(session_id)?)?;
+        writer.write_all(b"}")?;
+
+        Ok(writer.finish()?)
+    }
+
+    /// Next page of a session's metrics in insertion order
+    fn metrics_page(
+        &self,
+        session_id: &str,
+        after_id: i64,
+    ) -> Result<Vec<StoredMetric>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, timestamp, metric_type, metric_json(data, encoding) AS data
+             FROM metrics WHERE session_id = ?1 AND id > ?2
+             ORDER BY id ASC LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(
+            params![session_id, after_id, BUNDLE_PAGE_SIZE],
+            Self::row_to_metric,
+        )?;
+
+        let metrics: Result<Vec<_>, _> = rows.collect();
+        Ok(metrics?)
+    }
+
+    /// Next page of a session's network requests in insertion order, keyed
+    /// by rowid since request IDs are not ordered
+    fn network_requests_page(
+        &self,
+        session_id: &str,
+        after_rowid: i64,
+    ) -> Result<Vec<(i64, StoredNetworkRequest)>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, url, method, status_code, request_time,
+                    response_time, duration_ms, size_bytes, headers,
+                    initiator_type, initiator_url, priority, mime_type, resource_type,
+                    failed, initiator_line, initiator_function, decoded_size_bytes, timing,
+                    error_text, wall_time,
+                    rowid
+             FROM network_requests WHERE session_id = ?1 AND rowid > ?2
+             ORDER BY rowid ASC LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![session_id, after_rowid, BUNDLE_PAGE_SIZE], |row| {
+            Ok((row.get(22)?, Self::row_to_network_request(row)?))
+        })?;
+
+        let requests: Result<Vec<_>, _> = rows.collect();
+        Ok(requests?)
+    }
+
+    /// Verify and import a session bundle under a fresh session ID.
+    /// Everything is inserted in one transaction so a bad bundle leaves no
+    /// partial data behind.
+    pub fn import_session_bundle(&self, bundle_json: &str) -> Result<Session, StorageError> {
+        let bundle: SessionBundle = serde_json::from_str(bundle_json)?;
+        self.import_bundle(bundle)
+    }
+
+    /// Import a session bundle from a file written by `write_session_bundle`
+    pub fn import_session_bundle_file(
+        &self,
+        path: &std::path::Path,
+    ) -> Result<Session, StorageError> {
+        let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+        let bundle: SessionBundle = serde_json::from_reader(reader)?;
+        self.import_bundle(bundle)
+    }
+
+    fn import_bundle(&self, bundle: SessionBundle) -> Result<Session, StorageError> {
+        let content = bundle.open()?;
+
+        let mut session = content.session;
+        session.id = uuid::Uuid::new_v4().to_string();
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        Self::insert_session(&tx, &session)?;
+        for mut metric in content.metrics {
+            metric.id = None;
+            metric.session_id = session.id.clone();
+            Self::insert_metric(&tx, &metric, self.compress_metrics.load(Ordering::Relaxed))?;
+        }
+        for mut request in content.network_requests {
+            // Request IDs are table-wide keys; re-key to avoid collisions
+            request.id = uuid::Uuid::new_v4().to_string();
+            request.session_id = session.id.clone();
+            Self::insert_network_request(&tx, &request)?;
+        }
+        for mut annotation in content.annotations {
+            annotation.id = None;
+            annotation.session_id = session.id.clone();
+            Self::insert_annotation(&tx, &annotation)?;
+        }
+
+        tx.commit()?;
+        Ok(session)
+    }
+
+    // ==================== Comparison Operations ====================
+
+    /// Compare two sessions' headline aggregates, with A as the baseline
+    pub fn compare_sessions(
+        &self,
+        session_a: &str,
+        session_b: &str,
+    ) -> Result<SessionComparison, StorageError> {
+        let a = self.session_aggregates(session_a)?;
+        let b = self.session_aggregates(session_b)?;
+        Ok(SessionComparison::new(session_a, &a, session_b, &b))
+    }
+
+    /// Headline aggregates of one session, as compared by `compare_sessions`
+    pub fn session_aggregates(&self, session_id: &str) -> Result<SessionAggregates, StorageError> {
+        let session = self
+            .get_session(session_id)?
+            .ok_or_else(|| StorageError::SessionNotFound(session_id.to_string()))?;
+        let conn = self.conn.lock().unwrap();
+
+        let (mean_js_heap, peak_dom_nodes, last_sample): (Option<f64>, Option<f64>, Option<i64>) =
+            conn.query_row(
+                "SELECT AVG(json_extract(metric_json(data, encoding), '$.js_heap_used_size')),
+                        MAX(json_extract(metric_json(data, encoding), '$.dom_nodes')),
+                        MAX(timestamp)
+                 FROM metrics
+                 WHERE session_id = ?1 AND metric_type = ?2",
+                params![session_id, MetricType::Performance.as_str()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )?;
+
+        let (request_count, total_bytes, mean_request_duration_ms) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(size_bytes), 0), AVG(duration_ms)
+             FROM network_requests
+             WHERE session_id = ?1",
+            params![session_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        let end = session
+            .ended_at
+            .or(last_sample)
+            .unwrap_or(session.started_at);
+
+        Ok(SessionAggregates {
+            duration_ms: (end - session.started_at).max(0),
+            mean_js_heap,
+            peak_dom_nodes,
+            total_bytes,
+            request_count,
+            mean_request_duration_ms,
+        })
+    }
+
+    // ==================== HAR Export ====================
+
+    /// Export a session's network requests as a HAR 1.2 JSON document
+    pub fn export_har(&self, session_id: &str) -> Result<String, StorageError> {
+        if self.get_session(session_id)?.is_none() {
+            return Err(StorageError::SessionNotFound(session_id.to_string()));
+        }
+        let requests =
+            self.get_network_requests(session_id, &NetworkRequestFilter::default(), None)?;
+
+        Ok(serde_json::to_string(&Har::from_requests(&requests))?)
+    }
+
+    // ==================== CSV Export ====================
+
+    /// Export a session's performance samples or network requests as CSV
+    pub fn export_session_csv(
+        &self,
+        session_id: &str,
+        kind: CsvExportKind,
+    ) -> Result<String, StorageError> {
+        if self.get_session(session_id)?.is_none() {
+            return Err(StorageError::SessionNotFound(session_id.to_string()));
+        }
+
+        match kind {
+            CsvExportKind::Metrics => {
+                let metrics = self.get_metrics(
+                    session_id,
+                    Some(&[MetricType::Performance]),
+                    None,
+                    None,
+                    None,
+                )?;
+                metrics_to_csv(&metrics)
+            }
+            CsvExportKind::Network => {
+                let requests =
+                    self.get_network_requests(session_id, &NetworkRequestFilter::default(), None)?;
+                network_to_csv(&requests)
+            }
+        }
+    }
+
+    // ==================== NDJSON Export ====================
+
+    /// Write a session's metrics to `out` as NDJSON, one metric per line in
+    /// timestamp order. Reads page by page and releases the connection
+    /// between pages, so neither memory nor collection is held up by a large
+    /// session. Returns the number of metrics written.
+    pub fn write_metrics_ndjson<W: Write>(
+        &self,
+        session_id: &str,
+        mut out: W,
+    ) -> Result<i64, StorageError> {
+        if self.get_session(session_id)?.is_none() {
+            return Err(StorageError::SessionNotFound(session_id.to_string()));
+        }
+
+        let mut after = (i64::MIN, 0);
+        let mut count = 0;
+        loop {
+            let page: Vec<StoredMetric> = {
+                let conn = self.conn.lock().unwrap();
+                let mut stmt = conn.prepare(
+                    "SELECT id, session_id, timestamp, metric_type,
+                            metric_json(data, encoding) AS data
+                     FROM metrics
+                     WHERE session_id = ?1 AND (timestamp, id) > (?2, ?3)
+                     ORDER BY timestamp ASC, id ASC LIMIT ?4",
+                )?;
+                let rows = stmt.query_map(
+                    params![session_id, after.0, after.1, BUNDLE_PAGE_SIZE],
+                    Self::row_to_metric,
+                )?;
+                rows.collect::<Result<_, _>>()?
+            };
+            let Some(last) = page.last() else { break };
+            after = (last.timestamp, last.id.unwrap_or_default());
+
+            for metric in &page {
+                serde_json::to_writer(&mut out, metric)?;
+                out.write_all(b"\n")?;
+                count += 1;
+            }
+        }
+        out.flush()?;
+        Ok(count)
     }
 
     /// Get database file path
     pub fn get_db_path(app_data_dir: &std::path::Path) -> PathBuf {
         app_data_dir.join("awpa.db")
     }
+
+    /// Directory holding a session's screenshots
+    pub fn get_screenshot_dir(app_data_dir: &std::path::Path, session_id: &str) -> PathBuf {
+        app_data_dir.join("screenshots").join(session_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdp::RequestTiming;
+    use crate::storage::bundle::BUNDLE_FORMAT_VERSION;
+    use crate::storage::smoothing::SmoothingMethod;
+
+    fn seeded_db() -> (Database, String) {
+        let db = Database::in_memory().unwrap();
+        let session = Session::new("device-1".into(), None, None, None, None);
+        db.create_session(&session).unwrap();
+        db.store_metric(&StoredMetric {
+            id: None,
+            session_id: session.id.clone(),
+            timestamp: 1000,
+            metric_type: MetricType::Performance,
+            data: "{\"dom_nodes\":42}".into(),
+        })
+        .unwrap();
+        (db, session.id)
+    }
+
+    /// A GET request with the optional fields empty; tests set what they
+    /// check with struct update syntax
+    fn request(session_id: &str, id: &str) -> StoredNetworkRequest {
+        StoredNetworkRequest {
+            id: id.into(),
+            session_id: session_id.into(),
+            url: format!("https://example.com/{}", id),
+            method: Some("GET".into()),
+            status_code: Some(200),
+            request_time: 1000,
+            wall_time: None,
+            response_time: None,
+            duration_ms: None,
+            size_bytes: None,
+            decoded_size_bytes: None,
+            headers: None,
+            initiator_type: None,
+            initiator_url: None,
+            initiator_line: None,
+            initiator_function: None,
+            priority: None,
+            mime_type: None,
+            resource_type: None,
+            timing: None,
+            failed: false,
+            error_text: None,
+        }
+    }
+
+    #[test]
+    fn bundle_round_trip_imports_under_new_id() {
+        let (db, session_id) = seeded_db();
+        db.store_annotation(&Annotation {
+            id: None,
+            session_id: session_id.clone(),
+            timestamp: 1500,
+            label: "tapped checkout".into(),
+            color: None,
+        })
+        .unwrap();
+        let bundle = db.export_session_bundle(&session_id).unwrap();
+
+        let imported = db.import_session_bundle(&bundle).unwrap();
+        assert_ne!(imported.id, session_id);
+
+        let metrics = db
+            .get_metrics(&imported.id, None, None, None, None)
+            .unwrap();
+        assert_eq!(metrics.len(), 1);
+
+        let annotations = db.get_annotations(&imported.id).unwrap();
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].label, "tapped checkout");
+        assert_eq!(db.get_annotations(&session_id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn bundle_streams_across_pages() {
+        let (db, session_id) = seeded_db();
+        db.update_session_name(&session_id, Some("line one\nsaid \"hi\"\\"))
+            .unwrap();
+        for i in 0..BUNDLE_PAGE_SIZE {
+            db.store_metric(&StoredMetric {
+                id: None,
+                session_id: session_id.clone(),
+                timestamp: 2000 + i,
+                metric_type: MetricType::Performance,
+                data: "{}".into(),
+            })
+            .unwrap();
+        }
+
+        let bundle = db.write_session_bundle(&session_id, Vec::new()).unwrap();
+        let imported = db
+            .import_session_bundle(std::str::from_utf8(&bundle).unwrap())
+            .unwrap();
+        assert_eq!(
+            imported.display_name.as_deref(),
+            Some("line one\nsaid \"hi\"\\")
+        );
+
+        let metrics = db
+            .get_metrics(&imported.id, None, None, None, None)
+            .unwrap();
+        assert_eq!(metrics.len() as i64, BUNDLE_PAGE_SIZE + 1);
+    }
+
+    #[test]
+    fn bundle_with_flipped_byte_is_rejected() {
+        let (db, session_id) = seeded_db();
+        let bundle = db.export_session_bundle(&session_id).unwrap();
+
+        let mut parsed: SessionBundle = serde_json::from_str(&bundle).unwrap();
+        parsed.content = parsed.content.replacen("42", "43", 1);
+        let tampered = serde_json::to_string(&parsed).unwrap();
+
+        let result = db.import_session_bundle(&tampered);
+        assert!(matches!(result, Err(StorageError::ChecksumMismatch)));
+        assert_eq!(db.list_sessions(None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn bundle_with_unknown_version_is_rejected() {
+        let (db, session_id) = seeded_db();
+        let bundle = db.export_session_bundle(&session_id).unwrap();
+
+        let mut parsed: SessionBundle = serde_json::from_str(&bundle).unwrap();
+        parsed.format_version = BUNDLE_FORMAT_VERSION + 1;
+        let future = serde_json::to_string(&parsed).unwrap();
+
+        let result = db.import_session_bundle(&future);
+        assert!(matches!(
+            result,
+            Err(StorageError::UnsupportedBundleVersion(_))
+        ));
+    }
+
+    #[test]
+    fn har_export_keeps_unfinished_requests() {
+        let (db, session_id) = seeded_db();
+        for (id, response_time) in [("done", Some(1250)), ("pending", None)] {
+            db.store_network_request(&StoredNetworkRequest {
+                status_code: response_time.map(|_| 200),
+                response_time,
+                duration_ms: response_time.map(|t| (t - 1000) as f64),
+                wall_time: Some(1_760_000_000_000),
+                ..request(&session_id, id)
+            })
+            .unwrap();
+        }
+
+        let har: serde_json::Value =
+            serde_json::from_str(&db.export_har(&session_id).unwrap()).unwrap();
+        let entries = har["log"]["entries"].as_array().unwrap();
+        assert_eq!(har["log"]["version"], "1.2");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["startedDateTime"], "2025-10-09T08:53:20.000Z");
+
+        let time_of = |id: &str| {
+            entries
+                .iter()
+                .find(|e| e["request"]["url"].as_str().unwrap().ends_with(id))
+                .unwrap()["time"]
+                .as_f64()
+                .unwrap()
+        };
+        assert_eq!(time_of("done"), 250.0);
+        assert_eq!(time_of("pending"), -1.0);
+    }
+
+    #[test]
+    fn aggregate_metrics_computes_percentiles_per_bucket() {
+        let db = Database::in_memory().unwrap();
+        let session = Session::new("device-1".into(), None, None, None, None);
+        db.create_session(&session).unwrap();
+        for i in 1..=100 {
+            db.store_metric(&StoredMetric {
+                id: None,
+                session_id: session.id.clone(),
+                timestamp: i,
+                metric_type: MetricType::Performance,
+                data: format!("{{\"js_heap_used_size\":{}}}", i),
+            })
+            .unwrap();
+        }
+
+        let overall = db
+            .aggregate_metrics(
+                &session.id,
+                MetricType::Performance,
+                "js_heap_used_size",
+                None,
+            )
+            .unwrap();
+        assert_eq!(overall.buckets.len(), 1);
+        let stats = &overall.buckets[0];
+        assert_eq!(stats.count, 100);
+        assert_eq!(stats.min, Some(1.0));
+        assert_eq!(stats.max, Some(100.0));
+        assert_eq!(stats.mean, Some(50.5));
+        assert_eq!(stats.p50, Some(50.0));
+        assert_eq!(stats.p90, Some(90.0));
+        assert_eq!(stats.p95, Some(95.0));
+        assert_eq!(stats.p99, Some(99.0));
+
+        let halves = db
+            .aggregate_metrics(
+                &session.id,
+                MetricType::Performance,
+                "js_heap_used_size",
+                Some(2),
+            )
+            .unwrap();
+        assert_eq!(halves.buckets.len(), 2);
+        assert_eq!(halves.buckets[0].max, Some(50.0));
+        assert_eq!(halves.buckets[1].min, Some(51.0));
+        assert_eq!(halves.buckets[1].end_time, 100);
+
+        let missing = db
+            .aggregate_metrics(&session.id, MetricType::Performance, "dom_nodes", None)
+            .unwrap();
+        assert!(missing.buckets.is_empty());
+    }
+
+    #[test]
+    fn compare_sessions_reports_deltas_and_rates() {
+        let db = Database::in_memory().unwrap();
+        let mut ids = Vec::new();
+        for (heap, duration_ms) in [(100.0, 60_000), (150.0, 120_000)] {
+            let mut session = Session::new("device-1".into(), None, None, None, None);
+            session.started_at = 0;
+            db.create_session(&session).unwrap();
+            db.end_session(&session.id, duration_ms).unwrap();
+            db.store_metric(&StoredMetric {
+                id: None,
+                session_id: session.id.clone(),
+                timestamp: 1000,
+                metric_type: MetricType::Performance,
+                data: format!("{{\"js_heap_used_size\":{}}}", heap),
+            })
+            .unwrap();
+            for i in 0..10 {
+                db.store_network_request(&StoredNetworkRequest {
+                    url: "https://example.com/".into(),
+                    response_time: Some(1100),
+                    duration_ms: Some(100.0),
+                    size_bytes: Some(1000.0),
+                    ..request(&session.id, &format!("{}-{}", session.id, i))
+                })
+                .unwrap();
+            }
+            ids.push(session.id);
+        }
+
+        let comparison = db.compare_sessions(&ids[0], &ids[1]).unwrap();
+        assert_eq!(comparison.mean_js_heap.absolute, Some(50.0));
+        assert_eq!(comparison.mean_js_heap.percent, Some(50.0));
+        assert_eq!(comparison.request_count.absolute, Some(0.0));
+        // Same request count over twice the time halves the rate
+        assert_eq!(comparison.requests_per_minute.a, Some(10.0));
+        assert_eq!(comparison.requests_per_minute.percent, Some(-50.0));
+        assert_eq!(comparison.peak_dom_nodes.percent, None);
+
+        assert!(db.compare_sessions(&ids[0], "missing").is_err());
+    }
+
+    #[test]
+    fn csv_export_escapes_fields_and_keeps_stable_header() {
+        let (db, session_id) = seeded_db();
+        db.store_network_request(&StoredNetworkRequest {
+            url: "https://example.com/a,b?q=\"x\"".into(),
+            ..request(&session_id, "r1")
+        })
+        .unwrap();
+
+        let network = db
+            .export_session_csv(&session_id, CsvExportKind::Network)
+            .unwrap();
+        let lines: Vec<&str> = network.split("\r\n").collect();
+        assert!(lines[0].starts_with("id,session_id,url,"));
+        assert!(lines[1].contains(",\"https://example.com/a,b?q=\"\"x\"\"\",GET,200,1000,"));
+
+        let metrics = db
+            .export_session_csv(&session_id, CsvExportKind::Metrics)
+            .unwrap();
+        let lines: Vec<&str> = metrics.split("\r\n").collect();
+        assert!(lines[0].starts_with("timestamp,"));
+        assert!(lines[0].contains("js_heap_used_size"));
+        let dom_column = lines[0].split(',').position(|c| c == "dom_nodes").unwrap();
+        assert_eq!(lines[1].split(',').nth(dom_column), Some("42"));
+    }
+
+    fn db_with_sessions_started_at(times: &[i64]) -> Database {
+        let db = Database::in_memory().unwrap();
+        for &started_at in times {
+            let mut session = Session::new("device-1".into(), None, None, None, None);
+            session.started_at = started_at;
+            db.create_session(&session).unwrap();
+        }
+        db
+    }
+
+    fn search_started_at(db: &Database, after: Option<i64>, before: Option<i64>) -> Vec<i64> {
+        let filter = SessionSearchFilter {
+            started_after: after,
+            started_before: before,
+            ..Default::default()
+        };
+        db.search_sessions(&filter)
+            .unwrap()
+            .iter()
+            .map(|s| s.started_at)
+            .collect()
+    }
+
+    #[test]
+    fn search_date_range_bounds_are_inclusive() {
+        let db = db_with_sessions_started_at(&[999, 1000, 2000, 2001]);
+
+        assert_eq!(
+            search_started_at(&db, Some(1000), None),
+            vec![2001, 2000, 1000]
+        );
+        assert_eq!(
+            search_started_at(&db, None, Some(2000)),
+            vec![2000, 1000, 999]
+        );
+        assert_eq!(
+            search_started_at(&db, Some(1000), Some(2000)),
+            vec![2000, 1000]
+        );
+        assert_eq!(search_started_at(&db, Some(2000), Some(2000)), vec![2000]);
+    }
+
+    #[test]
+    fn search_date_range_combines_with_other_filters() {
+        let db = db_with_sessions_started_at(&[1000, 2000]);
+        let mut other = Session::new("device-2".into(), None, None, None, None);
+        other.started_at = 1500;
+        db.create_session(&other).unwrap();
+
+        let filter = SessionSearchFilter {
+            device_id: Some("device-2".into()),
+            started_after: Some(1000),
+            started_before: Some(2000),
+            ..Default::default()
+        };
+        let found = db.search_sessions(&filter).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, other.id);
+
+        assert!(search_started_at(&db, Some(2001), None).is_empty());
+        assert!(search_started_at(&db, Some(2000), Some(1000)).is_empty());
+    }
+
+    #[test]
+    fn count_sessions_ignores_limit() {
+        let db = db_with_sessions_started_at(&[1000, 2000, 3000]);
+        let filter = SessionSearchFilter {
+            started_after: Some(2000),
+            limit: Some(1),
+            ..Default::default()
+        };
+
+        assert_eq!(db.search_sessions(&filter).unwrap().len(), 1);
+        assert_eq!(db.count_sessions(&filter).unwrap(), 2);
+    }
+
+    #[test]
+    fn get_metrics_filters_by_multiple_types() {
+        let db = Database::in_memory().unwrap();
+        let session = Session::new("device-1".into(), None, None, None, None);
+        db.create_session(&session).unwrap();
+        for (timestamp, metric_type) in [
+            (1, MetricType::Performance),
+            (2, MetricType::Memory),
+            (3, MetricType::WebVitals),
+            (4, MetricType::Performance),
+        ] {
+            db.store_metric(&StoredMetric {
+                id: None,
+                session_id: session.id.clone(),
+                timestamp,
+                metric_type,
+                data: "{}".into(),
+            })
+            .unwrap();
+        }
+
+        let types = [MetricType::Performance, MetricType::WebVitals];
+        let metrics = db
+            .get_metrics(&session.id, Some(&types), Some(2), None, None)
+            .unwrap();
+        let timestamps: Vec<i64> = metrics.iter().map(|m| m.timestamp).collect();
+        assert_eq!(timestamps, vec![3, 4]);
+
+        let single = db
+            .get_metrics(&session.id, Some(&[MetricType::Memory]), None, None, None)
+            .unwrap();
+        assert_eq!(single.len(), 1);
+
+        let all = db
+            .get_metrics(&session.id, Some(&[]), None, None, None)
+            .unwrap();
+        assert_eq!(all.len(), 4);
+
+        // Samples sharing a timestamp keep insertion order across types
+        for metric_type in [MetricType::WebVitals, MetricType::Performance] {
+            db.store_metric(&StoredMetric {
+                id: None,
+                session_id: session.id.clone(),
+                timestamp: 5,
+                metric_type,
+                data: "{}".into(),
+            })
+            .unwrap();
+        }
+        let tied = db
+            .get_metrics(&session.id, Some(&types), Some(5), None, None)
+            .unwrap();
+        let tied_types: Vec<MetricType> = tied.into_iter().map(|m| m.metric_type).collect();
+        assert_eq!(
+            tied_types,
+            vec![MetricType::WebVitals, MetricType::Performance]
+        );
+    }
+
+    #[test]
+    fn prune_old_sessions_cascades_and_keeps_recent_and_active() {
+        let (db, recent_id) = seeded_db();
+
+        let mut old = Session::new("device-1".into(), None, None, None, None);
+        old.started_at = 1_000;
+        old.ended_at = Some(2_000);
+        old.status = SessionStatus::Completed;
+        db.create_session(&old).unwrap();
+        db.store_metric(&StoredMetric {
+            id: None,
+            session_id: old.id.clone(),
+            timestamp: 1_500,
+            metric_type: MetricType::Performance,
+            data: "{}".into(),
+        })
+        .unwrap();
+        db.store_marker(&SessionMarker::new(&old.id, MarkerKind::CollectionStarted))
+            .unwrap();
+
+        let mut stale_active = Session::new("device-1".into(), None, None, None, None);
+        stale_active.started_at = 1_000;
+        db.create_session(&stale_active).unwrap();
+
+        let result = db.prune_old_sessions(10_000).unwrap();
+        assert_eq!(result.sessions_deleted, 1);
+        assert_eq!(result.metrics_deleted, 1);
+        assert_eq!(result.network_requests_deleted, 0);
+        assert_eq!(result.total_rows_deleted, 3);
+
+        assert!(db.get_session(&old.id).unwrap().is_none());
+        let orphans = db.get_metrics(&old.id, None, None, None, None).unwrap();
+        assert!(orphans.is_empty());
+        assert!(db.get_session(&recent_id).unwrap().is_some());
+        assert!(db.get_session(&stale_active.id).unwrap().is_some());
+
+        db.vacuum().unwrap();
+    }
+
+    #[test]
+    fn get_network_requests_filters_by_resource_type_and_url() {
+        let (db, session_id) = seeded_db();
+        for (i, (url, resource_type)) in [
+            ("https://example.com/", "document"),
+            ("https://example.com/app.js", "script"),
+            ("https://api.example.com/users", "xhr"),
+            ("https://api.example.com/orders", "fetch"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            db.store_network_request(&StoredNetworkRequest {
+                url: url.into(),
+                request_time: 1000 + i as i64,
+                resource_type: Some(resource_type.into()),
+                ..request(&session_id, &format!("r{}", i))
+            })
+            .unwrap();
+        }
+
+        let api = NetworkRequestFilter {
+            resource_types: Some(vec!["XHR".into(), "fetch".into()]),
+            url_contains: None,
+            status_class: None,
+        };
+        let ids: Vec<String> = db
+            .get_network_requests(&session_id, &api, None)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(ids, vec!["r2", "r3"]);
+
+        let orders = NetworkRequestFilter {
+            url_contains: Some("/orders".into()),
+            ..api
+        };
+        let requests = db.get_network_requests(&session_id, &orders, None).unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].resource_type.as_deref(), Some("fetch"));
+
+        let all = db
+            .get_network_requests(&session_id, &NetworkRequestFilter::default(), None)
+            .unwrap();
+        assert_eq!(all.len(), 4);
+    }
+
+    #[test]
+    fn session_metadata_round_trips() {
+        let (db, session_id) = seeded_db();
+        let metadata = HashMap::from([
+            ("build".to_string(), serde_json::json!(1234)),
+            ("git_sha".to_string(), serde_json::json!("abc123")),
+        ]);
+
+        db.update_session_metadata(&session_id, Some(&metadata))
+            .unwrap();
+        let session = db.get_session(&session_id).unwrap().unwrap();
+        assert_eq!(session.metadata, Some(metadata));
+
+        let json = serde_json::to_value(&session).unwrap();
+        assert_eq!(json["metadata"]["git_sha"], "abc123");
+
+        db.update_session_metadata(&session_id, None).unwrap();
+        let cleared = db.get_session(&session_id).unwrap().unwrap();
+        assert_eq!(cleared.metadata, None);
+
+        assert!(matches!(
+            db.update_session_metadata("missing", None),
+            Err(StorageError::SessionNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn bulk_tag_operations_keep_other_tags() {
+        let (db, session_id) = seeded_db();
+        db.update_session_tags(&session_id, Some(&["baseline".to_string()]))
+            .unwrap();
+        let ids = vec![session_id.clone(), "missing".to_string()];
+
+        let added = db.add_tag_to_sessions(&ids, "release").unwrap();
+        assert_eq!(added.succeeded, vec![session_id.clone()]);
+        assert_eq!(added.failed, vec!["missing".to_string()]);
+        db.add_tag_to_sessions(&ids, "release").unwrap();
+        let session = db.get_session(&session_id).unwrap().unwrap();
+        assert_eq!(
+            session.tags,
+            Some(vec!["baseline".to_string(), "release".to_string()])
+        );
+
+        db.remove_tag_from_sessions(&ids, "baseline").unwrap();
+        let session = db.get_session(&session_id).unwrap().unwrap();
+        assert_eq!(session.tags, Some(vec!["release".to_string()]));
+
+        let deleted = db.delete_sessions(&ids).unwrap();
+        assert_eq!(deleted.succeeded, vec![session_id.clone()]);
+        assert_eq!(deleted.failed, vec!["missing".to_string()]);
+        assert!(db.get_session(&session_id).unwrap().is_none());
+    }
+
+    #[test]
+    fn request_alert_round_trips() {
+        let (db, session_id) = seeded_db();
+        let thresholds = crate::storage::AlertThresholds {
+            max_request_duration_ms: Some(500.0),
+            ..Default::default()
+        };
+        assert!(thresholds
+            .check_request(&session_id, "r1", "https://example.com/fast", 120.0)
+            .is_none());
+
+        let alert = thresholds
+            .check_request(&session_id, "r2", "https://example.com/slow", 900.0)
+            .unwrap();
+        db.store_alert(&alert).unwrap();
+
+        let alerts = db.get_alerts(&session_id).unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, crate::storage::AlertKind::RequestDuration);
+        assert_eq!(alerts[0].value, 900.0);
+        assert_eq!(alerts[0].threshold, 500.0);
+        assert_eq!(alerts[0].request_id.as_deref(), Some("r2"));
+    }
+
+    #[test]
+    fn network_request_pages_break_ties_by_id() {
+        let (db, session_id) = seeded_db();
+        // Five requests sharing two timestamps, inserted out of id order
+        for (id, request_time) in [
+            ("c", 1000),
+            ("a", 1000),
+            ("e", 2000),
+            ("b", 1000),
+            ("d", 2000),
+        ] {
+            db.store_network_request(&StoredNetworkRequest {
+                request_time,
+                ..request(&session_id, id)
+            })
+            .unwrap();
+        }
+
+        let filter = NetworkRequestFilter::default();
+        let mut ids = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = db
+                .get_network_requests_page(&session_id, &filter, cursor.as_ref(), 2)
+                .unwrap();
+            ids.extend(page.requests.into_iter().map(|r| r.id));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(ids, vec!["a", "b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn initiator_stack_frame_round_trips() {
+        let (db, session_id) = seeded_db();
+        db.store_network_request(&StoredNetworkRequest {
+            url: "https://api.example.com/track".into(),
+            method: Some("POST".into()),
+            status_code: None,
+            initiator_type: Some("script".into()),
+            initiator_url: Some("https://cdn.example.com/analytics.js".into()),
+            initiator_line: Some(41),
+            initiator_function: Some("sendBeacon".into()),
+            resource_type: Some("xhr".into()),
+            ..request(&session_id, "xhr-1")
+        })
+        .unwrap();
+
+        let requests = db
+            .get_network_requests(&session_id, &NetworkRequestFilter::default(), None)
+            .unwrap();
+        assert_eq!(requests[0].initiator_line, Some(41));
+        assert_eq!(
+            requests[0].initiator_function.as_deref(),
+            Some("sendBeacon")
+        );
+        assert!(!requests[0].failed);
+    }
+
+    #[test]
+    fn network_summary_splits_wire_and_decoded_bytes() {
+        let (db, session_id) = seeded_db();
+        for (id, resource_type, wire, decoded) in [
+            ("r1", "script", 10_000.0, Some(40_000.0)),
+            ("r2", "script", 5_000.0, Some(20_000.0)),
+            ("r3", "xhr", 2_000.0, None),
+        ] {
+            db.store_network_request(&StoredNetworkRequest {
+                response_time: Some(1100),
+                duration_ms: Some(100.0),
+                size_bytes: Some(wire),
+                decoded_size_bytes: decoded,
+                resource_type: Some(resource_type.into()),
+                ..request(&session_id, id)
+            })
+            .unwrap();
+        }
+
+        let summary = db.get_network_summary(&session_id, None).unwrap();
+        assert_eq!(summary.total_bytes, 17_000.0);
+        assert_eq!(summary.total_decoded_bytes, 60_000.0);
+
+        let scripts = &summary.by_resource_type["script"];
+        assert_eq!(scripts.requests, 2);
+        assert_eq!(scripts.wire_bytes, 15_000.0);
+        assert_eq!(scripts.decoded_bytes, 60_000.0);
+        assert_eq!(summary.by_resource_type["xhr"].decoded_bytes, 0.0);
+    }
+
+    #[test]
+    fn console_logs_filter_by_level_and_prune_oldest() {
+        let (db, session_id) = seeded_db();
+        for (i, level) in [
+            ConsoleLevel::Info,
+            ConsoleLevel::Error,
+            ConsoleLevel::Warning,
+            ConsoleLevel::Error,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            db.store_console_log(&ConsoleLog {
+                id: None,
+                session_id: session_id.clone(),
+                timestamp: 1000 + i as i64,
+                level,
+                text: format!("message {}", i),
+                url: None,
+                line: None,
+            })
+            .unwrap();
+        }
+
+        let errors = db
+            .get_console_logs(&session_id, Some(&[ConsoleLevel::Error]))
+            .unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            db.get_console_logs(&session_id, Some(&[])).unwrap().len(),
+            4
+        );
+
+        assert_eq!(db.prune_console_logs(&session_id, 2).unwrap(), 2);
+        let texts: Vec<String> = db
+            .get_console_logs(&session_id, None)
+            .unwrap()
+            .into_iter()
+            .map(|log| log.text)
+            .collect();
+        assert_eq!(texts, vec!["message 2", "message 3"]);
+    }
+
+    #[test]
+    fn migrate_upgrades_legacy_schema_once() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sessions (
+                id TEXT PRIMARY KEY,
+                device_id TEXT NOT NULL,
+                device_name TEXT,
+                webview_url TEXT,
+                package_name TEXT,
+                target_title TEXT,
+                started_at INTEGER NOT NULL,
+                ended_at INTEGER,
+                status TEXT NOT NULL DEFAULT 'active',
+                metadata TEXT
+            );
+            CREATE TABLE network_requests (
+                id TEXT PRIMARY KEY,
+                session_id TEXT NOT NULL,
+                url TEXT NOT NULL,
+                method TEXT,
+                status_code INTEGER,
+                request_time INTEGER NOT NULL,
+                response_time INTEGER,
+                duration_ms REAL,
+                size_bytes REAL,
+                headers TEXT
+            );",
+        )
+        .unwrap();
+        let db = Database {
+            conn: Mutex::new(conn),
+            path: None,
+            compress_metrics: AtomicBool::new(false),
+        };
+        db.initialize().unwrap();
+
+        let latest = MIGRATIONS.last().unwrap().version;
+        assert_eq!(db.schema_version().unwrap(), latest);
+
+        let mut session = Session::new("device-1".into(), None, None, None, None);
+        session.tags = Some(vec!["legacy".into()]);
+        db.create_session(&session).unwrap();
+        let loaded = db.get_session(&session.id).unwrap().unwrap();
+        assert_eq!(loaded.tags, Some(vec!["legacy".to_string()]));
+
+        db.migrate().unwrap();
+        let applied: i64 = db
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM schema_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(applied, MIGRATIONS.len() as i64);
+    }
+
+    #[test]
+    fn abort_session_records_reason_in_metadata() {
+        let (db, session_id) = seeded_db();
+        let metadata = HashMap::from([("build".to_string(), serde_json::json!(7))]);
+        db.update_session_metadata(&session_id, Some(&metadata))
+            .unwrap();
+
+        assert!(db
+            .abort_session(&session_id, 5000, Some("connection lost"))
+            .unwrap());
+        // Already finished: no-op, reason and ended_at are kept
+        assert!(!db.abort_session(&session_id, 9000, Some("other")).unwrap());
+
+        let session = db.get_session(&session_id).unwrap().unwrap();
+        assert_eq!(session.status, SessionStatus::Aborted);
+        assert_eq!(session.ended_at, Some(5000));
+        let metadata = session.metadata.unwrap();
+        assert_eq!(metadata["abort_reason"], "connection lost");
+        assert_eq!(metadata["build"], 7);
+
+        let filter = SessionSearchFilter {
+            status: Some("aborted".into()),
+            ..Default::default()
+        };
+        assert_eq!(db.search_sessions(&filter).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn smoothed_metrics_drop_outliers_and_average() {
+        let db = Database::in_memory().unwrap();
+        let session = Session::new("device-1".into(), None, None, None, None);
+        db.create_session(&session).unwrap();
+        for (i, heap) in [10.0, 20.0, 10.0, 20.0, 10.0, 20.0, 10.0, 20.0, 500.0]
+            .iter()
+            .enumerate()
+        {
+            db.store_metric(&StoredMetric {
+                id: None,
+                session_id: session.id.clone(),
+                timestamp: i as i64 * 1000,
+                metric_type: MetricType::Performance,
+                data: format!("{{\"js_heap_used_size\":{}}}", heap),
+            })
+            .unwrap();
+        }
+
+        let options = SmoothingOptions {
+            method: SmoothingMethod::MovingAverage,
+            window: 2,
+            outlier_k: Some(2.0),
+        };
+        let series = db
+            .smoothed_metrics(
+                &session.id,
+                MetricType::Performance,
+                "js_heap_used_size",
+                &options,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(series.outliers_dropped, 1);
+        assert_eq!(series.points.len(), 8);
+        assert_eq!(series.points[0].smoothed, 10.0);
+        assert!(series.points[1..].iter().all(|p| p.smoothed == 15.0));
+        assert_eq!(series.points[1].raw, 20.0);
+
+        let ema = SmoothingOptions {
+            method: SmoothingMethod::Ema,
+            window: 3,
+            outlier_k: None,
+        };
+        let series = db
+            .smoothed_metrics(
+                &session.id,
+                MetricType::Performance,
+                "js_heap_used_size",
+                &ema,
+                Some(0),
+                Some(1000),
+            )
+            .unwrap();
+        let smoothed: Vec<f64> = series.points.iter().map(|p| p.smoothed).collect();
+        assert_eq!(smoothed, vec![10.0, 15.0]);
+    }
+
+    #[test]
+    fn request_timing_round_trips_and_feeds_har() {
+        let (db, session_id) = seeded_db();
+        let timing = RequestTiming {
+            dns_ms: None,
+            connect_ms: Some(20.0),
+            ssl_ms: Some(30.0),
+            send_ms: Some(1.0),
+            wait_ms: Some(40.0),
+            download_ms: Some(9.0),
+        };
+        for (id, timing) in [("r1", Some(timing.clone())), ("cached", None)] {
+            db.store_network_request(&StoredNetworkRequest {
+                response_time: Some(1100),
+                duration_ms: Some(100.0),
+                size_bytes: Some(10.0),
+                timing,
+                ..request(&session_id, id)
+            })
+            .unwrap();
+        }
+
+        let requests = db
+            .get_network_requests(&session_id, &NetworkRequestFilter::default(), None)
+            .unwrap();
+        let by_id = |id: &str| requests.iter().find(|r| r.id == id).unwrap();
+        assert_eq!(by_id("r1").timing, Some(timing));
+        assert_eq!(by_id("cached").timing, None);
+
+        let har: serde_json::Value =
+            serde_json::from_str(&db.export_har(&session_id).unwrap()).unwrap();
+        let entries = har["log"]["entries"].as_array().unwrap();
+        let entry = entries
+            .iter()
+            .find(|e| e["request"]["url"] == "https://example.com/r1")
+            .unwrap();
+        let timings = &entry["timings"];
+        assert_eq!(timings["dns"], -1.0);
+        assert_eq!(timings["connect"], 50.0);
+        assert_eq!(timings["ssl"], 30.0);
+        assert_eq!(timings["blocked"], 0.0);
+        assert_eq!(timings["receive"], 9.0);
+    }
+
+    #[test]
+    fn list_all_tags_counts_sessions_per_tag() {
+        let db = Database::in_memory().unwrap();
+        for tags in [
+            Some(vec!["release", "pixel"]),
+            Some(vec!["release", "release"]),
+            Some(vec!["baseline"]),
+            None,
+        ] {
+            let session = Session::new("device-1".into(), None, None, None, None);
+            db.create_session(&session).unwrap();
+            let tags: Option<Vec<String>> = tags.map(|t| t.into_iter().map(String::from).collect());
+            db.update_session_tags(&session.id, tags.as_deref())
+                .unwrap();
+        }
+
+        let tags = db.list_all_tags().unwrap();
+        let pairs: Vec<(&str, i64)> = tags.iter().map(|t| (t.tag.as_str(), t.count)).collect();
+        assert_eq!(pairs, vec![("release", 2), ("baseline", 1), ("pixel", 1)]);
+    }
+
+    #[test]
+    fn search_sessions_filters_by_duration() {
+        let db = db_with_sessions_started_at(&[1000, 2000, 3000]);
+        let sessions = db.search_sessions(&SessionSearchFilter::default()).unwrap();
+        // 1000 runs for 10s, 2000 for 5 minutes, 3000 is still active
+        for session in &sessions {
+            let ended_at = match session.started_at {
+                1000 => 11_000,
+                2000 => 302_000,
+                _ => continue,
+            };
+            db.end_session(&session.id, ended_at).unwrap();
+        }
+
+        let filter = SessionSearchFilter {
+            min_duration_ms: Some(60_000),
+            ..Default::default()
+        };
+        let found = db.search_sessions(&filter).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].started_at, 2000);
+
+        let filter = SessionSearchFilter {
+            max_duration_ms: Some(60_000),
+            ..Default::default()
+        };
+        let found = db.search_sessions(&filter).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].started_at, 1000);
+        assert_eq!(db.count_sessions(&filter).unwrap(), 1);
+    }
+
+    #[test]
+    fn response_bodies_round_trip_and_respect_cap() {
+        let (db, session_id) = seeded_db();
+        let body = |request_id: &str, size_bytes: i64| ResponseBody {
+            id: None,
+            session_id: session_id.clone(),
+            request_id: request_id.to_string(),
+            url: "https://example.com/api".to_string(),
+            timestamp: 1000,
+            size_bytes,
+            base64_encoded: true,
+            body: "AAEC".to_string(),
+        };
+
+        db.store_response_body(&body("1", 3), 3).unwrap();
+        assert!(matches!(
+            db.store_response_body(&body("2", 4), 3),
+            Err(StorageError::BodyTooLarge { size: 4, max: 3 })
+        ));
+
+        let stored = db.get_response_body(&session_id, "1").unwrap().unwrap();
+        assert!(stored.base64_encoded);
+        assert_eq!(stored.body, "AAEC");
+        assert!(db.get_response_body(&session_id, "2").unwrap().is_none());
+    }
+
+    #[test]
+    fn network_by_host_groups_and_sorts_by_bytes() {
+        let (db, session_id) = seeded_db();
+        for (id, url, bytes, duration) in [
+            ("r1", "https://api.example.com/users", 1_000.0, Some(100.0)),
+            (
+                "r2",
+                "https://cdn.example.com/app.js",
+                30_000.0,
+                Some(200.0),
+            ),
+            ("r3", "https://cdn.example.com/app.css", 10_000.0, None),
+            ("r4", "data:image/png;base64,AAAA", 0.0, None),
+        ] {
+            db.store_network_request(&StoredNetworkRequest {
+                url: url.into(),
+                response_time: Some(1100),
+                duration_ms: duration,
+                size_bytes: Some(bytes),
+                ..request(&session_id, id)
+            })
+            .unwrap();
+        }
+
+        let hosts = db.network_by_host(&session_id).unwrap();
+        let names: Vec<&str> = hosts.iter().map(|h| h.host.as_str()).collect();
+        assert_eq!(names, ["cdn.example.com", "api.example.com", "(inline)"]);
+        assert_eq!(hosts[0].requests, 2);
+        assert_eq!(hosts[0].total_bytes, 40_000.0);
+        assert_eq!(hosts[0].avg_duration_ms, Some(200.0));
+        assert_eq!(hosts[2].avg_duration_ms, None);
+    }
+
+    #[test]
+    fn annotations_round_trip_in_timeline_order() {
+        let (db, session_id) = seeded_db();
+        for (timestamp, label) in [(2000, "tapped checkout"), (1000, "opened cart")] {
+            db.store_annotation(&Annotation {
+                id: None,
+                session_id: session_id.clone(),
+                timestamp,
+                label: label.into(),
+                color: Some("#f59e0b".into()),
+            })
+            .unwrap();
+        }
+
+        let annotations = db.get_annotations(&session_id).unwrap();
+        let labels: Vec<&str> = annotations.iter().map(|a| a.label.as_str()).collect();
+        assert_eq!(labels, ["opened cart", "tapped checkout"]);
+
+        let id = annotations[0].id.unwrap();
+        db.delete_annotation(id).unwrap();
+        assert_eq!(db.get_annotations(&session_id).unwrap().len(), 1);
+        assert!(matches!(
+            db.delete_annotation(id),
+            Err(StorageError::AnnotationNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn find_duplicate_requests_counts_wasted_bytes() {
+        let (db, session_id) = seeded_db();
+        for (id, method, url, time) in [
+            ("r1", "GET", "https://example.com/config.json?v=1", 1000),
+            ("r2", "GET", "https://example.com/config.json?v=2", 2000),
+            ("r3", "GET", "https://example.com/config.json?v=2", 3000),
+            ("r4", "POST", "https://example.com/config.json?v=2", 4000),
+        ] {
+            db.store_network_request(&StoredNetworkRequest {
+                url: url.into(),
+                method: Some(method.into()),
+                request_time: time,
+                response_time: Some(time + 100),
+                duration_ms: Some(100.0),
+                size_bytes: Some(500.0),
+                ..request(&session_id, id)
+            })
+            .unwrap();
+        }
+
+        let exact = db.find_duplicate_requests(&session_id, None).unwrap();
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].request_ids, ["r2", "r3"]);
+        assert_eq!(exact[0].wasted_bytes, 500.0);
+
+        let stripped = db
+            .find_duplicate_requests(&session_id, Some(&UrlNormalizer::default()))
+            .unwrap();
+        assert_eq!(stripped.len(), 1);
+        assert_eq!(stripped[0].url, "https://example.com/config.json");
+        assert_eq!(stripped[0].count, 3);
+        assert_eq!(stripped[0].total_bytes, 1500.0);
+        assert_eq!(stripped[0].wasted_bytes, 1000.0);
+    }
+
+    #[test]
+    fn metrics_ndjson_writes_one_metric_per_line() {
+        let (db, session_id) = seeded_db();
+        db.store_metric(&StoredMetric {
+            id: None,
+            session_id: session_id.clone(),
+            timestamp: 500,
+            metric_type: MetricType::Memory,
+            data: "{\"line\":\"a\\nb\"}".into(),
+        })
+        .unwrap();
+
+        let mut out = Vec::new();
+        let count = db.write_metrics_ndjson(&session_id, &mut out).unwrap();
+        assert_eq!(count, 2);
+
+        let lines: Vec<StoredMetric> = std::str::from_utf8(&out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].timestamp, 500);
+        assert_eq!(lines[1].metric_type, MetricType::Performance);
+
+        // Spans several pages without repeating or dropping rows
+        for i in 0..BUNDLE_PAGE_SIZE {
+            db.store_metric(&StoredMetric {
+                id: None,
+                session_id: session_id.clone(),
+                timestamp: 500 + i % 3,
+                metric_type: MetricType::Performance,
+                data: "{}".into(),
+            })
+            .unwrap();
+        }
+        let mut out = Vec::new();
+        let count = db.write_metrics_ndjson(&session_id, &mut out).unwrap();
+        assert_eq!(count, BUNDLE_PAGE_SIZE + 2);
+        let ids: std::collections::HashSet<i64> = std::str::from_utf8(&out)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<StoredMetric>(line)
+                    .unwrap()
+                    .id
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(ids.len() as i64, count);
+
+        assert!(matches!(
+            db.write_metrics_ndjson("missing", Vec::new()),
+            Err(StorageError::SessionNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn compressed_metrics_read_back_and_aggregate() {
+        let db = Database::in_memory().unwrap();
+        let session = Session::new("device-1".into(), None, None, None, None);
+        db.create_session(&session).unwrap();
+        // A typical sample; rows too small to shrink stay plain
+        let data = |i: i64| {
+            format!(
+                "{{\"timestamp\":{},\"js_heap_used_size\":{},\"js_heap_total_size\":null,\
+                 \"dom_nodes\":null,\"layout_count\":null,\"script_duration\":null,\
+                 \"task_duration\":null,\"js_event_listeners\":null,\"documents\":null,\
+                 \"frames\":null,\"recalc_style_count\":null,\"extras\":{{}}}}",
+                i, i
+            )
+        };
+        let store = |i: i64| {
+            db.store_metric(&StoredMetric {
+                id: None,
+                session_id: session.id.clone(),
+                timestamp: i,
+                metric_type: MetricType::Performance,
+                data: data(i),
+            })
+            .unwrap();
+        };
+
+        // Half written plain, half compressed on insert
+        for i in 1..=5 {
+            store(i);
+        }
+        db.set_metric_compression(true);
+        for i in 6..=10 {
+            store(i);
+        }
+
+        let compacted = db.compact_metrics().unwrap();
+        assert_eq!(compacted.rows_compressed, 5);
+        assert!(compacted.bytes_after < compacted.bytes_before);
+        assert_eq!(db.compact_metrics().unwrap().rows_compressed, 0);
+
+        let metrics = db.get_metrics(&session.id, None, None, None, None).unwrap();
+        let read: Vec<String> = metrics.into_iter().map(|m| m.data).collect();
+        let expected: Vec<String> = (1..=10).map(data).collect();
+        assert_eq!(read, expected);
+
+        let summary = db
+            .aggregate_metrics(
+                &session.id,
+                MetricType::Performance,
+                "js_heap_used_size",
+                None,
+            )
+            .unwrap();
+        assert_eq!(summary.buckets[0].count, 10);
+        assert_eq!(summary.buckets[0].max, Some(10.0));
+    }
+
+    #[test]
+    fn get_network_requests_filters_by_status_class() {
+        let (db, session_id) = seeded_db();
+        for (i, (status_code, error_text)) in [
+            (Some(200), None),
+            (Some(304), None),
+            (Some(404), None),
+            (Some(503), None),
+            (None, Some("net::ERR_NAME_NOT_RESOLVED")),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            db.store_network_request(&StoredNetworkRequest {
+                url: format!("https://example.com/{}", i),
+                status_code,
+                request_time: 1000 + i as i64,
+                failed: error_text.is_some(),
+                error_text: error_text.map(String::from),
+                ..request(&session_id, &format!("r{}", i))
+            })
+            .unwrap();
+        }
+
+        let ids_for = |status_class| -> Vec<String> {
+            let filter = NetworkRequestFilter {
+                status_class: Some(status_class),
+                ..Default::default()
+            };
+            db.get_network_requests(&session_id, &filter, None)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.id)
+                .collect()
+        };
+        assert_eq!(ids_for(StatusClass::Success), vec!["r0"]);
+        assert_eq!(ids_for(StatusClass::Redirect), vec!["r1"]);
+        assert_eq!(ids_for(StatusClass::ClientError), vec!["r2"]);
+        assert_eq!(ids_for(StatusClass::ServerError), vec!["r3"]);
+        assert_eq!(ids_for(StatusClass::Failed), vec!["r4"]);
+
+        let failed = NetworkRequestFilter {
+            status_class: Some(StatusClass::Failed),
+            ..Default::default()
+        };
+        let requests = db.get_network_requests(&session_id, &failed, None).unwrap();
+        assert_eq!(
+            requests[0].error_text.as_deref(),
+            Some("net::ERR_NAME_NOT_RESOLVED")
+        );
+    }
+
+    #[test]
+    fn network_throughput_fills_empty_buckets() {
+        let (db, session_id) = seeded_db();
+        assert!(db.network_throughput(&session_id, 1000).unwrap().is_empty());
+
+        for (i, (request_time, size_bytes)) in [(1_200, 100.0), (1_900, 50.0), (4_100, 10.0)]
+            .into_iter()
+            .enumerate()
+        {
+            db.store_network_request(&StoredNetworkRequest {
+                url: "https://example.com/".into(),
+                request_time,
+                size_bytes: Some(size_bytes),
+                ..request(&session_id, &format!("r{}", i))
+            })
+            .unwrap();
+        }
+
+        let buckets = db.network_throughput(&session_id, 1000).unwrap();
+        let series: Vec<(i64, i64, f64)> = buckets
+            .iter()
+            .map(|b| (b.start_time, b.request_count, b.total_bytes))
+            .collect();
+        assert_eq!(
+            series,
+            vec![
+                (1000, 2, 150.0),
+                (2000, 0, 0.0),
+                (3000, 0, 0.0),
+                (4000, 1, 10.0),
+            ]
+        );
+
+        // A long session at a fine size is coarsened instead of returning
+        // a window per millisecond
+        db.store_network_request(&StoredNetworkRequest {
+            request_time: 1_000_000_000,
+            ..request(&session_id, "late")
+        })
+        .unwrap();
+        let buckets = db.network_throughput(&session_id, 1).unwrap();
+        assert!(buckets.len() as i64 <= MAX_THROUGHPUT_BUCKETS);
+        assert_eq!(buckets.iter().map(|b| b.request_count).sum::<i64>(), 4);
+    }
+
+    #[test]
+    fn request_data_chunks_are_scoped_to_the_session() {
+        let db = Database::in_memory().unwrap();
+        let first = Session::new("device-1".into(), None, None, None, None);
+        let second = Session::new("device-1".into(), None, None, None, None);
+        db.create_session(&first).unwrap();
+        db.create_session(&second).unwrap();
+        for (session, timestamp) in [(&first, 10), (&second, 20), (&first, 30)] {
+            db.store_request_data_chunk(&RequestDataChunk {
+                id: None,
+                session_id: session.id.clone(),
+                request_id: "1000.1".into(),
+                timestamp,
+                data_length: timestamp,
+                encoded_data_length: timestamp,
+            })
+            .unwrap();
+        }
+
+        let chunks = db.get_request_data_chunks(&first.id, "1000.1").unwrap();
+        let timestamps: Vec<i64> = chunks.iter().map(|c| c.timestamp).collect();
+        assert_eq!(timestamps, vec![10, 30]);
+        assert_eq!(
+            db.get_request_data_chunks(&second.id, "1000.1")
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn recent_reads_break_timestamp_ties_by_id() {
+        let db = Database::in_memory().unwrap();
+        let session = Session::new("device-1".into(), None, None, None, None);
+        db.create_session(&session).unwrap();
+        for i in 0..4 {
+            db.store_metric(&StoredMetric {
+                id: None,
+                session_id: session.id.clone(),
+                timestamp: 100,
+                metric_type: MetricType::Performance,
+                data: format!("{{\"dom_nodes\":{}}}", i),
+            })
+            .unwrap();
+        }
+        for id in ["c", "a", "d", "b"] {
+            db.store_network_request(&StoredNetworkRequest {
+                request_time: 100,
+                ..request(&session.id, id)
+            })
+            .unwrap();
+        }
+
+        // The newest three are the highest ids, returned in id order
+        let metrics = db.get_recent_metrics(&session.id, 3).unwrap();
+        let ids: Vec<i64> = metrics.iter().filter_map(|m| m.id).collect();
+        let expected: Vec<i64> = db
+            .get_metrics(&session.id, None, None, None, None)
+            .unwrap()
+            .iter()
+            .filter_map(|m| m.id)
+            .skip(1)
+            .collect();
+        assert_eq!(ids, expected);
+
+        let requests = db.get_recent_network_requests(&session.id, 3).unwrap();
+        let ids: Vec<&str> = requests.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, ["b", "c", "d"]);
+    }
 }
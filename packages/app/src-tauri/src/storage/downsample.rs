@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use super::metrics::StoredMetric;
+
+/// Largest-Triangle-Three-Buckets: pick `threshold` of the `(x, y)` points
+/// that best preserve the visual shape of the series, peaks and valleys
+/// included. Returns indices into `points` in ascending order; the first and
+/// last points are always kept. Points must be sorted by x.
+pub fn lttb(points: &[(f64, f64)], threshold: usize) -> Vec<usize> {
+    let len = points.len();
+    // Anything below 3 can't hold both endpoints plus a bucket
+    let threshold = threshold.max(3);
+    if len <= threshold {
+        return (0..len).collect();
+    }
+
+    let mut selected = Vec::with_capacity(threshold);
+    selected.push(0);
+
+    // Interior points are split into threshold - 2 buckets
+    let bucket_size = (len - 2) as f64 / (threshold - 2) as f64;
+    let bucket_start = |i: usize| (i as f64 * bucket_size) as usize + 1;
+    let mut prev = 0;
+
+    for bucket in 0..threshold - 2 {
+        let start = bucket_start(bucket);
+        let end = bucket_start(bucket + 1).min(len - 1);
+
+        // Average of the next bucket (just the last point for the final one)
+        let next_end = bucket_start(bucket + 2).min(len);
+        let next = &points[end..next_end.max(end + 1)];
+        let avg_x = next.iter().map(|p| p.0).sum::<f64>() / next.len() as f64;
+        let avg_y = next.iter().map(|p| p.1).sum::<f64>() / next.len() as f64;
+
+        let (ax, ay) = points[prev];
+        let best = (start..end)
+            .max_by(|&a, &b| {
+                let area = |i: usize| {
+                    let (x, y) = points[i];
+                    ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs()
+                };
+                area(a).total_cmp(&area(b))
+            })
+            .unwrap_or(start);
+
+        selected.push(best);
+        prev = best;
+    }
+
+    selected.push(len - 1);
+    selected
+}
+
+/// Reduce each metric type's series to at most `max_points` rows with LTTB
+/// over its primary numeric field. Types without one (or that are already
+/// small enough) are returned untouched, as are rows missing a numeric value
+/// for the field, so those come on top of `max_points`; order is preserved.
+pub fn downsample_metrics(metrics: Vec<StoredMetric>, max_points: usize) -> Vec<StoredMetric> {
+    // Per type: the (row, timestamp, value) points
+    let mut by_type: HashMap<&'static str, Vec<(usize, f64, f64)>> = HashMap::new();
+
+    for (row, metric) in metrics.iter().enumerate() {
+        let Some(field) = metric.metric_type.primary_field() else {
+            continue;
+        };
+        let value = serde_json::from_str::<serde_json::Value>(&metric.data)
+            .ok()
+            .and_then(|data| data.get(field)?.as_f64());
+        if let Some(value) = value {
+            by_type
+                .entry(metric.metric_type.as_str())
+                .or_default()
+                .push((row, metric.timestamp as f64, value));
+        }
+    }
+
+    // Rows without a plottable value aren't part of any series and are kept
+    let mut keep = vec![true; metrics.len()];
+    for points in by_type.values() {
+        if points.len() <= max_points {
+            continue;
+        }
+        for &(row, _, _) in points {
+            keep[row] = false;
+        }
+        let xy: Vec<(f64, f64)> = points.iter().map(|&(_, x, y)| (x, y)).collect();
+        for index in lttb(&xy, max_points) {
+            keep[points[index].0] = true;
+        }
+    }
+
+    metrics
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(metric, keep)| keep.then_some(metric))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lttb_returns_everything_under_threshold() {
+        let points = [(0.0, 1.0), (1.0, 2.0), (2.0, 3.0)];
+        assert_eq!(lttb(&points, 10), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn lttb_keeps_endpoints_and_peaks() {
+        // Flat line with one spike and one dip
+        let mut points: Vec<(f64, f64)> = (0..100).map(|i| (i as f64, 10.0)).collect();
+        points[30].1 = 100.0;
+        points[70].1 = -50.0;
+
+        let selected = lttb(&points, 10);
+        assert_eq!(selected.len(), 10);
+        assert_eq!(selected[0], 0);
+        assert_eq!(selected[9], 99);
+        assert!(selected.contains(&30));
+        assert!(selected.contains(&70));
+        assert!(selected.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn lttb_picks_largest_triangles() {
+        // Two buckets of three; worked out by hand from the triangle areas
+        let points = [
+            (1.0, 10.0),
+            (2.0, 20.0),
+            (3.0, 4.0),
+            (4.0, 30.0),
+            (5.0, 5.0),
+            (6.0, 25.0),
+            (7.0, 1.0),
+            (8.0, 8.0),
+        ];
+        assert_eq!(lttb(&points, 4), vec![0, 3, 4, 7]);
+    }
+
+    #[test]
+    fn downsample_only_touches_large_series() {
+        use crate::storage::MetricType;
+
+        let metric = |timestamp: i64, metric_type: MetricType, data: String| StoredMetric {
+            id: None,
+            session_id: "s".into(),
+            timestamp,
+            metric_type,
+            data,
+        };
+        let mut metrics: Vec<StoredMetric> = (0..50)
+            .map(|i| {
+                metric(
+                    i,
+                    MetricType::Performance,
+                    format!("{{\"js_heap_used_size\":{}}}", i % 7),
+                )
+            })
+            .collect();
+        metrics.push(metric(5, MetricType::Cpu, "{\"cpu_percent\":3.0}".into()));
+
+        let reduced = downsample_metrics(metrics, 10);
+        let performance = reduced
+            .iter()
+            .filter(|m| m.metric_type == MetricType::Performance)
+            .count();
+        assert_eq!(performance, 10);
+        // Small series pass through and input order is kept
+        assert_eq!(reduced.last().unwrap().metric_type, MetricType::Cpu);
+    }
+
+    #[test]
+    fn downsample_keeps_rows_without_primary_value() {
+        use crate::storage::MetricType;
+
+        let mut metrics: Vec<StoredMetric> = (0..50)
+            .map(|i| StoredMetric {
+                id: None,
+                session_id: "s".into(),
+                timestamp: i,
+                metric_type: MetricType::Performance,
+                data: if i == 25 {
+                    "{\"dom_nodes\":12}".into()
+                } else {
+                    format!("{{\"js_heap_used_size\":{}}}", i % 7)
+                },
+            })
+            .collect();
+        metrics[40].data = "not json".into();
+
+        let reduced = downsample_metrics(metrics, 10);
+        // Ten plotted points plus both rows the series couldn't place
+        assert_eq!(reduced.len(), 12);
+        assert!(reduced.iter().any(|m| m.timestamp == 25));
+        assert!(reduced.iter().any(|m| m.timestamp == 40));
+        assert!(reduced.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+    }
+}
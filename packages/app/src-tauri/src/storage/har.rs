@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use chrono::{TimeZone, Utc};
+use serde::Serialize;
+
+use super::metrics::StoredNetworkRequest;
+use crate::cdp::RequestTiming;
+
+/// HAR spec version written by `export_har`
+pub const HAR_VERSION: &str = "1.2";
+
+/// Top-level HAR document (`{"log": {...}}`)
+#[derive(Debug, Serialize)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HarLog {
+    pub version: &'static str,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HarCreator {
+    pub name: &'static str,
+    pub version: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: String,
+    /// Total elapsed time in ms, -1 when the request never finished
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    pub cache: HashMap<String, String>,
+    pub timings: HarTimings,
+    /// Chrome's custom field for the resource type
+    #[serde(rename = "_resourceType", skip_serializing_if = "Option::is_none")]
+    pub resource_type: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub cookies: Vec<HarNameValue>,
+    pub headers: Vec<HarNameValue>,
+    pub query_string: Vec<HarNameValue>,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: i32,
+    pub status_text: String,
+    pub http_version: String,
+    pub cookies: Vec<HarNameValue>,
+    pub headers: Vec<HarNameValue>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    pub mime_type: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HarNameValue {
+    pub name: String,
+    pub value: String,
+}
+
+/// Phase timings in ms; -1 marks phases that didn't apply. Without captured
+/// phases (cached responses) the total duration is reported as `wait`.
+#[derive(Debug, Serialize)]
+pub struct HarTimings {
+    pub blocked: f64,
+    pub dns: f64,
+    /// Includes `ssl`, as the HAR spec requires
+    pub connect: f64,
+    pub ssl: f64,
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+}
+
+impl HarTimings {
+    fn from_phases(timing: &RequestTiming, total: f64) -> Self {
+        let ssl = timing.ssl_ms;
+        let connect = timing
+            .connect_ms
+            .map(|connect| connect + ssl.unwrap_or(0.0));
+        let send = timing.send_ms.unwrap_or(0.0);
+        let wait = timing.wait_ms.unwrap_or(0.0);
+        let receive = timing.download_ms.unwrap_or(0.0);
+        // Whatever the phases don't cover was spent queued
+        let covered = timing.dns_ms.unwrap_or(0.0) + connect.unwrap_or(0.0) + send + wait + receive;
+
+        Self {
+            blocked: (total - covered).max(0.0),
+            dns: timing.dns_ms.unwrap_or(-1.0),
+            connect: connect.unwrap_or(-1.0),
+            ssl: ssl.unwrap_or(-1.0),
+            send,
+            wait,
+            receive,
+        }
+    }
+}
+
+impl Har {
+    /// Build a HAR log from stored network requests (expected oldest first)
+    pub fn from_requests(requests: &[StoredNetworkRequest]) -> Self {
+        Self {
+            log: HarLog {
+                version: HAR_VERSION,
+                creator: HarCreator {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                },
+                entries: requests.iter().map(HarEntry::from_request).collect(),
+            },
+        }
+    }
+}
+
+impl HarEntry {
+    fn from_request(request: &StoredNetworkRequest) -> Self {
+        let finished = request.response_time.is_some();
+        let time = match (request.duration_ms, request.response_time) {
+            (Some(duration), _) => duration,
+            (None, Some(response_time)) => (response_time - request.request_time) as f64,
+            (None, None) => -1.0,
+        };
+
+        let response_headers = request
+            .headers
+            .iter()
+            .flatten()
+            .map(|(name, value)| HarNameValue {
+                name: name.clone(),
+                value: value.clone(),
+            })
+            .collect();
+
+        let size = request.size_bytes.map(|s| s as i64).unwrap_or(-1);
+        // Content size is the uncompressed body; fall back to wire bytes
+        let content_size = request.decoded_size_bytes.map(|s| s as i64).unwrap_or(size);
+
+        Self {
+            // request_time is monotonic browser time; rows stored before
+            // wall_time was recorded fall back to it
+            started_date_time: iso8601(request.wall_time.unwrap_or(request.request_time)),
+            time,
+            request: HarRequest {
+                method: request.method.clone().unwrap_or_else(|| "GET".to_string()),
+                url: request.url.clone(),
+                http_version: String::new(),
+                cookies: Vec::new(),
+                headers: Vec::new(),
+                query_string: Vec::new(),
+                headers_size: -1,
+                body_size: -1,
+            },
+            response: HarResponse {
+                status: request.status_code.unwrap_or(0),
+                status_text: String::new(),
+                http_version: String::new(),
+                cookies: Vec::new(),
+                headers: response_headers,
+                content: HarContent {
+                    size: content_size.max(0),
+                    mime_type: request.mime_type.clone().unwrap_or_default(),
+                },
+                redirect_url: String::new(),
+                headers_size: -1,
+                body_size: size,
+            },
+            cache: HashMap::new(),
+            resource_type: request.resource_type.clone(),
+            timings: match (&request.timing, finished) {
+                (Some(timing), true) => HarTimings::from_phases(timing, time.max(0.0)),
+                (None, true) => HarTimings {
+                    blocked: -1.0,
+                    dns: -1.0,
+                    connect: -1.0,
+                    ssl: -1.0,
+                    send: 0.0,
+                    wait: time.max(0.0),
+                    receive: 0.0,
+                },
+                (_, false) => HarTimings {
+                    blocked: -1.0,
+                    dns: -1.0,
+                    connect: -1.0,
+                    ssl: -1.0,
+                    send: 0.0,
+                    wait: -1.0,
+                    receive: -1.0,
+                },
+            },
+        }
+    }
+}
+
+/// Millisecond epoch timestamp as ISO-8601 with a UTC offset
+fn iso8601(timestamp_ms: i64) -> String {
+    Utc.timestamp_millis_opt(timestamp_ms)
+        .single()
+        .unwrap_or_default()
+        .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
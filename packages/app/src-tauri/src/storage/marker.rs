@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Timeline marker recorded when metric collection or emulation changes state
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionMarker {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub session_id: String,
+    pub timestamp: i64,
+    pub kind: MarkerKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkerKind {
+    CollectionStarted,
+    CollectionStopped,
+    Paused,
+    Resumed,
+    /// Network throttling was applied or cleared
+    NetworkThrottleChanged,
+}
+
+impl MarkerKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarkerKind::CollectionStarted => "collection_started",
+            MarkerKind::CollectionStopped => "collection_stopped",
+            MarkerKind::Paused => "paused",
+            MarkerKind::Resumed => "resumed",
+            MarkerKind::NetworkThrottleChanged => "network_throttle_changed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "collection_started" => MarkerKind::CollectionStarted,
+            "collection_stopped" => MarkerKind::CollectionStopped,
+            "paused" => MarkerKind::Paused,
+            "resumed" => MarkerKind::Resumed,
+            "network_throttle_changed" => MarkerKind::NetworkThrottleChanged,
+            _ => MarkerKind::CollectionStarted,
+        }
+    }
+}
+
+impl SessionMarker {
+    pub fn new(session_id: &str, kind: MarkerKind) -> Self {
+        Self {
+            id: None,
+            session_id: session_id.to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            kind,
+        }
+    }
+}
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::collections::HashMap;
 
+use crate::cdp::RequestTiming;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct StoredMetric {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -19,6 +21,12 @@ pub enum MetricType {
     Memory,
     Network,
     WebVitals,
+    Cpu,
+    Frames,
+    /// Page load milestones, one row per navigation
+    Navigation,
+    /// JS coverage totals, one row per coverage run
+    Coverage,
 }
 
 impl MetricType {
@@ -28,18 +36,45 @@ impl MetricType {
             MetricType::Memory => "memory",
             MetricType::Network => "network",
             MetricType::WebVitals => "webvitals",
+            MetricType::Cpu => "cpu",
+            MetricType::Frames => "frames",
+            MetricType::Navigation => "navigation",
+            MetricType::Coverage => "coverage",
         }
     }
 
-    pub fn from_str(s: &str) -> Self {
+    /// Numeric field charted (and downsampled on) by default
+    pub fn primary_field(&self) -> Option<&'static str> {
+        match self {
+            MetricType::Performance => Some("js_heap_used_size"),
+            MetricType::Memory => Some("nodes"),
+            MetricType::Network => None,
+            MetricType::WebVitals => Some("lcp_ms"),
+            MetricType::Cpu => Some("cpu_percent"),
+            MetricType::Frames => Some("avg_fps"),
+            MetricType::Navigation => Some("load_ms"),
+            MetricType::Coverage => Some("unused_bytes"),
+        }
+    }
+
+    /// Parse a metric type, rejecting unknown names
+    pub fn try_from_str(s: &str) -> Option<Self> {
         match s {
-            "performance" => MetricType::Performance,
-            "memory" => MetricType::Memory,
-            "network" => MetricType::Network,
-            "webvitals" => MetricType::WebVitals,
-            _ => MetricType::Performance,
+            "performance" => Some(MetricType::Performance),
+            "memory" => Some(MetricType::Memory),
+            "network" => Some(MetricType::Network),
+            "webvitals" => Some(MetricType::WebVitals),
+            "cpu" => Some(MetricType::Cpu),
+            "frames" => Some(MetricType::Frames),
+            "navigation" => Some(MetricType::Navigation),
+            "coverage" => Some(MetricType::Coverage),
+            _ => None,
         }
     }
+
+    pub fn from_str(s: &str) -> Self {
+        Self::try_from_str(s).unwrap_or(MetricType::Performance)
+    }
 }
 
 impl StoredMetric {
@@ -55,6 +90,45 @@ impl StoredMetric {
             data: serde_json::to_string(metrics)?,
         })
     }
+
+    pub fn from_memory(
+        session_id: &str,
+        metrics: &crate::cdp::MemoryMetrics,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            id: None,
+            session_id: session_id.to_string(),
+            timestamp: metrics.timestamp,
+            metric_type: MetricType::Memory,
+            data: serde_json::to_string(metrics)?,
+        })
+    }
+
+    pub fn from_web_vitals(
+        session_id: &str,
+        vitals: &crate::cdp::WebVitals,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            id: None,
+            session_id: session_id.to_string(),
+            timestamp: vitals.timestamp,
+            metric_type: MetricType::WebVitals,
+            data: serde_json::to_string(vitals)?,
+        })
+    }
+
+    pub fn from_page_load(
+        session_id: &str,
+        timing: &crate::cdp::PageLoadTiming,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            id: None,
+            session_id: session_id.to_string(),
+            timestamp: timing.timestamp,
+            metric_type: MetricType::Navigation,
+            data: serde_json::to_string(timing)?,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -64,9 +138,192 @@ pub struct StoredNetworkRequest {
     pub url: String,
     pub method: Option<String>,
     pub status_code: Option<i32>,
+    /// Monotonic browser time in ms; only meaningful relative to other
+    /// timestamps from the same session
     pub request_time: i64,
+    /// When the request was sent, in epoch ms (CDP `wallTime`)
+    #[serde(default)]
+    pub wall_time: Option<i64>,
     pub response_time: Option<i64>,
     pub duration_ms: Option<f64>,
+    /// Bytes received over the wire (`encodedDataLength`)
     pub size_bytes: Option<f64>,
+    /// Uncompressed body size, summed from `Network.dataReceived`
+    #[serde(default)]
+    pub decoded_size_bytes: Option<f64>,
+    /// Response headers, keyed by lowercased name
     pub headers: Option<HashMap<String, String>>,
+    /// What triggered the request (parser, script, preload, other, ...)
+    pub initiator_type: Option<String>,
+    /// Initiating document or top script stack frame URL
+    pub initiator_url: Option<String>,
+    /// Line in `initiator_url` (0-based, as reported by CDP)
+    #[serde(default)]
+    pub initiator_line: Option<i64>,
+    /// Function in the top stack frame that issued the request
+    #[serde(default)]
+    pub initiator_function: Option<String>,
+    /// Initial resource priority assigned by the browser (VeryHigh..VeryLow)
+    pub priority: Option<String>,
+    /// Response MIME type
+    pub mime_type: Option<String>,
+    /// Lowercased CDP resource type (document, script, xhr, fetch, ...)
+    pub resource_type: Option<String>,
+    /// DNS/connect/SSL/wait/download phases; None for cached responses
+    #[serde(default)]
+    pub timing: Option<RequestTiming>,
+    /// The request never completed: it stalled past the collector's
+    /// timeout or the browser reported `loadingFailed`
+    #[serde(default)]
+    pub failed: bool,
+    /// Why loading failed, e.g. `net::ERR_NAME_NOT_RESOLVED` or a blocked
+    /// reason; None for stalled requests
+    #[serde(default)]
+    pub error_text: Option<String>,
+}
+
+/// Filters for `get_network_requests`; unset fields match every request
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct NetworkRequestFilter {
+    /// Resource types to keep, e.g. `["xhr", "fetch"]` for API calls
+    pub resource_types: Option<Vec<String>>,
+    /// Substring the URL must contain (case-sensitive)
+    pub url_contains: Option<String>,
+    /// Keep only responses in this status class, or failed requests
+    #[serde(default)]
+    pub status_class: Option<StatusClass>,
+}
+
+/// HTTP status class for `NetworkRequestFilter`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub enum StatusClass {
+    #[serde(rename = "2xx")]
+    Success,
+    #[serde(rename = "3xx")]
+    Redirect,
+    #[serde(rename = "4xx")]
+    ClientError,
+    #[serde(rename = "5xx")]
+    ServerError,
+    /// Stalled or `loadingFailed`; these have no usable status
+    #[serde(rename = "failed")]
+    Failed,
+}
+
+/// Position after the last request of a page. Requests are ordered by
+/// `request_time`, then `id`, so ties never skip or repeat rows.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct NetworkRequestCursor {
+    pub request_time: i64,
+    pub id: String,
+}
+
+/// One page of network requests
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NetworkRequestPage {
+    pub requests: Vec<StoredNetworkRequest>,
+    /// Pass back to fetch the next page; None on the last page
+    pub next_cursor: Option<NetworkRequestCursor>,
+}
+
+/// Aggregate network statistics for a session
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NetworkSummary {
+    pub session_id: String,
+    pub total_requests: i64,
+    pub completed_requests: i64,
+    /// Bytes received over the wire
+    pub total_bytes: f64,
+    /// Uncompressed body bytes; much larger than `total_bytes` means
+    /// compression is working, roughly equal on text means it isn't
+    pub total_decoded_bytes: f64,
+    pub avg_duration_ms: Option<f64>,
+    /// Request count per resource priority ("unknown" when not captured)
+    pub by_priority: HashMap<String, i64>,
+    /// Request count per URL, or per normalized endpoint when requested
+    pub by_endpoint: HashMap<String, i64>,
+    /// Transfer totals per resource type ("unknown" when not captured)
+    pub by_resource_type: HashMap<String, ResourceTypeBytes>,
+}
+
+/// Transfer totals for one resource type
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ResourceTypeBytes {
+    pub requests: i64,
+    pub wire_bytes: f64,
+    pub decoded_bytes: f64,
+}
+
+/// Requests for the same method and URL within a session
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DuplicateRequestGroup {
+    pub method: Option<String>,
+    /// URL as grouped, without the query string when that was stripped
+    pub url: String,
+    pub count: i64,
+    pub total_bytes: f64,
+    /// Wire bytes of every request after the first
+    pub wasted_bytes: f64,
+    /// In request order
+    pub request_ids: Vec<String>,
+}
+
+/// Network totals for one host
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HostNetworkSummary {
+    /// URL host, or "(inline)" for data:, blob: and unparseable URLs
+    pub host: String,
+    pub requests: i64,
+    /// Bytes received over the wire
+    pub total_bytes: f64,
+    pub avg_duration_ms: Option<f64>,
+}
+
+/// Requests started within one fixed window of a session
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ThroughputBucket {
+    /// Window start; a multiple of the bucket size
+    pub start_time: i64,
+    pub request_count: i64,
+    /// Wire bytes of those requests
+    pub total_bytes: f64,
+}
+
+/// Distribution of one numeric metric field over a time window
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MetricBucketStats {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub count: i64,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    pub p50: Option<f64>,
+    pub p90: Option<f64>,
+    pub p95: Option<f64>,
+    pub p99: Option<f64>,
+}
+
+/// Percentile summary of a metric field, optionally split into time buckets
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MetricsSummary {
+    pub session_id: String,
+    pub metric_type: MetricType,
+    pub field: String,
+    /// One entry per non-empty bucket, in time order
+    pub buckets: Vec<MetricBucketStats>,
+}
+
+/// Cumulative bytes received for a request at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RequestDataChunk {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub session_id: String,
+    pub request_id: String,
+    pub timestamp: i64,
+    /// Cumulative decoded bytes received so far
+    pub data_length: i64,
+    /// Cumulative bytes received over the wire so far
+    pub encoded_data_length: i64,
 }
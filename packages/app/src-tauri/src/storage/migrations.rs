@@ -0,0 +1,77 @@
+/// One schema change, applied once and recorded in `schema_version`.
+/// Columns are only added when missing, so a migration also applies cleanly
+/// to databases that already got them from `CREATE TABLE` or from the
+/// ad-hoc ALTERs used before versioning existed.
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    /// `(table, column, definition)` to add
+    pub columns: &'static [(&'static str, &'static str, &'static str)],
+}
+
+/// Every migration in order. Append new ones; never edit or reorder
+/// entries that have shipped.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "session display name and tags",
+        columns: &[
+            ("sessions", "display_name", "TEXT"),
+            ("sessions", "tags", "TEXT"),
+        ],
+    },
+    Migration {
+        version: 2,
+        description: "session preset name",
+        columns: &[("sessions", "preset_name", "TEXT")],
+    },
+    Migration {
+        version: 3,
+        description: "network request initiator, priority and content details",
+        columns: &[
+            ("network_requests", "initiator_type", "TEXT"),
+            ("network_requests", "initiator_url", "TEXT"),
+            ("network_requests", "priority", "TEXT"),
+            ("network_requests", "mime_type", "TEXT"),
+            ("network_requests", "resource_type", "TEXT"),
+        ],
+    },
+    Migration {
+        version: 4,
+        description: "stalled network requests",
+        columns: &[("network_requests", "failed", "INTEGER NOT NULL DEFAULT 0")],
+    },
+    Migration {
+        version: 5,
+        description: "network request initiator stack frame",
+        columns: &[
+            ("network_requests", "initiator_line", "INTEGER"),
+            ("network_requests", "initiator_function", "TEXT"),
+        ],
+    },
+    Migration {
+        version: 6,
+        description: "decoded response size",
+        columns: &[("network_requests", "decoded_size_bytes", "REAL")],
+    },
+    Migration {
+        version: 7,
+        description: "request phase timings",
+        columns: &[("network_requests", "timing", "TEXT")],
+    },
+    Migration {
+        version: 8,
+        description: "compressed metric data",
+        columns: &[("metrics", "encoding", "INTEGER NOT NULL DEFAULT 0")],
+    },
+    Migration {
+        version: 9,
+        description: "network request failure reason",
+        columns: &[("network_requests", "error_text", "TEXT")],
+    },
+    Migration {
+        version: 10,
+        description: "network request wall-clock time",
+        columns: &[("network_requests", "wall_time", "INTEGER")],
+    },
+];
@@ -1,7 +1,43 @@
+mod alert;
+mod annotation;
+mod body;
+mod bundle;
+mod codec;
+mod compare;
+mod console;
+mod csv;
 mod database;
+mod downsample;
+mod har;
+mod marker;
 mod metrics;
+mod migrations;
+mod normalize;
+mod preset;
+mod security;
 mod session;
+mod settings;
+mod smoothing;
 
-pub use database::Database;
-pub use metrics::{MetricType, StoredMetric, StoredNetworkRequest};
-pub use session::Session;
+pub use alert::{Alert, AlertKind, AlertThresholds};
+pub use annotation::Annotation;
+pub use body::ResponseBody;
+pub use codec::CompactResult;
+pub use compare::SessionComparison;
+pub use console::ConsoleLog;
+pub use csv::CsvExportKind;
+pub use database::{Database, StorageError};
+pub use marker::{MarkerKind, SessionMarker};
+pub use metrics::{
+    DuplicateRequestGroup, HostNetworkSummary, MetricType, MetricsSummary, NetworkRequestCursor,
+    NetworkRequestFilter, NetworkRequestPage, NetworkSummary, RequestDataChunk, StoredMetric,
+    StoredNetworkRequest, ThroughputBucket,
+};
+pub use normalize::UrlNormalizer;
+pub use preset::TestPreset;
+pub use security::SecurityEvent;
+pub use session::{
+    BatchResult, PruneResult, Session, SessionCounts, SessionSearchFilter, SessionStatus, TagCount,
+};
+pub use settings::Settings;
+pub use smoothing::{SmoothedSeries, SmoothingOptions};
@@ -0,0 +1,146 @@
+use regex::Regex;
+
+/// Replaces path segments matching `pattern` with `replacement`
+#[derive(Debug, Clone)]
+pub struct NormalizeRule {
+    pub pattern: Regex,
+    pub replacement: String,
+}
+
+impl NormalizeRule {
+    pub fn new(pattern: &str, replacement: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            pattern: Regex::new(pattern)?,
+            replacement: replacement.to_string(),
+        })
+    }
+}
+
+/// Collapses URLs that hit the same endpoint (`/api/user/123` and
+/// `/api/user/456`) into one key by dropping the query string and
+/// replacing variable path segments with placeholders.
+#[derive(Debug, Clone)]
+pub struct UrlNormalizer {
+    rules: Vec<NormalizeRule>,
+}
+
+impl UrlNormalizer {
+    pub fn new(rules: Vec<NormalizeRule>) -> Self {
+        Self { rules }
+    }
+
+    pub fn normalize(&self, url: &str) -> String {
+        let end = url.find(['?', '#']).unwrap_or(url.len());
+        let without_query = &url[..end];
+
+        // Keep scheme://host intact; only rewrite path segments
+        let path_start = match without_query.find("://") {
+            Some(i) => without_query[i + 3..]
+                .find('/')
+                .map_or(without_query.len(), |j| i + 3 + j),
+            None => 0,
+        };
+        let (origin, path) = without_query.split_at(path_start);
+
+        let segments: Vec<String> = path
+            .split('/')
+            .map(|segment| {
+                self.rules
+                    .iter()
+                    .find(|rule| rule.pattern.is_match(segment))
+                    .map(|rule| rule.replacement.clone())
+                    .unwrap_or_else(|| segment.to_string())
+            })
+            .collect();
+
+        format!("{}{}", origin, segments.join("/"))
+    }
+}
+
+impl Default for UrlNormalizer {
+    fn default() -> Self {
+        let rules = [
+            (r"^\d+$", ":id"),
+            (
+                r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+                ":uuid",
+            ),
+            (r"^[0-9a-fA-F]{16,}$", ":hash"),
+        ];
+        Self::new(
+            rules
+                .iter()
+                .map(|(pattern, replacement)| NormalizeRule::new(pattern, replacement).unwrap())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_rules_replace_variable_segments() {
+        let normalizer = UrlNormalizer::default();
+        for (url, expected) in [
+            (
+                "https://example.com/api/user/123",
+                "https://example.com/api/user/:id",
+            ),
+            (
+                "https://example.com/api/order/3f2b8c1e-9a4d-4e6f-8b2a-1c3d5e7f9a0b/items",
+                "https://example.com/api/order/:uuid/items",
+            ),
+            (
+                "https://example.com/static/0123456789abcdef0123.js",
+                "https://example.com/static/0123456789abcdef0123.js",
+            ),
+            (
+                "https://example.com/blob/0123456789abcdef0123",
+                "https://example.com/blob/:hash",
+            ),
+            (
+                "https://example.com/api/v2/users",
+                "https://example.com/api/v2/users",
+            ),
+        ] {
+            assert_eq!(normalizer.normalize(url), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn normalize_strips_query_and_fragment() {
+        let normalizer = UrlNormalizer::default();
+        assert_eq!(
+            normalizer.normalize("https://example.com/api/user/42?fields=name#top"),
+            "https://example.com/api/user/:id"
+        );
+        assert_eq!(
+            normalizer.normalize("https://example.com/search#results"),
+            "https://example.com/search"
+        );
+    }
+
+    #[test]
+    fn normalize_leaves_origin_untouched() {
+        let normalizer = UrlNormalizer::default();
+        assert_eq!(
+            normalizer.normalize("http://10.0.2.2:8080/api/7"),
+            "http://10.0.2.2:8080/api/:id"
+        );
+        assert_eq!(normalizer.normalize("http://12345?retry=1"), "http://12345");
+    }
+
+    #[test]
+    fn normalize_handles_relative_paths_and_empty_rules() {
+        assert_eq!(
+            UrlNormalizer::default().normalize("/api/user/99/avatar"),
+            "/api/user/:id/avatar"
+        );
+        assert_eq!(
+            UrlNormalizer::new(Vec::new()).normalize("https://example.com/api/user/99?v=1"),
+            "https://example.com/api/user/99"
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use crate::cdp::PresetConfig;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Named, reusable set of emulation settings (e.g. "mid-tier phone on 4G")
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TestPreset {
+    pub name: String,
+    pub config: PresetConfig,
+    pub created_at: i64,
+}
+
+impl TestPreset {
+    pub fn new(name: String, config: PresetConfig) -> Self {
+        Self {
+            name,
+            config,
+            created_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Page security state change (mixed content, certificate problems, ...)
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SecurityEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    pub session_id: String,
+    pub timestamp: i64,
+    /// CDP security level: unknown, neutral, insecure, secure, info, insecure-broken
+    pub state: String,
+    /// Human-readable reasons for a non-secure state
+    pub summary: Option<String>,
+}
@@ -17,11 +17,65 @@ pub struct Session {
     pub display_name: Option<String>,
     /// Tags for categorizing sessions (stored as JSON array)
     pub tags: Option<Vec<String>>,
-    #[serde(skip)]
-    #[specta(skip)]
+    /// Name of the last test preset applied during the session
+    pub preset_name: Option<String>,
+    /// Free-form run details such as build numbers or git SHAs
     pub metadata: Option<HashMap<String, serde_json::Value>>,
 }
 
+/// Row counts of data stored for a session
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionCounts {
+    pub metric_count: i64,
+    pub network_request_count: i64,
+    pub marker_count: i64,
+}
+
+/// Rows removed by a retention prune. Child rows go with their session
+/// through `ON DELETE CASCADE`, so they are counted before the delete.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct PruneResult {
+    pub sessions_deleted: i64,
+    pub metrics_deleted: i64,
+    pub network_requests_deleted: i64,
+    /// Every deleted row, including markers, security events, alerts,
+    /// console logs and transfer samples
+    pub total_rows_deleted: i64,
+}
+
+/// Outcome of a bulk session operation. Ids that don't exist are reported
+/// in `failed` without rolling back the rest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct BatchResult {
+    pub succeeded: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+/// A tag and how many sessions carry it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: i64,
+}
+
+/// Filters for session search; unset fields match every session
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct SessionSearchFilter {
+    pub query: Option<String>,
+    pub device_id: Option<String>,
+    pub status: Option<String>,
+    pub tags: Option<Vec<String>>,
+    /// Inclusive lower bound on `started_at` (epoch millis)
+    pub started_after: Option<i64>,
+    /// Inclusive upper bound on `started_at` (epoch millis)
+    pub started_before: Option<i64>,
+    /// Inclusive bounds on `ended_at - started_at`; setting either
+    /// excludes sessions that haven't ended
+    pub min_duration_ms: Option<i64>,
+    pub max_duration_ms: Option<i64>,
+    pub limit: Option<u32>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
 #[serde(rename_all = "lowercase")]
 pub enum SessionStatus {
@@ -69,6 +123,7 @@ impl Session {
             status: SessionStatus::Active,
             display_name: None,
             tags: None,
+            preset_name: None,
             metadata: None,
         }
     }
@@ -0,0 +1,70 @@
+use super::alert::AlertThresholds;
+use crate::adb::AdbServer;
+use crate::cdp::{
+    BodyCaptureRule, DEFAULT_EMIT_INTERVAL_MS, DEFAULT_NETWORK_BATCH_MS,
+    DEFAULT_SNAPSHOT_INTERVAL_MS, DEFAULT_STALL_TIMEOUT_MS,
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Persisted application settings.
+/// Stored one key per field; keys missing from the database fall back to
+/// these defaults and unknown keys are ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct Settings {
+    /// Default Performance.getMetrics poll interval
+    pub poll_interval_ms: u64,
+    /// Default minimum spacing between `metrics:performance` UI events
+    pub emit_interval_ms: u64,
+    /// Spacing between consolidated `metrics:snapshot` events (0 = off)
+    pub snapshot_interval_ms: u64,
+    /// Window over which network events are batched for the UI (0 = no delay)
+    pub network_batch_ms: u64,
+    /// Mark requests that haven't finished after this long as failed
+    pub stall_timeout_ms: u64,
+    /// Store response bodies of matching requests (None = off)
+    pub body_capture: Option<BodyCaptureRule>,
+    /// Limits that raise `metrics:alert` during collection
+    pub alert_thresholds: AlertThresholds,
+    /// Default rolling sample cap per session (None keeps everything)
+    pub max_samples: Option<u32>,
+    /// Delete sessions older than this many days (None keeps everything)
+    pub retention_days: Option<u32>,
+    /// Test preset applied when collection starts on a session that has
+    /// none recorded
+    pub default_preset: Option<String>,
+    /// Custom adb executable path (None uses system adb, then the bundled one)
+    pub adb_path: Option<String>,
+    /// Remote adb server to run every adb command against
+    pub adb_server: Option<AdbServer>,
+    /// Custom location of the session database: a directory to keep it in
+    /// or the database file itself (`open_database` sets it). Read from the
+    /// database in the app data directory at launch, so changes apply on
+    /// the next start.
+    pub data_dir: Option<String>,
+    /// Gzip the data of newly stored metrics; `compact_database` converts
+    /// existing rows
+    pub compress_metrics: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            poll_interval_ms: 1000,
+            emit_interval_ms: DEFAULT_EMIT_INTERVAL_MS,
+            snapshot_interval_ms: DEFAULT_SNAPSHOT_INTERVAL_MS,
+            network_batch_ms: DEFAULT_NETWORK_BATCH_MS,
+            stall_timeout_ms: DEFAULT_STALL_TIMEOUT_MS,
+            body_capture: None,
+            alert_thresholds: AlertThresholds::default(),
+            max_samples: None,
+            retention_days: None,
+            default_preset: None,
+            adb_path: None,
+            adb_server: None,
+            data_dir: None,
+            compress_metrics: false,
+        }
+    }
+}
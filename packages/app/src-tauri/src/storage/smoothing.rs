@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::metrics::MetricType;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SmoothingMethod {
+    /// Mean of the last `window` points
+    MovingAverage,
+    /// Exponential moving average with alpha = 2 / (window + 1)
+    Ema,
+}
+
+/// How to smooth a metric series
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SmoothingOptions {
+    pub method: SmoothingMethod,
+    /// Points per window; tune to the sample rate (0 is treated as 1)
+    pub window: u32,
+    /// Drop points more than this many standard deviations from the mean
+    /// before smoothing (None keeps everything)
+    pub outlier_k: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SmoothedPoint {
+    pub timestamp: i64,
+    pub raw: f64,
+    pub smoothed: f64,
+}
+
+/// A numeric metric field with raw and smoothed values per sample
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SmoothedSeries {
+    pub session_id: String,
+    pub metric_type: MetricType,
+    pub field: String,
+    pub points: Vec<SmoothedPoint>,
+    /// Samples removed by the outlier filter
+    pub outliers_dropped: u32,
+}
+
+/// Smooth `(timestamp, value)` samples in time order, returning the kept
+/// points and how many were dropped as outliers
+pub fn smooth(samples: &[(i64, f64)], options: &SmoothingOptions) -> (Vec<SmoothedPoint>, u32) {
+    let kept: Vec<(i64, f64)> = match options.outlier_k {
+        Some(k) if samples.len() > 1 => {
+            let n = samples.len() as f64;
+            let mean = samples.iter().map(|(_, v)| v).sum::<f64>() / n;
+            let std_dev = (samples.iter().map(|(_, v)| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+            samples
+                .iter()
+                .copied()
+                .filter(|(_, v)| (v - mean).abs() <= k * std_dev)
+                .collect()
+        }
+        _ => samples.to_vec(),
+    };
+    let dropped = (samples.len() - kept.len()) as u32;
+
+    let window = options.window.max(1) as usize;
+    let points = match options.method {
+        SmoothingMethod::MovingAverage => {
+            let mut sum = 0.0;
+            kept.iter()
+                .enumerate()
+                .map(|(i, &(timestamp, raw))| {
+                    sum += raw;
+                    if i >= window {
+                        sum -= kept[i - window].1;
+                    }
+                    SmoothedPoint {
+                        timestamp,
+                        raw,
+                        smoothed: sum / (i + 1).min(window) as f64,
+                    }
+                })
+                .collect()
+        }
+        SmoothingMethod::Ema => {
+            let alpha = 2.0 / (window as f64 + 1.0);
+            let mut ema: Option<f64> = None;
+            kept.iter()
+                .map(|&(timestamp, raw)| {
+                    let smoothed = ema.map_or(raw, |prev| alpha * raw + (1.0 - alpha) * prev);
+                    ema = Some(smoothed);
+                    SmoothedPoint {
+                        timestamp,
+                        raw,
+                        smoothed,
+                    }
+                })
+                .collect()
+        }
+    };
+
+    (points, dropped)
+}
@@ -16,8 +16,9 @@ import {
   type CdpTarget,
   type Session,
 } from "./bindings";
-import type { NetworkEvent } from "./types";
+import type { CollectorEvent, NetworkEvent } from "./types";
 import type { TrimMemoryLevel } from "./types/deviceProfiles";
+import { errorMessage } from "./utils";
 import {
   useUiStore,
   useDeviceStore,
@@ -64,6 +65,8 @@ function App() {
     setConnectionState,
     selectedTarget,
     setSelectedTarget,
+    connectionId,
+    setConnectionId,
     setActivePort,
   } = useCdpStore();
 
@@ -96,13 +99,13 @@ function App() {
     setLoading(true);
     setError(null);
     try {
-      const result = await taurpc.api.get_devices();
+      const result = await taurpc.api.get_devices(null);
       setDevices(result);
       if (selectedDevice && !result.find((d) => d.id === selectedDevice.id)) {
         setSelectedDevice(null);
       }
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e));
+      setError(errorMessage(e));
     } finally {
       setLoading(false);
     }
@@ -116,7 +119,7 @@ function App() {
         const result = await taurpc.api.get_webviews(deviceId);
         setWebviews(result);
       } catch (e) {
-        setError(e instanceof Error ? e.message : String(e));
+        setError(errorMessage(e));
       } finally {
         setLoading(false);
       }
@@ -145,7 +148,7 @@ function App() {
       });
       incrementPort();
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e));
+      setError(errorMessage(e));
     }
   };
 
@@ -154,15 +157,16 @@ function App() {
       if (currentSession) {
         await endSession();
       }
-      await taurpc.api.disconnect_cdp();
+      await taurpc.api.disconnect_cdp(connectionId);
       setConnectionState("Disconnected");
+      setConnectionId(null);
       setSelectedTarget(null);
       setMetrics(null);
       setIsCollecting(false);
       clearHistory();
       clearNetworkRequests();
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e));
+      setError(errorMessage(e));
     }
   };
 
@@ -205,7 +209,17 @@ function App() {
       }
 
       setConnectionState("Connecting");
-      await taurpc.api.connect_cdp(target.webSocketDebuggerUrl);
+      // Reuse this window's connection; without an id the backend opens a new one
+      const id = await taurpc.api.connect_cdp(
+        target.webSocketDebuggerUrl,
+        null,
+        null,
+        null,
+        null,
+        null,
+        connectionId
+      );
+      setConnectionId(id);
       setConnectionState("Connected");
       setSelectedTarget(target);
 
@@ -223,7 +237,7 @@ function App() {
       setAutoConnectStep("connected");
       toast.success("Connected", { description: target.title || webview.package_name || "WebView" });
     } catch (e) {
-      const errorMsg = e instanceof Error ? e.message : String(e);
+      const errorMsg = errorMessage(e);
       setAutoConnectStep("error", errorMsg);
       toast.error("Connection failed", { description: errorMsg });
     }
@@ -235,7 +249,7 @@ function App() {
       setCdpTargets(targets);
       setActivePort(port);
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e));
+      setError(errorMessage(e));
     }
   };
 
@@ -246,12 +260,22 @@ function App() {
     }
     try {
       setConnectionState("Connecting");
-      await taurpc.api.connect_cdp(target.webSocketDebuggerUrl);
+      // Reuse this window's connection; without an id the backend opens a new one
+      const id = await taurpc.api.connect_cdp(
+        target.webSocketDebuggerUrl,
+        null,
+        null,
+        null,
+        null,
+        null,
+        connectionId
+      );
+      setConnectionId(id);
       setConnectionState("Connected");
       setSelectedTarget(target);
     } catch (e) {
       setConnectionState("Disconnected");
-      setError(e instanceof Error ? e.message : String(e));
+      setError(errorMessage(e));
     }
   };
 
@@ -264,14 +288,14 @@ function App() {
         package_name: null,
         target_title: selectedTarget.title,
         webview_url: selectedTarget.url,
-      });
+      }, null, null);
       setCurrentSession(session);
       clearHistory();
       clearNetworkRequests();
-      await taurpc.api.start_metrics_collection(1000);
+      await taurpc.api.start_metrics_collection(null, null, null, null);
       setIsCollecting(true);
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e));
+      setError(errorMessage(e));
     }
   };
 
@@ -280,12 +304,12 @@ function App() {
       if (currentSession) {
         await taurpc.api.end_session(currentSession.id);
       }
-      await taurpc.api.stop_metrics_collection();
+      await taurpc.api.stop_metrics_collection(null);
       setIsCollecting(false);
       setCurrentSession(null);
       await loadSessions();
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e));
+      setError(errorMessage(e));
     }
   };
 
@@ -299,7 +323,7 @@ function App() {
       }
 
       if (currentSession?.id === sessionId) {
-        await taurpc.api.stop_metrics_collection();
+        await taurpc.api.stop_metrics_collection(null);
         setIsCollecting(false);
         setCurrentSession(null);
       }
@@ -309,7 +333,7 @@ function App() {
       removeSession(sessionId);
       toast.success("Session deleted");
     } catch (e) {
-      const msg = e instanceof Error ? e.message : String(e);
+      const msg = errorMessage(e);
       setError(msg);
       toast.error("Failed to delete session", { description: msg });
     }
@@ -318,7 +342,7 @@ function App() {
   const fetchMetrics = useCallback(async () => {
     if (connectionState !== "Connected") return;
     try {
-      const result = await taurpc.api.get_performance_metrics();
+      const result = await taurpc.api.get_performance_metrics(false, null);
       setMetrics(result);
     } catch (e) {
       console.error("Failed to fetch metrics:", e);
@@ -375,16 +399,32 @@ function App() {
     let unlistenNetwork: (() => void) | undefined;
 
     const setupListeners = async () => {
-      unlistenPerf = await listen<PerformanceMetrics>("metrics:performance", (event) => {
-        setMetrics(event.payload);
-        addMetricsToHistory(event.payload);
-      });
-
-      unlistenNetwork = await listen<NetworkEvent>("metrics:network", (event) => {
-        if (event.payload.type === "NetworkComplete") {
-          addNetworkRequest(event.payload);
+      // Other connections may be collecting too; only show this one's
+      const isOurs = (event: { payload: CollectorEvent<unknown> }) => {
+        const id = useCdpStore.getState().connectionId;
+        return id === null || event.payload.connection_id === id;
+      };
+
+      unlistenPerf = await listen<CollectorEvent<PerformanceMetrics>>(
+        "metrics:performance",
+        (event) => {
+          if (!isOurs(event)) return;
+          setMetrics(event.payload.payload);
+          addMetricsToHistory(event.payload.payload);
         }
-      });
+      );
+
+      unlistenNetwork = await listen<CollectorEvent<NetworkEvent[]>>(
+        "metrics:network_batch",
+        (event) => {
+          if (!isOurs(event)) return;
+          for (const networkEvent of event.payload.payload) {
+            if (networkEvent.type === "NetworkComplete") {
+              addNetworkRequest(networkEvent);
+            }
+          }
+        }
+      );
     };
 
     setupListeners();
@@ -399,6 +439,41 @@ function App() {
     loadSessions();
   }, [refreshDevices, loadSessions]);
 
+  // A session left active by a crash: resume it (once connected) or abort it
+  useEffect(() => {
+    taurpc.api
+      .get_active_session()
+      .then((session) => {
+        if (!session) return;
+        toast.warning("Unfinished session found", {
+          description: session.display_name || session.target_title || session.id,
+          duration: Infinity,
+          action: {
+            label: "Resume",
+            onClick: async () => {
+              try {
+                setCurrentSession(await taurpc.api.set_active_session(session.id, null));
+              } catch (e) {
+                toast.error("Failed to resume session", { description: errorMessage(e) });
+              }
+            },
+          },
+          cancel: {
+            label: "Abort",
+            onClick: async () => {
+              try {
+                await taurpc.api.abort_session(session.id, "Interrupted by app restart");
+                await loadSessions();
+              } catch (e) {
+                toast.error("Failed to abort session", { description: errorMessage(e) });
+              }
+            },
+          },
+        });
+      })
+      .catch((e) => console.error("Failed to check for an unfinished session:", e));
+  }, [taurpc, setCurrentSession, loadSessions]);
+
   useEffect(() => {
     if (selectedDevice) {
       loadWebviews(selectedDevice.id);
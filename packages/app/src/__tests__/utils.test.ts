@@ -1,5 +1,5 @@
 import { describe, it, expect } from 'vitest';
-import { formatBytes, formatDuration, formatDateTime, truncateUrl } from '../utils';
+import { errorMessage, formatBytes, formatDuration, formatDateTime, truncateUrl } from '../utils';
 
 describe('formatBytes', () => {
   it('formats bytes correctly', () => {
@@ -44,3 +44,16 @@ describe('truncateUrl', () => {
     expect(result.endsWith('...')).toBe(true);
   });
 });
+
+describe('errorMessage', () => {
+  it('reads the message of an ApiError', () => {
+    expect(errorMessage({ kind: 'device_unauthorized', message: 'Device unauthorized' })).toBe(
+      'Device unauthorized'
+    );
+  });
+
+  it('handles Error instances and plain values', () => {
+    expect(errorMessage(new Error('boom'))).toBe('boom');
+    expect(errorMessage('plain')).toBe('plain');
+  });
+});
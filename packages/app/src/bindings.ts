@@ -4,53 +4,779 @@ import { createTauRPCProxy as createProxy, type InferCommandOutput } from 'taurp
 type TAURI_CHANNEL<T> = (response: T) => void
 
 
+/**
+ * An adb server on another machine (`adb -H <host> -P <port>`)
+ */
+export type AdbServer = { host: string; port: number }
+
+/**
+ * Output of `adb version`
+ */
+export type AdbVersion = { 
+/**
+ * Custom adb path in use; None for system adb or the bundled sidecar
+ */
+custom_path: string | null; 
+/**
+ * Protocol version, e.g. "1.0.41"
+ */
+version: string | null; 
+/**
+ * Platform-tools release, e.g. "34.0.5-10900879"
+ */
+platform_tools: string | null; 
+/**
+ * Absolute path the running binary reports
+ */
+installed_as: string | null }
+
+/**
+ * A threshold that was exceeded during a session
+ */
+export type Alert = { id?: number | null; session_id: string; timestamp: number; kind: AlertKind; 
+/**
+ * The offending value
+ */
+value: number; threshold: number; 
+/**
+ * Request that ran too long, for `request_duration` alerts
+ */
+request_id: string | null; url: string | null }
+
+export type AlertKind = "js_heap" | "dom_nodes" | "request_duration"
+
+/**
+ * Limits checked by the collector; unset limits are never triggered
+ */
+export type AlertThresholds = { max_js_heap_bytes: number | null; max_dom_nodes: number | null; max_request_duration_ms: number | null }
+
+/**
+ * User-placed label on a session timeline ("tapped checkout")
+ */
+export type Annotation = { id?: number | null; session_id: string; 
+/**
+ * Milliseconds since epoch, like metric timestamps
+ */
+timestamp: number; label: string; 
+/**
+ * CSS color for the marker line, e.g. "#f59e0b"
+ */
+color: string | null }
+
+/**
+ * Error returned by every procedure. Serialized as
+ * `{ kind, message }` so the UI can branch on `kind` and show `message`.
+ */
+export type ApiError = 
+/**
+ * The RSA key prompt on the device hasn't been accepted
+ */
+{ kind: "device_unauthorized"; message: string } | 
+/**
+ * Retryable once the device comes back
+ */
+{ kind: "device_offline"; message: string } | 
+/**
+ * No such device, or none connected
+ */
+{ kind: "device_not_found"; message: string } | 
+/**
+ * Several devices are connected and none was chosen
+ */
+{ kind: "device_selection_required"; message: string } | { kind: "no_permissions"; message: string } | { kind: "no_debuggable_web_views"; message: string } | 
+/**
+ * Any other ADB failure
+ */
+{ kind: "adb"; message: string } | 
+/**
+ * No CDP connection (or not the requested one); connect first
+ */
+{ kind: "not_connected"; message: string } | 
+/**
+ * The connection has no metrics collector running; start one first
+ */
+{ kind: "not_collecting"; message: string } | 
+/**
+ * The connection isn't recording into a session; create one first
+ */
+{ kind: "no_active_session"; message: string } | 
+/**
+ * Reaching the DevTools endpoint failed; usually retryable
+ */
+{ kind: "connection_failed"; message: string } | 
+/**
+ * The browser rejected or failed a CDP command
+ */
+{ kind: "cdp"; message: string } | 
+/**
+ * The session already completed or was aborted
+ */
+{ kind: "already_ended"; message: string } | 
+/**
+ * A session, preset or other stored item doesn't exist
+ */
+{ kind: "not_found"; message: string } | { kind: "storage"; message: string } | { kind: "invalid_input"; message: string } | { kind: "other"; message: string }
+
+/**
+ * Outcome of a bulk session operation. Ids that don't exist are reported
+ * in `failed` without rolling back the rest.
+ */
+export type BatchResult = { succeeded: string[]; failed: string[] }
+
+/**
+ * Opt-in response body capture while collecting
+ */
+export type BodyCaptureRule = { 
+/**
+ * Regex matched against the request URL
+ */
+url_pattern: string; 
+/**
+ * Bodies larger than this (decoded) are not stored
+ */
+max_bytes: number }
+
 /**
  * CDP target information from /json/list endpoint
  */
-export type CdpTarget = { id: string; title: string; url: string; type: string; webSocketDebuggerUrl: string | null; devtoolsFrontendUrl: string | null; faviconUrl: string | null }
+export type CdpTarget = { id: string; title: string; url: string; type: string; webSocketDebuggerUrl: string | null; devtoolsFrontendUrl: string | null; faviconUrl: string | null }
+
+/**
+ * Runtime counters for the current collection run
+ */
+export type CollectionStats = { 
+/**
+ * Performance samples written to the database
+ */
+samples_stored: number; 
+/**
+ * CDP network events handled
+ */
+network_events_processed: number; 
+/**
+ * CDP events skipped because the collector fell behind
+ */
+events_dropped: number; 
+/**
+ * Failed `Performance.getMetrics` polls
+ */
+poll_errors: number; 
+/**
+ * Failed database writes
+ */
+storage_errors: number }
+
+/**
+ * What the metrics collector is doing right now
+ */
+export type CollectorStatus = { running: boolean; paused: boolean; session_id: string | null; 
+/**
+ * Milliseconds since epoch when collection started
+ */
+started_at: number | null; poll_interval_ms: number | null; 
+/**
+ * Requests seen but not yet finished
+ */
+tracked_request_count: number }
+
+/**
+ * Outcome of `compact_metrics`
+ */
+export type CompactResult = { 
+/**
+ * Rows rewritten in compressed form
+ */
+rows_compressed: number; 
+/**
+ * Size of those rows' data before compression
+ */
+bytes_before: number; 
+/**
+ * Size of the same rows after compression
+ */
+bytes_after: number }
+
+/**
+ * CDP connection state
+ */
+export type ConnectionState = "Disconnected" | "Connecting" | "Connected" | { Error: string }
+
+/**
+ * Severity of a console message, normalized across `console.*` calls and
+ * browser log entries
+ */
+export type ConsoleLevel = "debug" | "info" | "warning" | "error"
+
+/**
+ * Console message or browser log entry from the page
+ */
+export type ConsoleLog = { id?: number | null; session_id: string; timestamp: number; level: ConsoleLevel; text: string; 
+/**
+ * Script or document that logged the message
+ */
+url: string | null; 
+/**
+ * 0-based line in `url`
+ */
+line: number | null }
+
+/**
+ * Process CPU usage over one sampling interval
+ */
+export type CpuUsage = { pid: number; 
+/**
+ * Sample end time in ms since epoch
+ */
+timestamp: number; 
+/**
+ * CPU time over wall time, in percent of one core (can exceed 100)
+ */
+cpu_percent: number; 
+/**
+ * Wall time between the two /proc reads
+ */
+interval_ms: number }
+
+export type CreateSessionParams = { device_id: string; device_name: string | null; package_name: string | null; target_title: string | null; webview_url: string | null }
+
+/**
+ * Which session data `export_session_csv` writes
+ */
+export type CsvExportKind = 
+/**
+ * Performance samples, one column per `PerformanceMetrics` field
+ */
+"metrics" | 
+/**
+ * One row per network request
+ */
+"network"
+
+export type Device = { id: string; 
+/**
+ * AVD name for emulators, otherwise the `model:` field
+ */
+name: string; status: DeviceStatus; is_emulator: boolean }
+
+/**
+ * Screen emulation (Emulation.setDeviceMetricsOverride)
+ */
+export type DeviceMetrics = { width: number; height: number; device_scale_factor: number; mobile: boolean }
+
+/**
+ * Hardware and OS context of a device, recorded with each session so runs
+ * can be compared by SDK level and RAM tier
+ */
+export type DeviceProps = { 
+/**
+ * `ro.product.model`
+ */
+model: string | null; 
+/**
+ * `ro.build.version.release`, e.g. "14"
+ */
+android_version: string | null; 
+/**
+ * `ro.build.version.sdk`, e.g. 34
+ */
+sdk_level: number | null; 
+/**
+ * MemTotal from /proc/meminfo
+ */
+total_ram_kb: number | null; 
+/**
+ * Cores the kernel can bring online, including ones parked right now
+ */
+cpu_cores: number | null }
+
+/**
+ * Connection state reported by `adb devices`
+ */
+export type DeviceStatus = 
+/**
+ * Connected and authorized; the only state ADB commands work in
+ */
+"device" | 
+/**
+ * Waiting for the user to accept the RSA key prompt
+ */
+"unauthorized" | "offline" | 
+/**
+ * The host user can't open the USB device (udev rules on Linux)
+ */
+"no_permissions" | "recovery" | 
+/**
+ * Any other state (bootloader, sideload, authorizing, ...)
+ */
+"unknown"
+
+/**
+ * Requests for the same method and URL within a session
+ */
+export type DuplicateRequestGroup = { method: string | null; 
+/**
+ * URL as grouped, without the query string when that was stripped
+ */
+url: string; count: number; total_bytes: number; 
+/**
+ * Wire bytes of every request after the first
+ */
+wasted_bytes: number; 
+/**
+ * In request order
+ */
+request_ids: string[] }
+
+/**
+ * A CDP target annotated with the WebView process serving it
+ */
+export type EnrichedTarget = { target: CdpTarget; socket_name: string; pid: number | null; package_name: string | null }
+
+/**
+ * Events after a caller's position, for consumers that pull instead of
+ * listening to Tauri events
+ */
+export type EventPage = { events: SequencedEvent[]; 
+/**
+ * Highest sequence number assigned so far (0 before the first event);
+ * pass it back as `since_seq` to continue
+ */
+max_seq: number; 
+/**
+ * Events between `since_seq` and the first returned one were dropped
+ * from the buffer (or never recorded because the log lagged)
+ */
+truncated: boolean }
+
+/**
+ * Frame timing derived from a Tracing capture
+ */
+export type FrameStats = { 
+/**
+ * Capture end time in ms since epoch
+ */
+timestamp: number; duration_ms: number; frame_count: number; 
+/**
+ * None when fewer than two frames were drawn
+ */
+avg_fps: number | null; 
+/**
+ * Frames longer than 16.6ms (missed a 60 Hz vsync)
+ */
+long_frames: number; 
+/**
+ * Frames longer than 50ms
+ */
+jank_frames: number; max_frame_ms: number | null; 
+/**
+ * The trace buffer wrapped and some events were lost
+ */
+data_loss: boolean }
+
+/**
+ * Network totals for one host
+ */
+export type HostNetworkSummary = { 
+/**
+ * URL host, or "(inline)" for data:, blob: and unparseable URLs
+ */
+host: string; requests: number; 
+/**
+ * Bytes received over the wire
+ */
+total_bytes: number; avg_duration_ms: number | null }
+
+/**
+ * JS coverage from `start_js_coverage` to `stop_js_coverage`
+ */
+export type JsCoverage = { timestamp: number; total_bytes: number; used_bytes: number; unused_bytes: number; 
+/**
+ * Largest unused first
+ */
+scripts: ScriptCoverageSummary[] }
+
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+
+/**
+ * Page layout metrics from Page.getLayoutMetrics
+ */
+export type LayoutMetrics = { 
+/**
+ * Layout viewport position and size within the page
+ */
+layout_viewport: Rect; 
+/**
+ * Visual viewport position and size within the page
+ */
+visual_viewport: Rect; 
+/**
+ * Pinch-zoom scale of the visual viewport
+ */
+visual_viewport_scale: number; 
+/**
+ * Size of the scrollable content
+ */
+content_size: Size }
+
+/**
+ * Running network totals for the current collection run, kept in memory
+ * so header stats don't have to query the database
+ */
+export type LiveCounters = { 
+/**
+ * Requests that finished loading
+ */
+request_count: number; 
+/**
+ * Encoded (wire) bytes of finished requests
+ */
+total_bytes: number; 
+/**
+ * Finished requests with a 4xx or 5xx status
+ */
+error_count: number }
+
+export type MarkerKind = "collection_started" | "collection_stopped" | "paused" | "resumed" | 
+/**
+ * Network throttling was applied or cleared
+ */
+"network_throttle_changed"
+
+/**
+ * System memory information from /proc/meminfo
+ */
+export type MemoryInfo = { 
+/**
+ * Total RAM in KB
+ */
+total_kb: number; 
+/**
+ * Available RAM in KB
+ */
+available_kb: number; 
+/**
+ * Free RAM in KB
+ */
+free_kb: number; 
+/**
+ * Buffers in KB
+ */
+buffers_kb: number; 
+/**
+ * Cached in KB
+ */
+cached_kb: number }
+
+/**
+ * Distribution of one numeric metric field over a time window
+ */
+export type MetricBucketStats = { start_time: number; end_time: number; count: number; min: number | null; max: number | null; mean: number | null; p50: number | null; p90: number | null; p95: number | null; p99: number | null }
+
+/**
+ * Change of one aggregate from session A (baseline) to session B
+ */
+export type MetricDelta = { a: number | null; b: number | null; 
+/**
+ * `b - a`
+ */
+absolute: number | null; 
+/**
+ * `(b - a) / a * 100`; None when the baseline is missing or zero
+ */
+percent: number | null }
+
+export type MetricType = "performance" | "memory" | "network" | "webvitals" | "cpu" | "frames" | 
+/**
+ * Page load milestones, one row per navigation
+ */
+"navigation" | 
+/**
+ * JS coverage totals, one row per coverage run
+ */
+"coverage"
+
+/**
+ * Metrics event for frontend
+ */
+export type MetricsEvent = ({ type: "Performance" } & PerformanceMetrics) | ({ type: "Alert" } & Alert) | { type: "NetworkRequest"; request_id: string; url: string; method: string; timestamp: number } | { type: "NetworkResponse"; request_id: string; status: number; status_text: string; timestamp: number; duration_ms: number | null } | { type: "NetworkComplete"; request_id: string; url: string; method: string; status: number | null; duration_ms: number; size_bytes: number } | 
+/**
+ * `loadingFailed` from the browser; stored as failed
+ */
+{ type: "NetworkFailed"; request_id: string; url: string; method: string; error_text: string; canceled: boolean } | 
+/**
+ * No `loadingFinished` within the stall timeout; stored as failed
+ */
+{ type: "NetworkStalled"; request_id: string; url: string; method: string; status: number | null; elapsed_ms: number } | 
+/**
+ * A document finished loading; metrics after this belong to the new page
+ */
+({ type: "PageLoad" } & PageLoadTiming)
+
+/**
+ * Collected metrics snapshot
+ */
+export type MetricsSnapshot = { timestamp: number; performance: PerformanceMetrics | null; network_requests: NetworkRequestInfo[]; network_responses: NetworkResponseInfo[] }
+
+/**
+ * Percentile summary of a metric field, optionally split into time buckets
+ */
+export type MetricsSummary = { session_id: string; metric_type: MetricType; field: string; 
+/**
+ * One entry per non-empty bucket, in time order
+ */
+buckets: MetricBucketStats[] }
+
+/**
+ * Position after the last request of a page. Requests are ordered by
+ * `request_time`, then `id`, so ties never skip or repeat rows.
+ */
+export type NetworkRequestCursor = { request_time: number; id: string }
+
+/**
+ * Filters for `get_network_requests`; unset fields match every request
+ */
+export type NetworkRequestFilter = { 
+/**
+ * Resource types to keep, e.g. `["xhr", "fetch"]` for API calls
+ */
+resource_types: string[] | null; 
+/**
+ * Substring the URL must contain (case-sensitive)
+ */
+url_contains: string | null; 
+/**
+ * Keep only responses in this status class, or failed requests
+ */
+status_class?: StatusClass | null }
+
+/**
+ * Network request info
+ */
+export type NetworkRequestInfo = { requestId: string; url: string; method: string; timestamp: number; resourceType: string | null }
+
+/**
+ * One page of network requests
+ */
+export type NetworkRequestPage = { requests: StoredNetworkRequest[]; 
+/**
+ * Pass back to fetch the next page; None on the last page
+ */
+next_cursor: NetworkRequestCursor | null }
+
+/**
+ * Network response info
+ */
+export type NetworkResponseInfo = { requestId: string; status: number; statusText: string; timestamp: number; encodedDataLength: number | null }
+
+/**
+ * Aggregate network statistics for a session
+ */
+export type NetworkSummary = { session_id: string; total_requests: number; completed_requests: number; 
+/**
+ * Bytes received over the wire
+ */
+total_bytes: number; 
+/**
+ * Uncompressed body bytes; much larger than `total_bytes` means
+ * compression is working, roughly equal on text means it isn't
+ */
+total_decoded_bytes: number; avg_duration_ms: number | null; 
+/**
+ * Request count per resource priority ("unknown" when not captured)
+ */
+by_priority: Partial<{ [key in string]: number }>; 
+/**
+ * Request count per URL, or per normalized endpoint when requested
+ */
+by_endpoint: Partial<{ [key in string]: number }>; 
+/**
+ * Transfer totals per resource type ("unknown" when not captured)
+ */
+by_resource_type: Partial<{ [key in string]: ResourceTypeBytes }> }
 
 /**
- * CDP connection state
+ * Network condition emulation (Network.emulateNetworkConditions)
  */
-export type ConnectionState = "Disconnected" | "Connecting" | "Connected" | { Error: string }
+export type NetworkThrottle = { offline: boolean; 
+/**
+ * Additional round-trip latency in milliseconds
+ */
+latency_ms: number; 
+/**
+ * Download throughput in bytes/sec (-1 disables the limit)
+ */
+download_throughput: number; 
+/**
+ * Upload throughput in bytes/sec (-1 disables the limit)
+ */
+upload_throughput: number }
 
-export type CreateSessionParams = { device_id: string; device_name: string | null; package_name: string | null; target_title: string | null; webview_url: string | null }
+/**
+ * Classic load milestones for one navigation, in ms from navigation start
+ */
+export type PageLoadTiming = { 
+/**
+ * Milliseconds since epoch when the load event was seen
+ */
+timestamp: number; url: string | null; 
+/**
+ * First response byte
+ */
+ttfb_ms: number | null; dom_content_loaded_ms: number | null; load_ms: number | null }
 
-export type Device = { id: string; name: string; status: string }
+/**
+ * Performance metrics from CDP
+ */
+export type PerformanceMetrics = { timestamp: number; js_heap_used_size: number | null; js_heap_total_size: number | null; dom_nodes: number | null; layout_count: number | null; script_duration: number | null; task_duration: number | null; 
+/**
+ * Only reported by some WebView builds
+ */
+js_event_listeners: number | null; documents: number | null; frames: number | null; recalc_style_count: number | null; 
+/**
+ * Metrics without a typed field, when requested with `include_all`
+ */
+extras?: Partial<{ [key in string]: number }> }
 
 /**
- * System memory information from /proc/meminfo
+ * One line of `adb forward --list`
  */
-export type MemoryInfo = { 
+export type PortForward = { device_id: string; 
 /**
- * Total RAM in KB
+ * Local side, e.g. `tcp:9222`
  */
-total_kb: number; 
+local: string; 
 /**
- * Available RAM in KB
+ * Device side, e.g. `localabstract:webview_devtools_remote_1234`
  */
-available_kb: number; 
+remote: string; 
 /**
- * Free RAM in KB
+ * Port from a `tcp:` local side
  */
-free_kb: number; 
+local_port: number | null; 
 /**
- * Buffers in KB
+ * Socket from a `localabstract:` remote side
  */
-buffers_kb: number; 
+socket_name: string | null }
+
+export type PortForwardResult = { local_port: number; socket_name: string }
+
 /**
- * Cached in KB
+ * Emulation settings applied together as a test preset.
+ * Unset fields leave the corresponding page setting untouched.
  */
-cached_kb: number }
+export type PresetConfig = { network_throttle: NetworkThrottle | null; 
+/**
+ * CPU slowdown factor (1 = no throttling)
+ */
+cpu_throttle_rate: number | null; device_metrics: DeviceMetrics | null; user_agent: string | null; cache_disabled: boolean | null }
 
-export type MetricType = "performance" | "memory" | "network" | "webvitals"
+/**
+ * Rows removed by a retention prune. Child rows go with their session
+ * through `ON DELETE CASCADE`, so they are counted before the delete.
+ */
+export type PruneResult = { sessions_deleted: number; metrics_deleted: number; network_requests_deleted: number; 
+/**
+ * Every deleted row, including markers, security events, alerts,
+ * console logs and transfer samples
+ */
+total_rows_deleted: number }
 
 /**
- * Performance metrics from CDP
+ * Rectangle in CSS pixels
  */
-export type PerformanceMetrics = { timestamp: number; js_heap_used_size: number | null; js_heap_total_size: number | null; dom_nodes: number | null; layout_count: number | null; script_duration: number | null; task_duration: number | null }
+export type Rect = { x: number; y: number; width: number; height: number }
 
-export type PortForwardResult = { local_port: number; socket_name: string }
+/**
+ * Cumulative bytes received for a request at a point in time
+ */
+export type RequestDataChunk = { id?: number | null; session_id: string; request_id: string; timestamp: number; 
+/**
+ * Cumulative decoded bytes received so far
+ */
+data_length: number; 
+/**
+ * Cumulative bytes received over the wire so far
+ */
+encoded_data_length: number }
+
+/**
+ * Per-phase request timings in ms, for waterfall segments. A phase is
+ * None when it didn't happen (e.g. DNS and connect on a reused connection).
+ * Requests served from cache carry no timing at all.
+ */
+export type RequestTiming = { dns_ms: number | null; 
+/**
+ * TCP connect, excluding the TLS handshake
+ */
+connect_ms: number | null; ssl_ms: number | null; send_ms: number | null; 
+/**
+ * Waiting for the first response byte after sending (TTFB)
+ */
+wait_ms: number | null; 
+/**
+ * Headers received to loading finished; set once the request completes
+ */
+download_ms: number | null }
+
+/**
+ * Transfer totals for one resource type
+ */
+export type ResourceTypeBytes = { requests: number; wire_bytes: number; decoded_bytes: number }
+
+/**
+ * Response body captured during collection
+ */
+export type ResponseBody = { id?: number | null; session_id: string; request_id: string; url: string; timestamp: number; 
+/**
+ * Decoded size in bytes
+ */
+size_bytes: number; 
+/**
+ * `body` holds base64 of binary content rather than text
+ */
+base64_encoded: boolean; body: string }
+
+/**
+ * Response body from `Network.getResponseBody`, as sent by the browser:
+ * text as-is, binary content base64-encoded
+ */
+export type ResponseBodyContent = { body: string; base64_encoded: boolean }
+
+/**
+ * A screenshot written to disk
+ */
+export type Screenshot = { timestamp: number; 
+/**
+ * Absolute path of the PNG file
+ */
+path: string }
+
+/**
+ * Executed vs. shipped JS for one script during a coverage run
+ */
+export type ScriptCoverageSummary = { script_id: string; 
+/**
+ * None for eval and other anonymous scripts
+ */
+url: string | null; total_bytes: number; used_bytes: number; unused_bytes: number }
+
+/**
+ * Page security state change (mixed content, certificate problems, ...)
+ */
+export type SecurityEvent = { id?: number | null; session_id: string; timestamp: number; 
+/**
+ * CDP security level: unknown, neutral, insecure, secure, info, insecure-broken
+ */
+state: string; 
+/**
+ * Human-readable reasons for a non-secure state
+ */
+summary: string | null }
+
+/**
+ * A `MetricsEvent` and its position in the collector's event log
+ */
+export type SequencedEvent = { seq: number; event: MetricsEvent }
 
 export type Session = { id: string; device_id: string; device_name: string | null; webview_url: string | null; package_name: string | null; target_title: string | null; started_at: number; ended_at: number | null; status: SessionStatus; 
 /**
@@ -60,13 +786,262 @@ display_name: string | null;
 /**
  * Tags for categorizing sessions (stored as JSON array)
  */
-tags: string[] | null }
+tags: string[] | null; 
+/**
+ * Name of the last test preset applied during the session
+ */
+preset_name: string | null; 
+/**
+ * Free-form run details such as build numbers or git SHAs
+ */
+metadata: Partial<{ [key in string]: JsonValue }> | null }
+
+/**
+ * Side-by-side comparison of two sessions; positive deltas mean B is higher
+ */
+export type SessionComparison = { session_a: string; session_b: string; duration_ms: MetricDelta; mean_js_heap: MetricDelta; peak_dom_nodes: MetricDelta; total_bytes: MetricDelta; request_count: MetricDelta; mean_request_duration_ms: MetricDelta; requests_per_minute: MetricDelta; bytes_per_minute: MetricDelta }
+
+/**
+ * Row counts of data stored for a session
+ */
+export type SessionCounts = { metric_count: number; network_request_count: number; marker_count: number }
+
+/**
+ * Session with its summary data, for loading a detail view in one call
+ */
+export type SessionDetail = { session: Session; counts: SessionCounts; recent_metrics: StoredMetric[]; recent_requests: StoredNetworkRequest[]; markers: SessionMarker[]; annotations: Annotation[] }
+
+/**
+ * Timeline marker recorded when metric collection or emulation changes state
+ */
+export type SessionMarker = { id?: number | null; session_id: string; timestamp: number; kind: MarkerKind }
+
+/**
+ * Filters for session search; unset fields match every session
+ */
+export type SessionSearchFilter = { query: string | null; device_id: string | null; status: string | null; tags: string[] | null; 
+/**
+ * Inclusive lower bound on `started_at` (epoch millis)
+ */
+started_after: number | null; 
+/**
+ * Inclusive upper bound on `started_at` (epoch millis)
+ */
+started_before: number | null; 
+/**
+ * Inclusive bounds on `ended_at - started_at`; setting either
+ * excludes sessions that haven't ended
+ */
+min_duration_ms: number | null; max_duration_ms: number | null; limit: number | null }
 
 export type SessionStatus = "active" | "completed" | "aborted"
 
+/**
+ * Persisted application settings.
+ * Stored one key per field; keys missing from the database fall back to
+ * these defaults and unknown keys are ignored.
+ */
+export type Settings = { 
+/**
+ * Default Performance.getMetrics poll interval
+ */
+poll_interval_ms: number; 
+/**
+ * Default minimum spacing between `metrics:performance` UI events
+ */
+emit_interval_ms: number; 
+/**
+ * Spacing between consolidated `metrics:snapshot` events (0 = off)
+ */
+snapshot_interval_ms: number; 
+/**
+ * Window over which network events are batched for the UI (0 = no delay)
+ */
+network_batch_ms: number; 
+/**
+ * Mark requests that haven't finished after this long as failed
+ */
+stall_timeout_ms: number; 
+/**
+ * Store response bodies of matching requests (None = off)
+ */
+body_capture: BodyCaptureRule | null; 
+/**
+ * Limits that raise `metrics:alert` during collection
+ */
+alert_thresholds: AlertThresholds; 
+/**
+ * Default rolling sample cap per session (None keeps everything)
+ */
+max_samples: number | null; 
+/**
+ * Delete sessions older than this many days (None keeps everything)
+ */
+retention_days: number | null; 
+/**
+ * Test preset applied when collection starts on a session that has
+ * none recorded
+ */
+default_preset: string | null; 
+/**
+ * Custom adb executable path (None uses system adb, then the bundled one)
+ */
+adb_path: string | null; 
+/**
+ * Remote adb server to run every adb command against
+ */
+adb_server: AdbServer | null; 
+/**
+ * Custom location of the session database: a directory to keep it in
+ * or the database file itself (`open_database` sets it). Read from the
+ * database in the app data directory at launch, so changes apply on
+ * the next start.
+ */
+data_dir: string | null; 
+/**
+ * Gzip the data of newly stored metrics; `compact_database` converts
+ * existing rows
+ */
+compress_metrics: boolean }
+
+/**
+ * Width/height pair in CSS pixels
+ */
+export type Size = { width: number; height: number }
+
+export type SmoothedPoint = { timestamp: number; raw: number; smoothed: number }
+
+/**
+ * A numeric metric field with raw and smoothed values per sample
+ */
+export type SmoothedSeries = { session_id: string; metric_type: MetricType; field: string; points: SmoothedPoint[]; 
+/**
+ * Samples removed by the outlier filter
+ */
+outliers_dropped: number }
+
+export type SmoothingMethod = 
+/**
+ * Mean of the last `window` points
+ */
+"moving_average" | 
+/**
+ * Exponential moving average with alpha = 2 / (window + 1)
+ */
+"ema"
+
+/**
+ * How to smooth a metric series
+ */
+export type SmoothingOptions = { method: SmoothingMethod; 
+/**
+ * Points per window; tune to the sample rate (0 is treated as 1)
+ */
+window: number; 
+/**
+ * Drop points more than this many standard deviations from the mean
+ * before smoothing (None keeps everything)
+ */
+outlier_k: number | null }
+
+/**
+ * HTTP status class for `NetworkRequestFilter`
+ */
+export type StatusClass = "2xx" | "3xx" | "4xx" | "5xx" | 
+/**
+ * Stalled or `loadingFailed`; these have no usable status
+ */
+"failed"
+
 export type StoredMetric = { id?: number | null; session_id: string; timestamp: number; metric_type: MetricType; data: string }
 
-export type StoredNetworkRequest = { id: string; session_id: string; url: string; method: string | null; status_code: number | null; request_time: number; response_time: number | null; duration_ms: number | null; size_bytes: number | null; headers: Partial<{ [key in string]: string }> | null }
+export type StoredNetworkRequest = { id: string; session_id: string; url: string; method: string | null; status_code: number | null; 
+/**
+ * Monotonic browser time in ms; only meaningful relative to other
+ * timestamps from the same session
+ */
+request_time: number; 
+/**
+ * When the request was sent, in epoch ms (CDP `wallTime`)
+ */
+wall_time?: number | null; response_time: number | null; duration_ms: number | null; 
+/**
+ * Bytes received over the wire (`encodedDataLength`)
+ */
+size_bytes: number | null; 
+/**
+ * Uncompressed body size, summed from `Network.dataReceived`
+ */
+decoded_size_bytes?: number | null; 
+/**
+ * Response headers, keyed by lowercased name
+ */
+headers: Partial<{ [key in string]: string }> | null; 
+/**
+ * What triggered the request (parser, script, preload, other, ...)
+ */
+initiator_type: string | null; 
+/**
+ * Initiating document or top script stack frame URL
+ */
+initiator_url: string | null; 
+/**
+ * Line in `initiator_url` (0-based, as reported by CDP)
+ */
+initiator_line?: number | null; 
+/**
+ * Function in the top stack frame that issued the request
+ */
+initiator_function?: string | null; 
+/**
+ * Initial resource priority assigned by the browser (VeryHigh..VeryLow)
+ */
+priority: string | null; 
+/**
+ * Response MIME type
+ */
+mime_type: string | null; 
+/**
+ * Lowercased CDP resource type (document, script, xhr, fetch, ...)
+ */
+resource_type: string | null; 
+/**
+ * DNS/connect/SSL/wait/download phases; None for cached responses
+ */
+timing?: RequestTiming | null; 
+/**
+ * The request never completed: it stalled past the collector's
+ * timeout or the browser reported `loadingFailed`
+ */
+failed?: boolean; 
+/**
+ * Why loading failed, e.g. `net::ERR_NAME_NOT_RESOLVED` or a blocked
+ * reason; None for stalled requests
+ */
+error_text?: string | null }
+
+/**
+ * A tag and how many sessions carry it
+ */
+export type TagCount = { tag: string; count: number }
+
+/**
+ * Named, reusable set of emulation settings (e.g. "mid-tier phone on 4G")
+ */
+export type TestPreset = { name: string; config: PresetConfig; created_at: number }
+
+/**
+ * Requests started within one fixed window of a session
+ */
+export type ThroughputBucket = { 
+/**
+ * Window start; a multiple of the bucket size
+ */
+start_time: number; request_count: number; 
+/**
+ * Wire bytes of those requests
+ */
+total_bytes: number }
 
 /**
  * Memory trim levels for Android's onTrimMemory callback
@@ -93,33 +1068,186 @@ export type TrimMemoryLevel =
  */
 "Complete"
 
-export type WebView = { socket_name: string; pid: number; package_name: string | null }
+export type WebView = { socket_name: string; kind: WebViewKind; 
+/**
+ * None when the owning process couldn't be found (browser sockets
+ * carry no pid)
+ */
+pid: number | null; package_name: string | null }
+
+/**
+ * What is serving a devtools socket
+ */
+export type WebViewKind = 
+/**
+ * An app's WebView (`webview_devtools_remote_<pid>`)
+ */
+"web_view" | 
+/**
+ * Chrome's main browser socket (`chrome_devtools_remote`) or another
+ * Chromium browser's `<name>_devtools_remote`
+ */
+"chrome" | 
+/**
+ * A second Chrome process with a pid-suffixed socket
+ * (`chrome_devtools_remote_<pid>`), such as one hosting Custom Tabs
+ */
+"custom_tabs"
+
+/**
+ * Browser build behind a target, from `Browser.getVersion` or, failing
+ * that, `/json/version`. WebViews report their Chromium version as
+ * `Chrome/<version>` in `product`.
+ */
+export type WebViewVersion = { product: string; 
+/**
+ * Chromium revision; not reported by `/json/version`
+ */
+revision: string | null; 
+/**
+ * V8 version
+ */
+js_version: string | null; user_agent: string | null; protocol_version: string | null }
+
+/**
+ * Core Web Vitals read back from the page's PerformanceObservers.
+ * Values reset when the page navigates.
+ */
+export type WebVitals = { timestamp: number; 
+/**
+ * Largest Contentful Paint, ms since navigation start
+ */
+lcp_ms: number | null; 
+/**
+ * Cumulative Layout Shift, excluding shifts right after input
+ */
+cls: number; 
+/**
+ * First Input Delay
+ */
+fid_ms: number | null; 
+/**
+ * Slowest interaction seen (upper bound on Interaction to Next Paint)
+ */
+inp_ms: number | null }
 
-const ARGS_MAP = { 'api':'{"connect_cdp":["ws_url"],"create_session":["params"],"delete_session":["session_id"],"disconnect_cdp":[],"end_session":["session_id"],"get_cdp_state":[],"get_cdp_targets":["port"],"get_device_meminfo":["device_id"],"get_devices":[],"get_performance_metrics":[],"get_session":["session_id"],"get_session_metrics":["session_id","metric_type","start_time","end_time","limit"],"get_session_network_requests":["session_id","limit"],"get_webviews":["device_id"],"list_sessions":["limit"],"search_sessions":["query","device_id","status","tags","limit"],"send_trim_memory":["device_id","package_name","level"],"start_metrics_collection":["poll_interval_ms"],"start_port_forward":["device_id","socket_name","local_port"],"stop_all_port_forwards":["device_id"],"stop_metrics_collection":[],"stop_port_forward":["device_id","local_port"],"update_session_name":["session_id","display_name"],"update_session_tags":["session_id","tags"]}' }
-export type Router = { "api": {connect_cdp: (wsUrl: string) => Promise<null>, 
-create_session: (params: CreateSessionParams) => Promise<Session>, 
+const ARGS_MAP = { 'api':'{"abort_session":["session_id","reason"],"add_annotation":["session_id","label","color","timestamp"],"add_tag_to_sessions":["session_ids","tag"],"apply_network_throttle":["preset","custom","connection_id"],"apply_preset":["name","connection_id"],"capture_heap_snapshot":["connection_id"],"check_cdp_health":["connection_id"],"compact_database":[],"compare_sessions":["session_a","session_b"],"connect_browser":["host","port"],"connect_cdp":["ws_url","create_blank_page","timeout_ms","retries","page_attempts","page_retry_delay_ms","connection_id"],"connect_wireless_device":["host","port"],"count_sessions":["filter"],"create_session":["params","connection_id","activate"],"delete_annotation":["id"],"delete_session":["session_id"],"delete_sessions":["session_ids"],"disconnect_cdp":["connection_id"],"disconnect_wireless_device":["host","port"],"end_session":["session_id"],"export_har":["session_id"],"export_metrics_ndjson":["session_id","path"],"export_session_bundle":["session_id"],"export_session_bundle_to_file":["session_id","path"],"export_session_csv":["session_id","kind"],"fetch_response_body":["request_id","connection_id"],"get_active_session":[],"get_adb_version":[],"get_alerts":["session_id"],"get_all_tags":[],"get_annotations":["session_id"],"get_cdp_state":["connection_id"],"get_cdp_targets":["port"],"get_collection_stats":["connection_id"],"get_collector_status":["connection_id"],"get_console_logs":["session_id","level_filter"],"get_cpu_metrics":["session_id","start_time","end_time","limit"],"get_current_snapshot":["connection_id"],"get_database_path":[],"get_default_device":[],"get_device_info":["device_id"],"get_device_meminfo":["device_id"],"get_devices":["connected_only"],"get_devtools_url":["port","target_id","local_port"],"get_duplicate_requests":["session_id","normalize"],"get_enriched_targets":["device_id","socket_name","port"],"get_frame_stats":["session_id","start_time","end_time","limit"],"get_layout_metrics":["connection_id"],"get_live_counters":["connection_id"],"get_metrics_summary":["session_id","metric_type","field","buckets"],"get_network_by_host":["session_id"],"get_network_summary":["session_id","normalize"],"get_network_throughput":["session_id","bucket_ms"],"get_performance_metrics":["include_all","connection_id"],"get_raw_performance_metrics":["connection_id"],"get_request_transfer_timeline":["session_id","request_id"],"get_response_body":["session_id","request_id"],"get_schema_version":[],"get_session":["session_id"],"get_session_counts":["session_id"],"get_session_detail":["session_id","metric_limit","request_limit"],"get_session_markers":["session_id"],"get_session_metrics":["session_id","metric_types","start_time","end_time","limit","max_points"],"get_session_network_requests":["session_id","filter","limit"],"get_session_network_requests_page":["session_id","filter","cursor","limit"],"get_session_security_events":["session_id"],"get_settings":[],"get_smoothed_metrics":["session_id","metric_type","field","options"],"get_web_vitals":["session_id","start_time","end_time","limit"],"get_webview_version":["port","connection_id"],"get_webviews":["device_id"],"import_session_bundle":["bundle"],"import_session_bundle_file":["path"],"list_active_forwards":["device_id"],"list_presets":[],"list_sessions":["limit"],"open_database":["path"],"pair_wireless_device":["host","port","code"],"pause_metrics_collection":["connection_id"],"poll_events":["since_seq","limit","connection_id"],"prune_database":["retention_days"],"remove_tag_from_sessions":["session_ids","tag"],"resume_metrics_collection":["connection_id"],"resume_session":["session_id","connection_id"],"save_preset":["name","config"],"search_sessions":["filter"],"send_trim_memory":["device_id","package_name","level"],"set_active_session":["session_id","connection_id"],"set_adb_path":["path"],"set_adb_server":["host","port"],"set_alert_thresholds":["thresholds"],"set_auto_reconnect":["enabled","connection_id"],"set_screenshot_interval":["every_n_polls","connection_id"],"start_cpu_profile":["sampling_interval_us","connection_id"],"start_device_watch":[],"start_frame_capture":["duration_ms","connection_id"],"start_js_coverage":["connection_id"],"start_metrics_collection":["poll_interval_ms","max_samples","emit_interval_ms","connection_id"],"start_port_forward":["device_id","socket_name","local_port"],"start_port_forward_auto":["device_id","socket_name"],"start_screencast":["quality","max_width","every_nth_frame","connection_id"],"stop_all_port_forwards":["device_id"],"stop_cpu_profile":["connection_id"],"stop_device_watch":[],"stop_js_coverage":["connection_id"],"stop_metrics_collection":["connection_id"],"stop_port_forward":["device_id","local_port"],"stop_screencast":["connection_id"],"switch_target":["ws_url","connection_id"],"take_screenshot":["connection_id"],"update_poll_interval":["poll_interval_ms","connection_id"],"update_session_from_target":["session_id","connection_id"],"update_session_metadata":["session_id","metadata"],"update_session_name":["session_id","display_name"],"update_session_tags":["session_id","tags"],"update_settings":["settings"]}' }
+export type Router = { "api": {abort_session: (sessionId: string | null, reason: string | null) => Promise<null>, 
+add_annotation: (sessionId: string | null, label: string, color: string | null, timestamp: number | null) => Promise<Annotation>, 
+add_tag_to_sessions: (sessionIds: string[], tag: string) => Promise<BatchResult>, 
+apply_network_throttle: (preset: string | null, custom: NetworkThrottle | null, connectionId: string | null) => Promise<NetworkThrottle>, 
+apply_preset: (name: string, connectionId: string | null) => Promise<TestPreset>, 
+capture_heap_snapshot: (connectionId: string | null) => Promise<string>, 
+check_cdp_health: (connectionId: string | null) => Promise<number>, 
+compact_database: () => Promise<CompactResult>, 
+compare_sessions: (sessionA: string, sessionB: string) => Promise<SessionComparison>, 
+connect_browser: (host: string, port: number) => Promise<CdpTarget[]>, 
+connect_cdp: (wsUrl: string, createBlankPage: boolean | null, timeoutMs: number | null, retries: number | null, pageAttempts: number | null, pageRetryDelayMs: number | null, connectionId: string | null) => Promise<string>, 
+connect_wireless_device: (host: string, port: number) => Promise<string>, 
+count_sessions: (filter: SessionSearchFilter) => Promise<number>, 
+create_session: (params: CreateSessionParams, connectionId: string | null, activate: boolean | null) => Promise<Session>, 
+delete_annotation: (id: number) => Promise<null>, 
 delete_session: (sessionId: string) => Promise<null>, 
-disconnect_cdp: () => Promise<null>, 
+delete_sessions: (sessionIds: string[]) => Promise<BatchResult>, 
+disconnect_cdp: (connectionId: string | null) => Promise<null>, 
+disconnect_wireless_device: (host: string, port: number) => Promise<null>, 
 end_session: (sessionId: string | null) => Promise<null>, 
-get_cdp_state: () => Promise<ConnectionState>, 
+export_har: (sessionId: string) => Promise<string>, 
+export_metrics_ndjson: (sessionId: string, path: string) => Promise<number>, 
+export_session_bundle: (sessionId: string) => Promise<string>, 
+export_session_bundle_to_file: (sessionId: string, path: string) => Promise<null>, 
+export_session_csv: (sessionId: string, kind: CsvExportKind) => Promise<string>, 
+fetch_response_body: (requestId: string, connectionId: string | null) => Promise<ResponseBodyContent>, 
+get_active_session: () => Promise<Session | null>, 
+get_adb_version: () => Promise<AdbVersion>, 
+get_alerts: (sessionId: string) => Promise<Alert[]>, 
+get_all_tags: () => Promise<TagCount[]>, 
+get_annotations: (sessionId: string) => Promise<Annotation[]>, 
+get_cdp_state: (connectionId: string | null) => Promise<ConnectionState>, 
 get_cdp_targets: (port: number) => Promise<CdpTarget[]>, 
+get_collection_stats: (connectionId: string | null) => Promise<CollectionStats>, 
+get_collector_status: (connectionId: string | null) => Promise<CollectorStatus>, 
+get_console_logs: (sessionId: string, levelFilter: ConsoleLevel[] | null) => Promise<ConsoleLog[]>, 
+get_cpu_metrics: (sessionId: string, startTime: number | null, endTime: number | null, limit: number | null) => Promise<CpuUsage[]>, 
+get_current_snapshot: (connectionId: string | null) => Promise<MetricsSnapshot>, 
+get_database_path: () => Promise<string>, 
+get_default_device: () => Promise<Device>, 
+get_device_info: (deviceId: string) => Promise<DeviceProps>, 
 get_device_meminfo: (deviceId: string) => Promise<MemoryInfo>, 
-get_devices: () => Promise<Device[]>, 
-get_performance_metrics: () => Promise<PerformanceMetrics>, 
+get_devices: (connectedOnly: boolean | null) => Promise<Device[]>, 
+get_devtools_url: (port: number, targetId: string, localPort: number) => Promise<string>, 
+get_duplicate_requests: (sessionId: string, normalize: boolean | null) => Promise<DuplicateRequestGroup[]>, 
+get_enriched_targets: (deviceId: string, socketName: string, port: number) => Promise<EnrichedTarget[]>, 
+get_frame_stats: (sessionId: string, startTime: number | null, endTime: number | null, limit: number | null) => Promise<FrameStats[]>, 
+get_layout_metrics: (connectionId: string | null) => Promise<LayoutMetrics>, 
+get_live_counters: (connectionId: string | null) => Promise<LiveCounters>, 
+get_metrics_summary: (sessionId: string, metricType: MetricType, field: string, buckets: number | null) => Promise<MetricsSummary>, 
+get_network_by_host: (sessionId: string) => Promise<HostNetworkSummary[]>, 
+get_network_summary: (sessionId: string, normalize: boolean | null) => Promise<NetworkSummary>, 
+get_network_throughput: (sessionId: string, bucketMs: number | null) => Promise<ThroughputBucket[]>, 
+get_performance_metrics: (includeAll: boolean, connectionId: string | null) => Promise<PerformanceMetrics>, 
+get_raw_performance_metrics: (connectionId: string | null) => Promise<[string, number][]>, 
+get_request_transfer_timeline: (sessionId: string, requestId: string) => Promise<RequestDataChunk[]>, 
+get_response_body: (sessionId: string, requestId: string) => Promise<ResponseBody | null>, 
+get_schema_version: () => Promise<number>, 
 get_session: (sessionId: string) => Promise<Session | null>, 
-get_session_metrics: (sessionId: string, metricType: string | null, startTime: number | null, endTime: number | null, limit: number | null) => Promise<StoredMetric[]>, 
-get_session_network_requests: (sessionId: string, limit: number | null) => Promise<StoredNetworkRequest[]>, 
+get_session_counts: (sessionId: string) => Promise<SessionCounts>, 
+get_session_detail: (sessionId: string, metricLimit: number | null, requestLimit: number | null) => Promise<SessionDetail>, 
+get_session_markers: (sessionId: string) => Promise<SessionMarker[]>, 
+get_session_metrics: (sessionId: string, metricTypes: MetricType[] | null, startTime: number | null, endTime: number | null, limit: number | null, maxPoints: number | null) => Promise<StoredMetric[]>, 
+get_session_network_requests: (sessionId: string, filter: NetworkRequestFilter | null, limit: number | null) => Promise<StoredNetworkRequest[]>, 
+get_session_network_requests_page: (sessionId: string, filter: NetworkRequestFilter | null, cursor: NetworkRequestCursor | null, limit: number) => Promise<NetworkRequestPage>, 
+get_session_security_events: (sessionId: string) => Promise<SecurityEvent[]>, 
+get_settings: () => Promise<Settings>, 
+get_smoothed_metrics: (sessionId: string, metricType: MetricType, field: string, options: SmoothingOptions) => Promise<SmoothedSeries>, 
+get_web_vitals: (sessionId: string, startTime: number | null, endTime: number | null, limit: number | null) => Promise<WebVitals[]>, 
+get_webview_version: (port: number | null, connectionId: string | null) => Promise<WebViewVersion>, 
 get_webviews: (deviceId: string) => Promise<WebView[]>, 
+import_session_bundle: (bundle: string) => Promise<Session>, 
+import_session_bundle_file: (path: string) => Promise<Session>, 
+list_active_forwards: (deviceId: string | null) => Promise<PortForward[]>, 
+list_presets: () => Promise<TestPreset[]>, 
 list_sessions: (limit: number | null) => Promise<Session[]>, 
-search_sessions: (query: string | null, deviceId: string | null, status: string | null, tags: string[] | null, limit: number | null) => Promise<Session[]>, 
+open_database: (path: string) => Promise<string>, 
+pair_wireless_device: (host: string, port: number, code: string) => Promise<null>, 
+pause_metrics_collection: (connectionId: string | null) => Promise<null>, 
+poll_events: (sinceSeq: number | null, limit: number | null, connectionId: string | null) => Promise<EventPage>, 
+prune_database: (retentionDays: number) => Promise<PruneResult>, 
+remove_tag_from_sessions: (sessionIds: string[], tag: string) => Promise<BatchResult>, 
+resume_metrics_collection: (connectionId: string | null) => Promise<null>, 
+resume_session: (sessionId: string, connectionId: string | null) => Promise<Session>, 
+save_preset: (name: string, config: PresetConfig) => Promise<TestPreset>, 
+search_sessions: (filter: SessionSearchFilter) => Promise<Session[]>, 
 send_trim_memory: (deviceId: string, packageName: string, level: TrimMemoryLevel) => Promise<null>, 
-start_metrics_collection: (pollIntervalMs: number | null) => Promise<null>, 
+set_active_session: (sessionId: string, connectionId: string | null) => Promise<Session>, 
+set_adb_path: (path: string | null) => Promise<AdbVersion>, 
+set_adb_server: (host: string | null, port: number | null) => Promise<Device[]>, 
+set_alert_thresholds: (thresholds: AlertThresholds) => Promise<null>, 
+set_auto_reconnect: (enabled: boolean, connectionId: string | null) => Promise<null>, 
+set_screenshot_interval: (everyNPolls: number, connectionId: string | null) => Promise<null>, 
+start_cpu_profile: (samplingIntervalUs: number | null, connectionId: string | null) => Promise<null>, 
+start_device_watch: () => Promise<null>, 
+start_frame_capture: (durationMs: number, connectionId: string | null) => Promise<FrameStats>, 
+start_js_coverage: (connectionId: string | null) => Promise<null>, 
+start_metrics_collection: (pollIntervalMs: number | null, maxSamples: number | null, emitIntervalMs: number | null, connectionId: string | null) => Promise<null>, 
 start_port_forward: (deviceId: string, socketName: string, localPort: number) => Promise<PortForwardResult>, 
+start_port_forward_auto: (deviceId: string, socketName: string) => Promise<PortForwardResult>, 
+start_screencast: (quality: number | null, maxWidth: number | null, everyNthFrame: number | null, connectionId: string | null) => Promise<null>, 
 stop_all_port_forwards: (deviceId: string) => Promise<null>, 
-stop_metrics_collection: () => Promise<null>, 
+stop_cpu_profile: (connectionId: string | null) => Promise<string>, 
+stop_device_watch: () => Promise<null>, 
+stop_js_coverage: (connectionId: string | null) => Promise<JsCoverage>, 
+stop_metrics_collection: (connectionId: string | null) => Promise<null>, 
 stop_port_forward: (deviceId: string, localPort: number) => Promise<null>, 
+stop_screencast: (connectionId: string | null) => Promise<null>, 
+switch_target: (wsUrl: string, connectionId: string | null) => Promise<null>, 
+take_screenshot: (connectionId: string | null) => Promise<Screenshot>, 
+update_poll_interval: (pollIntervalMs: number, connectionId: string | null) => Promise<null>, 
+update_session_from_target: (sessionId: string | null, connectionId: string | null) => Promise<Session>, 
+update_session_metadata: (sessionId: string, metadata: Partial<{ [key in string]: JsonValue }> | null) => Promise<null>, 
 update_session_name: (sessionId: string, displayName: string | null) => Promise<null>, 
-update_session_tags: (sessionId: string, tags: string[] | null) => Promise<null>} };
+update_session_tags: (sessionId: string, tags: string[] | null) => Promise<null>, 
+update_settings: (settings: Settings) => Promise<Settings>} };
 
 
 export const createTauRPCProxy = () => createProxy<Router>(ARGS_MAP)
@@ -1,6 +1,7 @@
 import { useState, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import type { Session, StoredMetric, StoredNetworkRequest } from "../types";
+import { errorMessage } from "../utils";
 
 interface ExportImportProps {
   session?: Session;
@@ -52,7 +53,7 @@ export function ExportDialog({ session, onClose }: ExportImportProps) {
 
       setSuccess(true);
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e));
+      setError(errorMessage(e));
     } finally {
       setExporting(false);
     }
@@ -149,6 +150,8 @@ export function ImportDialog({ onClose, onImportComplete }: ImportDialogProps) {
           target_title: `[Imported] ${data.session.target_title || "Session"}`,
           webview_url: data.session.webview_url,
         },
+        // Importing shouldn't take over the session being recorded
+        activate: false,
       });
 
       // Note: In a full implementation, you would also import the metrics
@@ -158,7 +161,7 @@ export function ImportDialog({ onClose, onImportComplete }: ImportDialogProps) {
       setSuccess(true);
       onImportComplete?.();
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e));
+      setError(errorMessage(e));
     } finally {
       setImporting(false);
     }
@@ -6,6 +6,7 @@ import {
   type DeviceProfile,
   type TrimMemoryLevel,
 } from "@/types/deviceProfiles";
+import { errorMessage } from "@/utils";
 
 interface MemoryInfo {
   total_kb: number;
@@ -50,7 +51,7 @@ export function MemorySimulationPanel({
       setMemoryInfo(info);
       setError(null);
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e));
+      setError(errorMessage(e));
     }
   }, [deviceId, onGetMeminfo]);
 
@@ -61,7 +62,7 @@ export function MemorySimulationPanel({
       setPressureCount((c) => c + 1);
       setError(null);
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e));
+      setError(errorMessage(e));
     }
   }, [deviceId, packageName, selectedProfile.trimLevel, onSendTrimMemory]);
 
@@ -36,7 +36,8 @@ export function SessionDetail({
       // Load metrics
       const storedMetrics = await taurpc.api.get_session_metrics(
         session.id,
-        "performance",
+        ["performance"],
+        null,
         null,
         null,
         null
@@ -53,12 +54,17 @@ export function SessionDetail({
           layout_count: data.layout_count ?? null,
           script_duration: data.script_duration ?? null,
           task_duration: data.task_duration ?? null,
+          js_event_listeners: data.js_event_listeners ?? null,
+          documents: data.documents ?? null,
+          frames: data.frames ?? null,
+          recalc_style_count: data.recalc_style_count ?? null,
+          extras: data.extras ?? {},
         };
       });
       setMetrics(parsedMetrics);
 
       // Load network requests
-      const requests = await taurpc.api.get_session_network_requests(session.id, null);
+      const requests = await taurpc.api.get_session_network_requests(session.id, null, null);
       setNetworkRequests(requests);
     } catch (e) {
       console.error("Failed to load session data:", e);
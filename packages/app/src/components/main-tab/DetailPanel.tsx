@@ -115,7 +115,7 @@ export function DetailPanel({
                 {webviews.slice(0, 5).map((wv) => (
                   <div key={wv.socket_name} className="text-xs">
                     <span className="text-gray-300">
-                      {wv.package_name || `PID ${wv.pid}`}
+                      {wv.package_name || (wv.pid ? `PID ${wv.pid}` : wv.socket_name)}
                     </span>
                   </div>
                 ))}
@@ -151,7 +151,7 @@ export function DetailPanel({
                 </div>
                 <div className="flex justify-between">
                   <span className="text-gray-400">PID</span>
-                  <span className="text-gray-200">{webview.pid}</span>
+                  <span className="text-gray-200">{webview.pid ?? "Unknown"}</span>
                 </div>
                 <div className="flex justify-between">
                   <span className="text-gray-400">Socket</span>
@@ -204,7 +204,7 @@ export function TreePanel({
                                     />
                                     <Globe className="w-3.5 h-3.5 text-blue-400 shrink-0" />
                                     <span className="text-xs text-gray-300 truncate">
-                                      {webview.package_name || `PID ${webview.pid}`}
+                                      {webview.package_name || (webview.pid ? `PID ${webview.pid}` : webview.socket_name)}
                                     </span>
                                   </button>
 
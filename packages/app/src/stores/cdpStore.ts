@@ -15,6 +15,10 @@ interface CdpState {
   selectedTarget: CdpTarget | null;
   setSelectedTarget: (target: CdpTarget | null) => void;
 
+  // Backend connection id returned by connect_cdp
+  connectionId: string | null;
+  setConnectionId: (id: string | null) => void;
+
   // Active port
   activePort: number | null;
   setActivePort: (port: number | null) => void;
@@ -37,6 +41,10 @@ export const useCdpStore = create<CdpState>((set) => ({
   selectedTarget: null,
   setSelectedTarget: (selectedTarget) => set({ selectedTarget }),
 
+  // Backend connection id
+  connectionId: null,
+  setConnectionId: (connectionId) => set({ connectionId }),
+
   // Active port
   activePort: null,
   setActivePort: (activePort) => set({ activePort }),
@@ -47,6 +55,7 @@ export const useCdpStore = create<CdpState>((set) => ({
       cdpTargets: [],
       connectionState: "Disconnected",
       selectedTarget: null,
+      connectionId: null,
       activePort: null,
     }),
 }));
@@ -17,6 +17,14 @@ export interface NetworkEvent {
   timestamp?: number;
 }
 
+// Payload of every metrics:* event; says which connection and session
+// the event came from
+export interface CollectorEvent<T> {
+  connection_id: string;
+  session_id: string;
+  payload: T;
+}
+
 // Re-export types from bindings for convenience
 export type {
   Device,
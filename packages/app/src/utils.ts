@@ -1,3 +1,15 @@
+/**
+ * Message to show for a rejected procedure call; procedures reject with
+ * an `ApiError` ({ kind, message }).
+ */
+export function errorMessage(e: unknown): string {
+  if (e instanceof Error) return e.message;
+  if (typeof e === "object" && e !== null && "message" in e) {
+    return String((e as { message: unknown }).message);
+  }
+  return String(e);
+}
+
 export function formatBytes(bytes: number | null): string {
   if (bytes === null) return "-";
   if (bytes < 1024) return `${bytes} B`;